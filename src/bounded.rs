@@ -0,0 +1,313 @@
+//! Length-bounded XDR types: `string<N>`, `opaque<N>` and `T<N>` (RFC 4506 §4.10–4.13).
+//!
+//! XDR IDL lets a protocol declare a maximum size for variable-length data:
+//!
+//! ```text
+//! string name<255>;
+//! opaque data<1024>;
+//! int    gids<16>;
+//! ```
+//!
+//! The wrappers in this module carry that bound in the type. The bound is
+//! checked when a value is constructed, again by the [`Serializer`] before the
+//! length prefix is written, and by both deserializers as soon as the length
+//! prefix is read — before anything is allocated. Violations are reported as
+//! [`Error::LengthOverflow`].
+//!
+//! ```rust
+//! use serde::{Deserialize, Serialize};
+//! use xdr_serde::bounded::{BoundedBytes, BoundedString, BoundedVec};
+//! use xdr_serde::{Error, from_bytes, to_bytes};
+//!
+//! #[derive(Debug, PartialEq, Serialize, Deserialize)]
+//! struct AuthSys {
+//!     machine_name: BoundedString<255>,
+//!     gids: BoundedVec<u32, 16>,
+//!     cookie: BoundedBytes<8>,
+//! }
+//!
+//! let auth = AuthSys {
+//!     machine_name: BoundedString::new("client".to_string()).unwrap(),
+//!     gids: BoundedVec::new(vec![100, 101]).unwrap(),
+//!     cookie: BoundedBytes::new(vec![0xAA; 8]).unwrap(),
+//! };
+//! let bytes = to_bytes(&auth).unwrap();
+//! assert_eq!(auth, from_bytes(&bytes).unwrap());
+//!
+//! assert_eq!(
+//!     BoundedVec::<u32, 16>::new(vec![0; 17]),
+//!     Err(Error::LengthOverflow { max: 16, got: 17 }),
+//! );
+//! ```
+//!
+//! # Plain fields
+//!
+//! A `String`, `Vec<u8>` (with `serde_bytes`) or `Vec<T>` field can be bounded
+//! without changing its type through [`serialize`] and [`deserialize`]:
+//!
+//! ```rust
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Entry {
+//!     #[serde(
+//!         serialize_with = "xdr_serde::bounded::serialize::<255, _, _>",
+//!         deserialize_with = "xdr_serde::bounded::deserialize::<255, _, _>"
+//!     )]
+//!     name: String,
+//! }
+//! ```
+//!
+//! # Wire format
+//!
+//! Bounds do not change the encoding: a `BoundedString<N>` is encoded exactly
+//! like a `String`. The bound is passed to the XDR (de)serializer through a
+//! single-field tuple struct named [`BOUNDED_TOKEN`](crate::BOUNDED_TOKEN),
+//! whose length argument carries `N`.
+//!
+//! [`Serializer`]: crate::Serializer
+//! [`Error::LengthOverflow`]: crate::Error::LengthOverflow
+
+use crate::error::Error;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use core::marker::PhantomData;
+use core::ops::Deref;
+use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeTupleStruct, Serializer};
+
+fn check_bound(max: u32, len: usize) -> Result<(), Error> {
+    if len > max as usize {
+        return Err(Error::LengthOverflow {
+            max,
+            got: u32::try_from(len).unwrap_or(u32::MAX),
+        });
+    }
+    Ok(())
+}
+
+// ── serialize_with / deserialize_with functions ───────────────────────────
+
+/// Serialize `value` with an upper bound of `N` on its length prefix.
+///
+/// `T` must serialize as an XDR string, variable-length opaque or
+/// variable-length array; the bound applies to its length prefix.
+pub fn serialize<const N: u32, T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: Serialize + ?Sized,
+    S: Serializer,
+{
+    let mut ts = serializer.serialize_tuple_struct(crate::BOUNDED_TOKEN, N as usize)?;
+    ts.serialize_field(value)?;
+    ts.end()
+}
+
+/// Deserialize a value whose length prefix may not exceed `N`.
+///
+/// The XDR deserializers reject an oversized length prefix before reading or
+/// allocating the data that follows it.
+pub fn deserialize<'de, const N: u32, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_tuple_struct(
+        crate::BOUNDED_TOKEN,
+        N as usize,
+        BoundedVisitor::<N, T>(PhantomData),
+    )
+}
+
+struct BoundedVisitor<const N: u32, T>(PhantomData<T>);
+
+impl<'de, const N: u32, T: Deserialize<'de>> Visitor<'de> for BoundedVisitor<N, T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a value of at most {} elements", N)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<T, A::Error> {
+        seq.next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))
+    }
+}
+
+// ── Bounded wrapper types ─────────────────────────────────────────────────
+
+macro_rules! bounded_common {
+    ($name:ident [$($param:ident)?], $inner:ty, $target:ty) => {
+        impl<$($param,)? const N: u32> $name<$($param,)? N> {
+            /// The declared maximum length.
+            pub const MAX: u32 = N;
+
+            /// Wrap `value`, returning [`Error::LengthOverflow`] if it exceeds `N`.
+            pub fn new(value: $inner) -> Result<Self, Error> {
+                check_bound(N, value.len())?;
+                Ok($name(value))
+            }
+
+            /// Unwrap into the inner value.
+            pub fn into_inner(self) -> $inner {
+                self.0
+            }
+        }
+
+        impl<$($param,)? const N: u32> Deref for $name<$($param,)? N> {
+            type Target = $target;
+            fn deref(&self) -> &$target {
+                &self.0
+            }
+        }
+
+        impl<$($param,)? const N: u32> TryFrom<$inner> for $name<$($param,)? N> {
+            type Error = Error;
+            fn try_from(value: $inner) -> Result<Self, Error> {
+                Self::new(value)
+            }
+        }
+
+        impl<$($param,)? const N: u32> From<$name<$($param,)? N>> for $inner {
+            fn from(value: $name<$($param,)? N>) -> $inner {
+                value.0
+            }
+        }
+    };
+}
+
+/// An XDR `string<N>`: a UTF-8 string of at most `N` bytes.
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BoundedString<const N: u32>(String);
+
+bounded_common!(BoundedString[], String, str);
+
+impl<const N: u32> BoundedString<N> {
+    /// Borrow the string contents.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl<const N: u32> TryFrom<&str> for BoundedString<N> {
+    type Error = Error;
+    fn try_from(value: &str) -> Result<Self, Error> {
+        Self::new(value.to_string())
+    }
+}
+
+impl<const N: u32> Serialize for BoundedString<N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize::<N, _, _>(self.0.as_str(), serializer)
+    }
+}
+
+impl<'de, const N: u32> Deserialize<'de> for BoundedString<N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s: String = deserialize::<N, _, _>(deserializer)?;
+        Self::new(s).map_err(de::Error::custom)
+    }
+}
+
+/// An XDR `opaque<N>`: variable-length opaque data of at most `N` bytes.
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BoundedBytes<const N: u32>(Vec<u8>);
+
+bounded_common!(BoundedBytes[], Vec<u8>, [u8]);
+
+impl<const N: u32> BoundedBytes<N> {
+    /// Borrow the raw bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl<const N: u32> TryFrom<&[u8]> for BoundedBytes<N> {
+    type Error = Error;
+    fn try_from(value: &[u8]) -> Result<Self, Error> {
+        Self::new(value.to_vec())
+    }
+}
+
+impl<const N: u32> Serialize for BoundedBytes<N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize::<N, _, _>(&Bytes(&self.0), serializer)
+    }
+}
+
+impl<'de, const N: u32> Deserialize<'de> for BoundedBytes<N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let ByteBuf(v) = deserialize::<N, _, _>(deserializer)?;
+        Self::new(v).map_err(de::Error::custom)
+    }
+}
+
+/// An XDR `T<N>`: a variable-length array of at most `N` elements.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BoundedVec<T, const N: u32>(Vec<T>);
+
+bounded_common!(BoundedVec[T], Vec<T>, [T]);
+
+impl<T, const N: u32> Default for BoundedVec<T, N> {
+    fn default() -> Self {
+        BoundedVec(Vec::new())
+    }
+}
+
+impl<T: Serialize, const N: u32> Serialize for BoundedVec<T, N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize::<N, _, _>(&self.0, serializer)
+    }
+}
+
+impl<'de, T: Deserialize<'de>, const N: u32> Deserialize<'de> for BoundedVec<T, N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let v: Vec<T> = deserialize::<N, _, _>(deserializer)?;
+        Self::new(v).map_err(de::Error::custom)
+    }
+}
+
+// ── Internal byte helpers ─────────────────────────────────────────────────
+
+/// Routes a byte slice through `serialize_bytes` (XDR opaque) rather than
+/// serde's default sequence-of-u8 encoding.
+struct Bytes<'a>(&'a [u8]);
+
+impl Serialize for Bytes<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(self.0)
+    }
+}
+
+/// Receives XDR opaque data via `deserialize_byte_buf`.
+pub(crate) struct ByteBuf(pub(crate) Vec<u8>);
+
+impl<'de> Deserialize<'de> for ByteBuf {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_byte_buf(ByteBufVisitor)
+    }
+}
+
+struct ByteBufVisitor;
+
+impl<'de> Visitor<'de> for ByteBufVisitor {
+    type Value = ByteBuf;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "variable-length opaque data")
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<ByteBuf, E> {
+        Ok(ByteBuf(v.to_vec()))
+    }
+    fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<ByteBuf, E> {
+        Ok(ByteBuf(v))
+    }
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<ByteBuf, A::Error> {
+        let mut buf = Vec::new();
+        while let Some(b) = seq.next_element::<u8>()? {
+            buf.push(b);
+        }
+        Ok(ByteBuf(buf))
+    }
+}
@@ -6,12 +6,54 @@
 //!   directly from the input buffer when possible.
 //! - [`ReaderDeserializer`] — `io::Read`-based deserializer. Uses `read_exact` internally;
 //!   all string/byte outputs are owned. Use this when reading from a socket, file, etc.
+//!   Requires the `std` feature.
 
 use crate::error::{Error, Result};
+use crate::limits::DecodeLimits;
+use alloc::string::String;
+use alloc::vec::Vec;
+#[cfg(feature = "futures")]
+use futures_util::io::{AsyncBufRead, AsyncBufReadExt};
 use serde::de::{
     self, Deserialize, DeserializeOwned, EnumAccess, MapAccess, SeqAccess, VariantAccess, Visitor,
 };
+#[cfg(feature = "std")]
 use std::io::Read;
+#[cfg(feature = "tokio")]
+use tokio::io::{AsyncBufRead as TokioAsyncBufRead, AsyncBufReadExt as _};
+
+/// Reads larger than this are grown incrementally by [`ReaderDeserializer`],
+/// so a forged length prefix cannot force a large up-front allocation.
+#[cfg(feature = "std")]
+const READ_CHUNK: usize = 64 * 1024;
+
+pub(crate) fn length_overflow(max: usize, got: usize) -> Error {
+    Error::LengthOverflow {
+        max: u32::try_from(max).unwrap_or(u32::MAX),
+        got: u32::try_from(got).unwrap_or(u32::MAX),
+    }
+}
+
+/// Check a decoded length prefix against a configured maximum and any
+/// pending `<N>` bound.
+fn check_len(len: u32, max: u32, bound: Option<u32>) -> Result<usize> {
+    let max = bound.map_or(max, |b| b.min(max));
+    if len > max {
+        return Err(Error::LengthOverflow { max, got: len });
+    }
+    Ok(len as usize)
+}
+
+/// Check the next element of a linked list against `max` nodes, given the
+/// `read` elements before it: items are the odd elements, each after a
+/// pointer.
+fn check_list_node(read: usize, max: u32) -> Result<()> {
+    let node = read / 2 + 1;
+    if read % 2 == 1 && node > max as usize {
+        return Err(length_overflow(max as usize, node));
+    }
+    Ok(())
+}
 
 // ── Slice-based entry points ───────────────────────────────────────────────
 
@@ -44,11 +86,124 @@ pub fn from_bytes_partial<'de, T: Deserialize<'de>>(input: &'de [u8]) -> Result<
 /// Only the bytes necessary to decode `T` are consumed from `reader`.
 /// Wrap the reader in a [`std::io::BufReader`] for better performance over
 /// sockets or files.
+#[cfg(feature = "std")]
 pub fn from_reader<R: Read, T: DeserializeOwned>(reader: R) -> Result<T> {
     let mut de = ReaderDeserializer::new(reader);
     T::deserialize(&mut de)
 }
 
+// ── Async entry points ─────────────────────────────────────────────────────
+
+/// Deserialize a value from a [`futures::io::AsyncBufRead`](AsyncBufRead).
+///
+/// Requires the `futures` feature.
+///
+/// The async counterpart of [`from_reader`]: only the bytes necessary to
+/// decode `T` are consumed, and a stream that ends early fails with
+/// [`Error::UnexpectedEof`]. A buffered reader is required so that bytes
+/// following the value can be left unconsumed; wrap an `AsyncRead` in a
+/// `futures::io::BufReader`.
+///
+/// The value is decoded with the slice [`Deserializer`] once enough input has
+/// been buffered, retrying as more arrives. Length and count prefixes set how
+/// much must arrive before the next retry, so a large array or opaque is
+/// decoded once rather than after every refill.
+#[cfg(feature = "futures")]
+pub async fn from_async_reader<R, T>(mut reader: R) -> Result<T>
+where
+    R: AsyncBufRead + Unpin,
+    T: DeserializeOwned,
+{
+    let mut decode = AsyncDecode::new();
+    loop {
+        let available = reader.fill_buf().await?;
+        match decode.feed(available)? {
+            Progress::Done(value, n) => {
+                reader.consume_unpin(n);
+                return Ok(value);
+            }
+            Progress::More(n) => reader.consume_unpin(n),
+        }
+    }
+}
+
+/// Deserialize a value from a [`tokio::io::AsyncBufRead`](TokioAsyncBufRead).
+///
+/// Requires the `tokio` feature. Behaves as [`from_async_reader`]; wrap an
+/// `AsyncRead` in a `tokio::io::BufReader`.
+#[cfg(feature = "tokio")]
+pub async fn from_tokio_reader<R, T>(mut reader: R) -> Result<T>
+where
+    R: TokioAsyncBufRead + Unpin,
+    T: DeserializeOwned,
+{
+    let mut decode = AsyncDecode::new();
+    loop {
+        let available = reader.fill_buf().await?;
+        match decode.feed(available)? {
+            Progress::Done(value, n) => {
+                reader.consume(n);
+                return Ok(value);
+            }
+            Progress::More(n) => reader.consume(n),
+        }
+    }
+}
+
+/// The outcome of [`AsyncDecode::feed`]: consume `n` of the bytes offered,
+/// and either stop with the value or offer more.
+#[cfg(any(feature = "futures", feature = "tokio"))]
+enum Progress<T> {
+    Done(T, usize),
+    More(usize),
+}
+
+/// Incremental decoding over a buffered async reader.
+///
+/// Consumed bytes are kept in `buf`. Each call is offered the reader's
+/// current buffer and a decode is attempted over both. Offered bytes are
+/// consumed only as far as the value is known to extend, so nothing after it
+/// is taken from the reader.
+///
+/// A failed attempt leaves a lower bound on the encoded length, taken from
+/// the length and count prefixes read so far, and no attempt is made until
+/// that much is buffered. A large opaque, string or array is therefore
+/// decoded once it has arrived rather than again after every refill.
+#[cfg(any(feature = "futures", feature = "tokio"))]
+struct AsyncDecode {
+    buf: Vec<u8>,
+    /// Lower bound on the encoded length, from the last failed attempt.
+    needed: usize,
+}
+
+#[cfg(any(feature = "futures", feature = "tokio"))]
+impl AsyncDecode {
+    fn new() -> Self {
+        AsyncDecode {
+            buf: Vec::new(),
+            needed: 0,
+        }
+    }
+
+    /// Offer the reader's buffer; an empty one means end of stream.
+    fn feed<T: DeserializeOwned>(&mut self, available: &[u8]) -> Result<Progress<T>> {
+        let consumed = self.buf.len();
+        self.buf.extend_from_slice(available);
+        if self.buf.len() < self.needed && !available.is_empty() {
+            return Ok(Progress::More(available.len()));
+        }
+        let mut de = Deserializer::new(&self.buf);
+        match T::deserialize(&mut de) {
+            Ok(value) => Ok(Progress::Done(value, de.pos - consumed)),
+            Err(Error::UnexpectedEof) if !available.is_empty() => {
+                self.needed = de.needed;
+                Ok(Progress::More(available.len()))
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
+
 // ══════════════════════════════════════════════════════════════════════════
 // Slice-based Deserializer
 // ══════════════════════════════════════════════════════════════════════════
@@ -57,11 +212,53 @@ pub fn from_reader<R: Read, T: DeserializeOwned>(reader: R) -> Result<T> {
 pub struct Deserializer<'de> {
     input: &'de [u8],
     pos: usize,
+    strict_padding: bool,
+    limits: DecodeLimits,
+    depth: u32,
+    /// Length bound announced by a `BOUNDED_TOKEN` tuple struct, applied to
+    /// the next length prefix read.
+    bound: Option<u32>,
+    /// Input length the last read past the end would have needed.
+    needed: usize,
 }
 
 impl<'de> Deserializer<'de> {
     pub fn new(input: &'de [u8]) -> Self {
-        Deserializer { input, pos: 0 }
+        Deserializer {
+            input,
+            pos: 0,
+            strict_padding: false,
+            limits: DecodeLimits::UNLIMITED,
+            depth: 0,
+            bound: None,
+            needed: 0,
+        }
+    }
+
+    /// Reject non-zero padding bytes with [`Error::InvalidPadding`].
+    ///
+    /// RFC 4506 §3 requires the 0–3 residual bytes to be zero. By default they
+    /// are skipped without inspection; strict mode validates them.
+    ///
+    /// ```rust
+    /// use serde::Deserialize;
+    /// use xdr_serde::{Deserializer, Error};
+    ///
+    /// let bytes = [0, 0, 0, 1, b'a', 0xFF, 0, 0];
+    /// let mut de = Deserializer::new(&bytes).with_strict_padding(true);
+    /// assert_eq!(String::deserialize(&mut de), Err(Error::InvalidPadding));
+    /// ```
+    pub fn with_strict_padding(mut self, strict: bool) -> Self {
+        self.strict_padding = strict;
+        self
+    }
+
+    /// Enforce `limits` on everything decoded by this deserializer.
+    ///
+    /// Violations are reported as [`Error::LengthOverflow`].
+    pub fn with_limits(mut self, limits: DecodeLimits) -> Self {
+        self.limits = limits;
+        self
     }
 
     /// Returns the unconsumed portion of the input buffer.
@@ -70,11 +267,16 @@ impl<'de> Deserializer<'de> {
     }
 
     pub(crate) fn take(&mut self, n: usize) -> Result<&'de [u8]> {
-        if self.pos + n > self.input.len() {
+        let end = self.pos.saturating_add(n);
+        if end > self.limits.max_bytes {
+            return Err(length_overflow(self.limits.max_bytes, end));
+        }
+        if end > self.input.len() {
+            self.needed = end;
             return Err(Error::UnexpectedEof);
         }
-        let slice = &self.input[self.pos..self.pos + n];
-        self.pos += n;
+        let slice = &self.input[self.pos..end];
+        self.pos = end;
         Ok(slice)
     }
 
@@ -100,23 +302,50 @@ impl<'de> Deserializer<'de> {
         let data = self.take(n)?;
         let remainder = n % 4;
         if remainder != 0 {
-            self.take(4 - remainder)?;
+            let pad = self.take(4 - remainder)?;
+            if self.strict_padding && pad.iter().any(|&b| b != 0) {
+                return Err(Error::InvalidPadding);
+            }
         }
         Ok(data)
     }
 
-    /// Variable-length opaque: read 4-byte length then `n` padded bytes.
-    fn read_variable_opaque(&mut self) -> Result<&'de [u8]> {
-        let n = self.read_u32()? as usize;
+    /// Variable-length opaque: read 4-byte length (at most `max`) then `n`
+    /// padded bytes.
+    fn read_variable_opaque(&mut self, max: u32) -> Result<&'de [u8]> {
+        let n = check_len(self.read_u32()?, max, self.bound.take())?;
         self.read_padded_bytes(n)
     }
 
-    fn read_variable_opaque_owned(&mut self) -> Result<Vec<u8>> {
-        Ok(self.read_variable_opaque()?.to_vec())
+    fn read_variable_opaque_owned(&mut self, max: u32) -> Result<Vec<u8>> {
+        Ok(self.read_variable_opaque(max)?.to_vec())
+    }
+
+    /// Read a variable-length array/map count prefix.
+    fn read_count(&mut self) -> Result<usize> {
+        check_len(
+            self.read_u32()?,
+            self.limits.max_elements,
+            self.bound.take(),
+        )
+    }
+
+    /// Run `f` one nesting level deeper, enforcing `max_depth`.
+    fn nested<T>(&mut self, f: impl FnOnce(&mut Self) -> Result<T>) -> Result<T> {
+        if self.depth >= self.limits.max_depth {
+            return Err(Error::LengthOverflow {
+                max: self.limits.max_depth,
+                got: self.depth.saturating_add(1),
+            });
+        }
+        self.depth += 1;
+        let result = f(self);
+        self.depth -= 1;
+        result
     }
 }
 
-impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
+impl<'de> de::Deserializer<'de> for &mut Deserializer<'de> {
     type Error = Error;
 
     fn deserialize_any<V: Visitor<'de>>(self, _v: V) -> Result<V::Value> {
@@ -171,26 +400,26 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
     }
 
     fn deserialize_str<V: Visitor<'de>>(self, v: V) -> Result<V::Value> {
-        let bytes = self.read_variable_opaque()?;
-        v.visit_str(std::str::from_utf8(bytes).map_err(|_| Error::InvalidString)?)
+        let bytes = self.read_variable_opaque(self.limits.max_string_len)?;
+        v.visit_borrowed_str(core::str::from_utf8(bytes).map_err(|_| Error::InvalidString)?)
     }
     fn deserialize_string<V: Visitor<'de>>(self, v: V) -> Result<V::Value> {
-        let s = String::from_utf8(self.read_variable_opaque_owned()?)
+        let s = String::from_utf8(self.read_variable_opaque_owned(self.limits.max_string_len)?)
             .map_err(|_| Error::InvalidString)?;
         v.visit_string(s)
     }
 
     fn deserialize_bytes<V: Visitor<'de>>(self, v: V) -> Result<V::Value> {
-        v.visit_bytes(self.read_variable_opaque()?)
+        v.visit_borrowed_bytes(self.read_variable_opaque(self.limits.max_opaque_len)?)
     }
     fn deserialize_byte_buf<V: Visitor<'de>>(self, v: V) -> Result<V::Value> {
-        v.visit_byte_buf(self.read_variable_opaque_owned()?)
+        v.visit_byte_buf(self.read_variable_opaque_owned(self.limits.max_opaque_len)?)
     }
 
     fn deserialize_option<V: Visitor<'de>>(self, v: V) -> Result<V::Value> {
         match self.read_u32()? {
             0 => v.visit_none(),
-            1 => v.visit_some(self),
+            1 => self.nested(|de| v.visit_some(de)),
             n => Err(Error::InvalidOption(n)),
         }
     }
@@ -218,19 +447,40 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
     }
 
     fn deserialize_seq<V: Visitor<'de>>(self, v: V) -> Result<V::Value> {
-        let count = self.read_u32()? as usize;
-        v.visit_seq(SliceSeqAccess::new(self, count))
+        let count = self.read_count()?;
+        self.nested(|de| {
+            let mut seq = SliceSeqAccess::new(de, count);
+            let result = v.visit_seq(&mut seq);
+            if let Err(Error::UnexpectedEof) = result {
+                // The element that ran out is not empty, so neither is any
+                // other element of its type: each still to come takes at
+                // least one XDR unit.
+                let rest = seq.remaining.saturating_mul(4);
+                seq.de.needed = seq.de.needed.saturating_add(rest);
+            }
+            result
+        })
     }
     fn deserialize_tuple<V: Visitor<'de>>(self, len: usize, v: V) -> Result<V::Value> {
-        v.visit_seq(SliceSeqAccess::new(self, len))
+        self.nested(|de| v.visit_seq(SliceSeqAccess::new(de, len)))
     }
     fn deserialize_tuple_struct<V: Visitor<'de>>(
         self,
-        _: &'static str,
+        name: &'static str,
         len: usize,
         v: V,
     ) -> Result<V::Value> {
-        v.visit_seq(SliceSeqAccess::new(self, len))
+        if name == crate::BOUNDED_TOKEN {
+            // `len` carries the bound; the single element is the bounded value.
+            self.bound = Some(u32::try_from(len).unwrap_or(u32::MAX));
+            let result = v.visit_seq(SliceSeqAccess::new(self, 1));
+            self.bound = None;
+            return result;
+        }
+        if name == crate::LINKED_LIST_TOKEN {
+            return self.nested(|de| v.visit_seq(SliceListAccess::new(de)));
+        }
+        self.nested(|de| v.visit_seq(SliceSeqAccess::new(de, len)))
     }
     fn deserialize_struct<V: Visitor<'de>>(
         self,
@@ -238,11 +488,11 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
         fields: &'static [&'static str],
         v: V,
     ) -> Result<V::Value> {
-        v.visit_seq(SliceSeqAccess::new(self, fields.len()))
+        self.nested(|de| v.visit_seq(SliceSeqAccess::new(de, fields.len())))
     }
     fn deserialize_map<V: Visitor<'de>>(self, v: V) -> Result<V::Value> {
-        let count = self.read_u32()? as usize;
-        v.visit_map(SliceMapAccess::new(self, count))
+        let count = self.read_count()?;
+        self.nested(|de| v.visit_map(SliceMapAccess::new(de, count)))
     }
     fn deserialize_enum<V: Visitor<'de>>(
         self,
@@ -250,7 +500,7 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
         _: &'static [&'static str],
         v: V,
     ) -> Result<V::Value> {
-        v.visit_enum(SliceEnumAccess::new(self))
+        self.nested(|de| v.visit_enum(SliceEnumAccess::new(de)))
     }
     fn deserialize_identifier<V: Visitor<'de>>(self, v: V) -> Result<V::Value> {
         v.visit_u32(self.read_u32()?)
@@ -450,6 +700,29 @@ impl<'de, 'a> SeqAccess<'de> for SliceSeqAccess<'a, 'de> {
     }
 }
 
+/// The elements of a linked list: a pointer before each item and a null
+/// pointer at the end. Items are counted against `max_elements`.
+struct SliceListAccess<'a, 'de: 'a> {
+    de: &'a mut Deserializer<'de>,
+    read: usize,
+}
+impl<'a, 'de> SliceListAccess<'a, 'de> {
+    fn new(de: &'a mut Deserializer<'de>) -> Self {
+        Self { de, read: 0 }
+    }
+}
+impl<'de, 'a> SeqAccess<'de> for SliceListAccess<'a, 'de> {
+    type Error = Error;
+    fn next_element_seed<T: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>> {
+        check_list_node(self.read, self.de.limits.max_elements)?;
+        self.read += 1;
+        seed.deserialize(&mut *self.de).map(Some)
+    }
+}
+
 struct SliceMapAccess<'a, 'de: 'a> {
     de: &'a mut Deserializer<'de>,
     remaining: usize,
@@ -489,7 +762,11 @@ impl<'de, 'a> EnumAccess<'de> for SliceEnumAccess<'a, 'de> {
     type Variant = Self;
     fn variant_seed<V: de::DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self)> {
         let idx = self.de.read_u32()?;
-        let val = seed.deserialize(de::value::U32Deserializer::<crate::error::Error>::new(idx))?;
+        // The seed only maps the discriminant to a variant, so any failure
+        // means the value is not one the type knows.
+        let val = seed
+            .deserialize(de::value::U32Deserializer::<crate::error::Error>::new(idx))
+            .map_err(|_| Error::InvalidDiscriminant(idx as i32))?;
         Ok((val, self))
     }
 }
@@ -520,13 +797,43 @@ impl<'de, 'a> VariantAccess<'de> for SliceEnumAccess<'a, 'de> {
 /// XDR deserializer backed by any [`std::io::Read`] source.
 ///
 /// All decoded strings and byte sequences are returned as owned values.
+#[cfg(feature = "std")]
 pub struct ReaderDeserializer<R: Read> {
     reader: R,
+    strict_padding: bool,
+    limits: DecodeLimits,
+    depth: u32,
+    consumed: usize,
+    bound: Option<u32>,
 }
 
+#[cfg(feature = "std")]
 impl<R: Read> ReaderDeserializer<R> {
     pub fn new(reader: R) -> Self {
-        ReaderDeserializer { reader }
+        ReaderDeserializer {
+            reader,
+            strict_padding: false,
+            limits: DecodeLimits::UNLIMITED,
+            depth: 0,
+            consumed: 0,
+            bound: None,
+        }
+    }
+
+    /// Reject non-zero padding bytes with [`Error::InvalidPadding`].
+    ///
+    /// See [`Deserializer::with_strict_padding`].
+    pub fn with_strict_padding(mut self, strict: bool) -> Self {
+        self.strict_padding = strict;
+        self
+    }
+
+    /// Enforce `limits` on everything decoded by this deserializer.
+    ///
+    /// Lengths and counts are checked before anything is allocated.
+    pub fn with_limits(mut self, limits: DecodeLimits) -> Self {
+        self.limits = limits;
+        self
     }
 
     pub fn into_reader(self) -> R {
@@ -534,14 +841,25 @@ impl<R: Read> ReaderDeserializer<R> {
     }
 
     pub(crate) fn read_exact_buf(&mut self, n: usize) -> Result<Vec<u8>> {
-        let mut buf = vec![0u8; n];
-        self.reader.read_exact(&mut buf).map_err(|e| {
-            if e.kind() == std::io::ErrorKind::UnexpectedEof {
-                Error::UnexpectedEof
-            } else {
-                Error::Io(e.to_string())
-            }
-        })?;
+        let total = self.consumed.saturating_add(n);
+        if total > self.limits.max_bytes {
+            return Err(length_overflow(self.limits.max_bytes, total));
+        }
+        self.consumed = total;
+
+        if n <= READ_CHUNK {
+            let mut buf = alloc::vec![0u8; n];
+            self.reader.read_exact(&mut buf)?;
+            return Ok(buf);
+        }
+
+        // Large reads grow with the data actually received, so a short stream
+        // fails with UnexpectedEof instead of after allocating `n` bytes.
+        let mut buf = Vec::with_capacity(READ_CHUNK);
+        (&mut self.reader).take(n as u64).read_to_end(&mut buf)?;
+        if buf.len() < n {
+            return Err(Error::UnexpectedEof);
+        }
         Ok(buf)
     }
 
@@ -569,17 +887,43 @@ impl<R: Read> ReaderDeserializer<R> {
         let data = self.read_exact_buf(n)?;
         let remainder = n % 4;
         if remainder != 0 {
-            self.read_exact_buf(4 - remainder)?;
+            let pad = self.read_exact_buf(4 - remainder)?;
+            if self.strict_padding && pad.iter().any(|&b| b != 0) {
+                return Err(Error::InvalidPadding);
+            }
         }
         Ok(data)
     }
 
-    fn read_variable_opaque(&mut self) -> Result<Vec<u8>> {
-        let n = self.read_u32()? as usize;
+    fn read_variable_opaque(&mut self, max: u32) -> Result<Vec<u8>> {
+        let n = check_len(self.read_u32()?, max, self.bound.take())?;
         self.read_padded_bytes(n)
     }
+
+    fn read_count(&mut self) -> Result<usize> {
+        check_len(
+            self.read_u32()?,
+            self.limits.max_elements,
+            self.bound.take(),
+        )
+    }
+
+    /// Run `f` one nesting level deeper, enforcing `max_depth`.
+    fn nested<T>(&mut self, f: impl FnOnce(&mut Self) -> Result<T>) -> Result<T> {
+        if self.depth >= self.limits.max_depth {
+            return Err(Error::LengthOverflow {
+                max: self.limits.max_depth,
+                got: self.depth.saturating_add(1),
+            });
+        }
+        self.depth += 1;
+        let result = f(self);
+        self.depth -= 1;
+        result
+    }
 }
 
+#[cfg(feature = "std")]
 impl<'de, R: Read> de::Deserializer<'de> for &mut ReaderDeserializer<R> {
     type Error = Error;
 
@@ -640,21 +984,21 @@ impl<'de, R: Read> de::Deserializer<'de> for &mut ReaderDeserializer<R> {
         self.deserialize_string(v)
     }
     fn deserialize_string<V: Visitor<'de>>(self, v: V) -> Result<V::Value> {
-        let s =
-            String::from_utf8(self.read_variable_opaque()?).map_err(|_| Error::InvalidString)?;
+        let s = String::from_utf8(self.read_variable_opaque(self.limits.max_string_len)?)
+            .map_err(|_| Error::InvalidString)?;
         v.visit_string(s)
     }
     fn deserialize_bytes<V: Visitor<'de>>(self, v: V) -> Result<V::Value> {
         self.deserialize_byte_buf(v)
     }
     fn deserialize_byte_buf<V: Visitor<'de>>(self, v: V) -> Result<V::Value> {
-        v.visit_byte_buf(self.read_variable_opaque()?)
+        v.visit_byte_buf(self.read_variable_opaque(self.limits.max_opaque_len)?)
     }
 
     fn deserialize_option<V: Visitor<'de>>(self, v: V) -> Result<V::Value> {
         match self.read_u32()? {
             0 => v.visit_none(),
-            1 => v.visit_some(self),
+            1 => self.nested(|de| v.visit_some(de)),
             n => Err(Error::InvalidOption(n)),
         }
     }
@@ -679,19 +1023,28 @@ impl<'de, R: Read> de::Deserializer<'de> for &mut ReaderDeserializer<R> {
     }
 
     fn deserialize_seq<V: Visitor<'de>>(self, v: V) -> Result<V::Value> {
-        let count = self.read_u32()? as usize;
-        v.visit_seq(ReaderSeqAccess::new(self, count))
+        let count = self.read_count()?;
+        self.nested(|de| v.visit_seq(ReaderSeqAccess::new(de, count)))
     }
     fn deserialize_tuple<V: Visitor<'de>>(self, len: usize, v: V) -> Result<V::Value> {
-        v.visit_seq(ReaderSeqAccess::new(self, len))
+        self.nested(|de| v.visit_seq(ReaderSeqAccess::new(de, len)))
     }
     fn deserialize_tuple_struct<V: Visitor<'de>>(
         self,
-        _: &'static str,
+        name: &'static str,
         len: usize,
         v: V,
     ) -> Result<V::Value> {
-        v.visit_seq(ReaderSeqAccess::new(self, len))
+        if name == crate::BOUNDED_TOKEN {
+            self.bound = Some(u32::try_from(len).unwrap_or(u32::MAX));
+            let result = v.visit_seq(ReaderSeqAccess::new(self, 1));
+            self.bound = None;
+            return result;
+        }
+        if name == crate::LINKED_LIST_TOKEN {
+            return self.nested(|de| v.visit_seq(ReaderListAccess::new(de)));
+        }
+        self.nested(|de| v.visit_seq(ReaderSeqAccess::new(de, len)))
     }
     fn deserialize_struct<V: Visitor<'de>>(
         self,
@@ -699,11 +1052,11 @@ impl<'de, R: Read> de::Deserializer<'de> for &mut ReaderDeserializer<R> {
         fields: &'static [&'static str],
         v: V,
     ) -> Result<V::Value> {
-        v.visit_seq(ReaderSeqAccess::new(self, fields.len()))
+        self.nested(|de| v.visit_seq(ReaderSeqAccess::new(de, fields.len())))
     }
     fn deserialize_map<V: Visitor<'de>>(self, v: V) -> Result<V::Value> {
-        let count = self.read_u32()? as usize;
-        v.visit_map(ReaderMapAccess::new(self, count))
+        let count = self.read_count()?;
+        self.nested(|de| v.visit_map(ReaderMapAccess::new(de, count)))
     }
     fn deserialize_enum<V: Visitor<'de>>(
         self,
@@ -711,7 +1064,7 @@ impl<'de, R: Read> de::Deserializer<'de> for &mut ReaderDeserializer<R> {
         _: &'static [&'static str],
         v: V,
     ) -> Result<V::Value> {
-        v.visit_enum(ReaderEnumAccess::new(self))
+        self.nested(|de| v.visit_enum(ReaderEnumAccess::new(de)))
     }
     fn deserialize_identifier<V: Visitor<'de>>(self, v: V) -> Result<V::Value> {
         v.visit_u32(self.read_u32()?)
@@ -725,8 +1078,10 @@ impl<'de, R: Read> de::Deserializer<'de> for &mut ReaderDeserializer<R> {
 
 // ── FixedOpaqueReaderDe: reader counterpart of FixedOpaqueSliceDe ──────────
 
+#[cfg(feature = "std")]
 struct FixedOpaqueReaderDe<'a, R: Read>(&'a mut ReaderDeserializer<R>);
 
+#[cfg(feature = "std")]
 impl<'de, 'a, R: Read> de::Deserializer<'de> for FixedOpaqueReaderDe<'a, R> {
     type Error = Error;
 
@@ -847,10 +1202,12 @@ impl<'de, 'a, R: Read> de::Deserializer<'de> for FixedOpaqueReaderDe<'a, R> {
     }
 }
 
+#[cfg(feature = "std")]
 struct OwnedByteSeqAccess {
     data: Vec<u8>,
     pos: usize,
 }
+#[cfg(feature = "std")]
 impl<'de> SeqAccess<'de> for OwnedByteSeqAccess {
     type Error = Error;
     fn next_element_seed<T: de::DeserializeSeed<'de>>(
@@ -872,10 +1229,12 @@ impl<'de> SeqAccess<'de> for OwnedByteSeqAccess {
 
 // ── Reader-based compound access ───────────────────────────────────────────
 
+#[cfg(feature = "std")]
 struct ReaderSeqAccess<'a, R: Read> {
     de: &'a mut ReaderDeserializer<R>,
     remaining: usize,
 }
+#[cfg(feature = "std")]
 impl<'a, R: Read> ReaderSeqAccess<'a, R> {
     fn new(de: &'a mut ReaderDeserializer<R>, count: usize) -> Self {
         Self {
@@ -884,6 +1243,7 @@ impl<'a, R: Read> ReaderSeqAccess<'a, R> {
         }
     }
 }
+#[cfg(feature = "std")]
 impl<'de, 'a, R: Read> SeqAccess<'de> for ReaderSeqAccess<'a, R> {
     type Error = Error;
     fn next_element_seed<T: de::DeserializeSeed<'de>>(
@@ -901,10 +1261,37 @@ impl<'de, 'a, R: Read> SeqAccess<'de> for ReaderSeqAccess<'a, R> {
     }
 }
 
+/// The elements of a linked list, as [`SliceListAccess`].
+#[cfg(feature = "std")]
+struct ReaderListAccess<'a, R: Read> {
+    de: &'a mut ReaderDeserializer<R>,
+    read: usize,
+}
+#[cfg(feature = "std")]
+impl<'a, R: Read> ReaderListAccess<'a, R> {
+    fn new(de: &'a mut ReaderDeserializer<R>) -> Self {
+        Self { de, read: 0 }
+    }
+}
+#[cfg(feature = "std")]
+impl<'de, 'a, R: Read> SeqAccess<'de> for ReaderListAccess<'a, R> {
+    type Error = Error;
+    fn next_element_seed<T: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>> {
+        check_list_node(self.read, self.de.limits.max_elements)?;
+        self.read += 1;
+        seed.deserialize(&mut *self.de).map(Some)
+    }
+}
+
+#[cfg(feature = "std")]
 struct ReaderMapAccess<'a, R: Read> {
     de: &'a mut ReaderDeserializer<R>,
     remaining: usize,
 }
+#[cfg(feature = "std")]
 impl<'a, R: Read> ReaderMapAccess<'a, R> {
     fn new(de: &'a mut ReaderDeserializer<R>, count: usize) -> Self {
         Self {
@@ -913,6 +1300,7 @@ impl<'a, R: Read> ReaderMapAccess<'a, R> {
         }
     }
 }
+#[cfg(feature = "std")]
 impl<'de, 'a, R: Read> MapAccess<'de> for ReaderMapAccess<'a, R> {
     type Error = Error;
     fn next_key_seed<K: de::DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>> {
@@ -927,23 +1315,31 @@ impl<'de, 'a, R: Read> MapAccess<'de> for ReaderMapAccess<'a, R> {
     }
 }
 
+#[cfg(feature = "std")]
 struct ReaderEnumAccess<'a, R: Read> {
     de: &'a mut ReaderDeserializer<R>,
 }
+#[cfg(feature = "std")]
 impl<'a, R: Read> ReaderEnumAccess<'a, R> {
     fn new(de: &'a mut ReaderDeserializer<R>) -> Self {
         Self { de }
     }
 }
+#[cfg(feature = "std")]
 impl<'de, 'a, R: Read> EnumAccess<'de> for ReaderEnumAccess<'a, R> {
     type Error = Error;
     type Variant = Self;
     fn variant_seed<V: de::DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self)> {
         let idx = self.de.read_u32()?;
-        let val = seed.deserialize(de::value::U32Deserializer::<crate::error::Error>::new(idx))?;
+        // The seed only maps the discriminant to a variant, so any failure
+        // means the value is not one the type knows.
+        let val = seed
+            .deserialize(de::value::U32Deserializer::<crate::error::Error>::new(idx))
+            .map_err(|_| Error::InvalidDiscriminant(idx as i32))?;
         Ok((val, self))
     }
 }
+#[cfg(feature = "std")]
 impl<'de, 'a, R: Read> VariantAccess<'de> for ReaderEnumAccess<'a, R> {
     type Error = Error;
     fn unit_variant(self) -> Result<()> {
@@ -0,0 +1,340 @@
+//! Explicit discriminant values for XDR enums and discriminated unions
+//! (RFC 4506 §4.3, §4.15).
+//!
+//! A plain `#[derive(Serialize, Deserialize)]` enum is encoded with its Rust
+//! variant *index* as the discriminant: the first variant is 0, the second 1,
+//! and so on. Real protocols assign arbitrary signed 32-bit values instead:
+//!
+//! ```text
+//! enum nfsstat3 {
+//!     NFS3_OK       = 0,
+//!     NFS3ERR_PERM  = 1,
+//!     NFS3ERR_NOENT = 2,
+//!     NFS3ERR_IO    = 5
+//! };
+//! ```
+//!
+//! The [`xdr_enum!`](crate::xdr_enum) macro declares a Rust enum together
+//! with `Serialize`/`Deserialize` impls that put the declared value on the
+//! wire. Variants are either unit variants (a `void` arm) or carry exactly one
+//! value (a single-declaration arm), mirroring XDR union syntax:
+//!
+//! ```rust
+//! use serde::{Deserialize, Serialize};
+//! use xdr_serde::{Error, from_bytes, to_bytes, xdr_enum};
+//!
+//! xdr_enum! {
+//!     #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+//!     pub enum Nfsstat3 {
+//!         Ok = 0,
+//!         Perm = 1,
+//!         NoEnt = 2,
+//!         Io = 5,
+//!     }
+//! }
+//!
+//! #[derive(Debug, PartialEq, Serialize, Deserialize)]
+//! pub struct ReadOk {
+//!     pub count: u32,
+//!     pub eof: bool,
+//! }
+//!
+//! xdr_enum! {
+//!     #[derive(Debug, PartialEq)]
+//!     pub enum ReadRes {
+//!         Ok(ReadOk) = 0,
+//!         Io = 5,
+//!     }
+//! }
+//!
+//! assert_eq!(to_bytes(&Nfsstat3::Io).unwrap(), [0, 0, 0, 5]);
+//! assert_eq!(Nfsstat3::NoEnt.discriminant(), 2);
+//! assert_eq!(from_bytes::<Nfsstat3>(&[0, 0, 0, 3]), Err(Error::InvalidDiscriminant(3)));
+//!
+//! let res = ReadRes::Ok(ReadOk { count: 4, eof: true });
+//! let bytes = to_bytes(&res).unwrap();
+//! assert_eq!(bytes, [0, 0, 0, 0, 0, 0, 0, 4, 0, 0, 0, 1]);
+//! assert_eq!(res, from_bytes(&bytes).unwrap());
+//! ```
+//!
+//! Discriminants may be any constant expression of type `i32`, including
+//! named constants. An arm with several fields should use a struct as its
+//! single value, as XDR itself requires.
+//!
+//! Unknown discriminants are rejected with [`Error::InvalidDiscriminant`].
+//! Plain derived enums report an out-of-range variant index the same way.
+//!
+//! # Default arms
+//!
+//! An XDR union may end with a `default:` arm that accepts every discriminant
+//! not listed explicitly. Declare it last, as `_ => Variant(i32)` for a `void`
+//! default or `_ => Variant(i32, Type)` for a default arm carrying a value. The
+//! raw discriminant is kept, so the value re-encodes byte-for-byte:
+//!
+//! ```rust
+//! use xdr_serde::{from_bytes, to_bytes, xdr_enum};
+//!
+//! xdr_enum! {
+//!     #[derive(Debug, PartialEq)]
+//!     pub enum ResOp {
+//!         Access(u32) = 3,
+//!         Close = 4,
+//!         _ => Unknown(i32, u32),
+//!     }
+//! }
+//!
+//! let bytes = [0, 0, 0, 99, 0, 0, 0, 7];
+//! let op: ResOp = from_bytes(&bytes).unwrap();
+//! assert_eq!(op, ResOp::Unknown(99, 7));
+//! assert_eq!(op.discriminant(), 99);
+//! assert_eq!(to_bytes(&op).unwrap(), bytes);
+//! ```
+//!
+//! Attributes for the default variant go after the `=>`. A default arm with a
+//! discriminant that matches an explicit arm still encodes as written, but
+//! decodes as the explicit arm.
+//!
+//! [`Error::InvalidDiscriminant`]: crate::Error::InvalidDiscriminant
+
+use core::fmt;
+use serde::de::{self, DeserializeSeed, Deserializer, Unexpected, Visitor};
+
+/// Reads a union discriminant through `EnumAccess::variant_seed`.
+///
+/// The XDR deserializers hand over the raw 32-bit discriminant, which is
+/// rejected unless it is one of `arms` or the union has a `default:` arm;
+/// they report the rejection as
+/// [`Error::InvalidDiscriminant`](crate::Error::InvalidDiscriminant).
+/// Self-describing formats hand over the variant name, which is mapped back through `arms`.
+#[doc(hidden)]
+pub struct DiscriminantSeed<'a> {
+    pub arms: &'a [(&'a str, i32)],
+    pub default: bool,
+}
+
+impl DiscriminantSeed<'_> {
+    fn check<E: de::Error>(&self, d: i32) -> Result<i32, E> {
+        if self.default || self.arms.iter().any(|&(_, arm)| arm == d) {
+            Ok(d)
+        } else {
+            Err(E::invalid_value(Unexpected::Signed(d as i64), self))
+        }
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for DiscriminantSeed<'_> {
+    type Value = i32;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<i32, D::Error> {
+        deserializer.deserialize_identifier(self)
+    }
+}
+
+impl<'de> Visitor<'de> for DiscriminantSeed<'_> {
+    type Value = i32;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("an XDR discriminant")
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<i32, E> {
+        // The wire carries the two's-complement bits of the signed value.
+        self.check(v as u32 as i32)
+    }
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<i32, E> {
+        let d = i32::try_from(v).map_err(|_| E::invalid_value(Unexpected::Signed(v), &self))?;
+        self.check(d)
+    }
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<i32, E> {
+        self.arms
+            .iter()
+            .find(|(name, _)| *name == v)
+            .map(|&(_, d)| d)
+            .ok_or_else(|| E::custom(format_args!("unknown variant `{}`", v)))
+    }
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<i32, E> {
+        match core::str::from_utf8(v) {
+            Ok(s) => self.visit_str(s),
+            Err(_) => Err(E::invalid_value(Unexpected::Bytes(v), &self)),
+        }
+    }
+}
+
+/// Declare an enum or discriminated union with explicit XDR discriminants.
+///
+/// Each variant is either `Name = value` (a `void` arm) or
+/// `Name(Type) = value` (an arm carrying one value). An optional final
+/// `_ => Name(i32)` or `_ => Name(i32, Type)` declares the union's `default:`
+/// arm. The macro emits the enum exactly as written, a `discriminant()`
+/// accessor, and `Serialize` / `Deserialize` impls that use the declared
+/// values on the wire.
+///
+/// See the [`discriminant`](crate::discriminant) module for examples.
+#[macro_export]
+macro_rules! xdr_enum {
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident {
+            $(
+                $(#[$vmeta:meta])*
+                $variant:ident $(($ty:ty))? = $disc:expr
+            ),+
+            $(, _ => $(#[$dmeta:meta])* $default:ident(i32 $(, $dty:ty)?))?
+            $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis enum $name {
+            $(
+                $(#[$vmeta])*
+                $variant $(($ty))?,
+            )+
+            $(
+                $(#[$dmeta])*
+                $default(i32 $(, $dty)?),
+            )?
+        }
+
+        impl $name {
+            /// The XDR discriminant of this value.
+            #[allow(dead_code)]
+            pub fn discriminant(&self) -> i32 {
+                match self {
+                    $( $crate::xdr_enum!(@pat $name $variant _ $($ty)?) => $disc, )+
+                    $( $name::$default(d, ..) => *d, )?
+                }
+            }
+        }
+
+        impl $crate::__private::serde::Serialize for $name {
+            fn serialize<S>(&self, serializer: S) -> ::core::result::Result<S::Ok, S::Error>
+            where
+                S: $crate::__private::serde::Serializer,
+            {
+                match self {
+                    $(
+                        $crate::xdr_enum!(@pat $name $variant __xdr_value $($ty)?) => {
+                            $crate::xdr_enum!(
+                                @ser serializer, $name, $variant, $disc, __xdr_value $(, $ty)?
+                            )
+                        }
+                    )+
+                    $(
+                        $crate::xdr_enum!(
+                            @pat $name $default (__xdr_d, __xdr_value) i32 $($dty)?
+                        ) => {
+                            $crate::xdr_enum!(
+                                @ser serializer, $name, $default, *__xdr_d, __xdr_value $(, $dty)?
+                            )
+                        }
+                    )?
+                }
+            }
+        }
+
+        impl<'de> $crate::__private::serde::Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> ::core::result::Result<Self, D::Error>
+            where
+                D: $crate::__private::serde::Deserializer<'de>,
+            {
+                struct __XdrVisitor;
+
+                impl<'de> $crate::__private::serde::de::Visitor<'de> for __XdrVisitor {
+                    type Value = $name;
+
+                    fn expecting(
+                        &self,
+                        f: &mut ::core::fmt::Formatter<'_>,
+                    ) -> ::core::fmt::Result {
+                        f.write_str(concat!("XDR union ", stringify!($name)))
+                    }
+
+                    #[allow(unreachable_code)]
+                    fn visit_enum<A>(self, data: A) -> ::core::result::Result<$name, A::Error>
+                    where
+                        A: $crate::__private::serde::de::EnumAccess<'de>,
+                    {
+                        let (d, variant) = data.variant_seed(
+                            $crate::discriminant::DiscriminantSeed {
+                                arms: &[$( (stringify!($variant), $disc), )+],
+                                default: $crate::xdr_enum!(@has_default $($default)?),
+                            },
+                        )?;
+                        $(
+                            if d == $disc {
+                                return $crate::xdr_enum!(@de variant, $name, $variant $(, $ty)?);
+                            }
+                        )+
+                        $(
+                            return $crate::xdr_enum!(
+                                @de_default variant, $name, $default, d $(, $dty)?
+                            );
+                        )?
+                        ::core::unreachable!("discriminant {} was checked by DiscriminantSeed", d)
+                    }
+                }
+
+                deserializer.deserialize_enum(
+                    stringify!($name),
+                    &[$( stringify!($variant) ),+],
+                    __XdrVisitor,
+                )
+            }
+        }
+    };
+
+    // ── Internal rules ─────────────────────────────────────────────────────
+
+    (@has_default) => { false };
+    (@has_default $default:ident) => { true };
+
+    // Default arms: `$bind` is `(discriminant, value)`.
+    (@pat $name:ident $variant:ident ($d:ident, $value:ident) i32) => {
+        $name::$variant($d)
+    };
+    (@pat $name:ident $variant:ident ($d:ident, $value:ident) i32 $ty:ty) => {
+        $name::$variant($d, $value)
+    };
+    (@pat $name:ident $variant:ident $bind:tt) => {
+        $name::$variant
+    };
+    (@pat $name:ident $variant:ident $bind:tt $ty:ty) => {
+        $name::$variant($bind)
+    };
+
+    (@ser $ser:ident, $name:ident, $variant:ident, $disc:expr, $value:ident) => {
+        $crate::__private::serde::Serializer::serialize_unit_variant(
+            $ser,
+            stringify!($name),
+            ($disc) as i32 as u32,
+            stringify!($variant),
+        )
+    };
+    (@ser $ser:ident, $name:ident, $variant:ident, $disc:expr, $value:ident, $ty:ty) => {
+        $crate::__private::serde::Serializer::serialize_newtype_variant(
+            $ser,
+            stringify!($name),
+            ($disc) as i32 as u32,
+            stringify!($variant),
+            $value,
+        )
+    };
+
+    (@de $access:ident, $name:ident, $variant:ident) => {
+        $crate::__private::serde::de::VariantAccess::unit_variant($access)
+            .map(|()| $name::$variant)
+    };
+    (@de $access:ident, $name:ident, $variant:ident, $ty:ty) => {
+        $crate::__private::serde::de::VariantAccess::newtype_variant::<$ty>($access)
+            .map($name::$variant)
+    };
+
+    (@de_default $access:ident, $name:ident, $variant:ident, $d:ident) => {
+        $crate::__private::serde::de::VariantAccess::unit_variant($access)
+            .map(|()| $name::$variant($d))
+    };
+    (@de_default $access:ident, $name:ident, $variant:ident, $d:ident, $ty:ty) => {
+        $crate::__private::serde::de::VariantAccess::newtype_variant::<$ty>($access)
+            .map(|value| $name::$variant($d, value))
+    };
+}
@@ -1,7 +1,8 @@
+use alloc::string::{String, ToString};
+use core::fmt;
 use serde::{de, ser};
-use std::fmt;
 
-pub type Result<T> = std::result::Result<T, Error>;
+pub type Result<T> = core::result::Result<T, Error>;
 
 /// Errors that can occur during XDR serialization or deserialization.
 #[derive(Debug, Clone, PartialEq)]
@@ -38,6 +39,9 @@ pub enum Error {
 
     /// An I/O error occurred during writing
     Io(String),
+
+    /// The output buffer passed to [`to_slice`](crate::to_slice) is too small
+    BufferTooSmall,
 }
 
 impl fmt::Display for Error {
@@ -63,11 +67,43 @@ impl fmt::Display for Error {
             Error::InvalidPadding => write!(f, "non-zero padding bytes"),
             Error::Unsupported(t) => write!(f, "XDR does not support type: {}", t),
             Error::Io(msg) => write!(f, "I/O error: {}", msg),
+            Error::BufferTooSmall => write!(f, "output buffer too small"),
+        }
+    }
+}
+
+impl core::error::Error for Error {}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for Error {
+    /// Recovers an [`Error`] that was converted into an I/O error (such as one
+    /// raised inside a [`Read`](std::io::Read) adapter); otherwise maps
+    /// [`UnexpectedEof`](std::io::ErrorKind::UnexpectedEof) to
+    /// [`Error::UnexpectedEof`] and everything else to [`Error::Io`].
+    fn from(e: std::io::Error) -> Self {
+        if e.get_ref().is_some_and(|inner| inner.is::<Error>()) {
+            let inner = e.into_inner().expect("checked above");
+            return *inner.downcast::<Error>().expect("checked above");
+        }
+        match e.kind() {
+            std::io::ErrorKind::UnexpectedEof => Error::UnexpectedEof,
+            _ => Error::Io(e.to_string()),
         }
     }
 }
 
-impl std::error::Error for Error {}
+#[cfg(feature = "std")]
+impl From<Error> for std::io::Error {
+    /// Wraps `e` so that converting back recovers it unchanged.
+    fn from(e: Error) -> Self {
+        let kind = match e {
+            Error::UnexpectedEof => std::io::ErrorKind::UnexpectedEof,
+            Error::Io(_) => std::io::ErrorKind::Other,
+            _ => std::io::ErrorKind::InvalidData,
+        };
+        std::io::Error::new(kind, e)
+    }
+}
 
 impl ser::Error for Error {
     fn custom<T: fmt::Display>(msg: T) -> Self {
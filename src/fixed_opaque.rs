@@ -116,7 +116,7 @@ where
 {
     deserializer.deserialize_newtype_struct(
         crate::FIXED_OPAQUE_TOKEN,
-        FixedOpaqueVisitor::<T>(std::marker::PhantomData),
+        FixedOpaqueVisitor::<T>(core::marker::PhantomData),
     )
 }
 
@@ -140,14 +140,14 @@ impl serde::Serialize for FixedOpaqueHelper<'_> {
 /// drive `inner_de` by calling `T::deserialize(inner_de)`, which for `[u8;N]`
 /// calls `inner_de.deserialize_tuple(N, ...)`. The inner deserializer reads N
 /// raw bytes (with trailing padding consumed) and yields them as a seq of u8.
-struct FixedOpaqueVisitor<T>(std::marker::PhantomData<T>);
+struct FixedOpaqueVisitor<T>(core::marker::PhantomData<T>);
 
 impl<'de, T: XdrFixedOpaque + serde::Deserialize<'de>> serde::de::Visitor<'de>
     for FixedOpaqueVisitor<T>
 {
     type Value = T;
 
-    fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn expecting(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "fixed-length opaque ({} bytes)", T::fixed_len())
     }
 
@@ -163,11 +163,11 @@ impl<'de, T: XdrFixedOpaque + serde::Deserialize<'de>> serde::de::Visitor<'de>
     fn visit_bytes<E: serde::de::Error>(self, v: &[u8]) -> Result<T, E> {
         T::from_exact_bytes(v).ok_or_else(|| E::invalid_length(v.len(), &self))
     }
-    fn visit_byte_buf<E: serde::de::Error>(self, v: Vec<u8>) -> Result<T, E> {
+    fn visit_byte_buf<E: serde::de::Error>(self, v: alloc::vec::Vec<u8>) -> Result<T, E> {
         self.visit_bytes(&v)
     }
     fn visit_seq<A: serde::de::SeqAccess<'de>>(self, mut seq: A) -> Result<T, A::Error> {
-        let mut buf = Vec::with_capacity(T::fixed_len());
+        let mut buf = alloc::vec::Vec::with_capacity(T::fixed_len());
         while let Some(b) = seq.next_element::<u8>()? {
             buf.push(b);
         }
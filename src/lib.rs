@@ -30,16 +30,80 @@
 //! let decoded: FileHandle = from_bytes(&bytes).unwrap();
 //! assert_eq!(fh, decoded);
 //! ```
+//!
+//! ## `no_std`
+//!
+//! The crate is `no_std` and needs `alloc`. The `std` feature (on by default)
+//! adds the `std::io` entry points: [`to_writer`], [`from_reader`] and
+//! [`ReaderDeserializer`]. Without it, the slice-based [`Deserializer`],
+//! [`to_bytes`], [`to_slice`], [`serialized_size`], [`fixed_opaque`],
+//! [`bounded`] and [`Error`] are all available:
+//!
+//! ```toml
+//! xdr-serde = { version = "0.2", default-features = false }
+//! ```
+//!
+//! ## Async I/O
+//!
+//! The `futures` feature adds `from_async_reader` and `to_async_writer` for
+//! `futures::io`; the `tokio` feature adds `from_tokio_reader` and
+//! `to_tokio_writer` for `tokio::io`. Both decode exactly like
+//! [`from_reader`], consuming only the bytes of the value.
+
+#![no_std]
+
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
+pub mod bounded;
 pub mod de;
+pub mod discriminant;
 pub mod error;
 pub mod fixed_opaque;
+pub mod limits;
+pub mod linked_list;
+#[cfg(feature = "mount3")]
+pub mod mount3;
+#[cfg(feature = "nfs3")]
+pub mod nfs3;
+#[cfg(feature = "nfs4")]
+pub mod nfs4;
+#[cfg(feature = "nlm4")]
+pub mod nlm4;
+#[cfg(feature = "nsm")]
+pub mod nsm;
+pub mod rpc;
 pub mod ser;
+#[cfg(any(feature = "mount3", feature = "nfs3", feature = "nfs4"))]
+mod status_union;
 
-pub use de::{Deserializer, ReaderDeserializer, from_bytes, from_bytes_partial, from_reader};
+#[cfg(feature = "futures")]
+pub use de::from_async_reader;
+#[cfg(feature = "tokio")]
+pub use de::from_tokio_reader;
+pub use de::{Deserializer, from_bytes, from_bytes_partial};
+#[cfg(feature = "std")]
+pub use de::{ReaderDeserializer, from_reader};
 pub use error::{Error, Result};
-pub use ser::{Serializer, to_bytes, to_writer};
+pub use limits::DecodeLimits;
+#[cfg(feature = "futures")]
+pub use ser::to_async_writer;
+#[cfg(feature = "tokio")]
+pub use ser::to_tokio_writer;
+#[cfg(feature = "std")]
+pub use ser::to_writer;
+pub use ser::{Serializer, serialized_size, to_bytes, to_slice};
 pub use serde::{Deserialize, Serialize};
+/// Variable-length opaque data with no bound, `opaque name<>`.
+pub use serde_bytes::ByteBuf;
+
+#[cfg(feature = "derive")]
+pub use xdr_serde_derive::{XdrDeserialize, XdrSerialize};
+
+#[doc(hidden)]
+#[path = "private.rs"]
+pub mod __private;
 
 /// Sentinel name passed to `serialize_newtype_struct` / `deserialize_newtype_struct`
 /// so that our XDR serializer can distinguish fixed-length opaque data (no length
@@ -48,3 +112,19 @@ pub use serde::{Deserialize, Serialize};
 /// This is an implementation detail; users interact with it only via
 /// `#[serde(with = "xdr_serde::fixed_opaque")]`.
 pub const FIXED_OPAQUE_TOKEN: &str = "__xdr_fixed_opaque__";
+
+/// Sentinel name passed to `serialize_tuple_struct` / `deserialize_tuple_struct`
+/// to carry an XDR length bound (`string<N>`, `opaque<N>`, `T<N>`). The tuple
+/// length argument is `N`; the single field is the bounded value.
+///
+/// This is an implementation detail; users interact with it only via the
+/// types and functions in [`bounded`].
+pub const BOUNDED_TOKEN: &str = "__xdr_bounded__";
+
+/// Sentinel name passed to `deserialize_tuple_struct` for an XDR linked list,
+/// so that our XDR deserializers count its nodes against
+/// [`DecodeLimits::max_elements`].
+///
+/// This is an implementation detail; users interact with it only via
+/// `#[serde(with = "xdr_serde::linked_list")]`.
+pub const LINKED_LIST_TOKEN: &str = "__xdr_linked_list__";
@@ -0,0 +1,60 @@
+//! Resource limits applied while decoding untrusted XDR input.
+//!
+//! XDR length prefixes are attacker-controlled 32-bit values: a 4-byte packet
+//! can claim a 4 GiB string or a billion-element array. [`DecodeLimits`] caps
+//! what a single decode is allowed to consume before any allocation happens.
+//!
+//! ```rust
+//! use serde::Deserialize;
+//! use xdr_serde::{DecodeLimits, Deserializer, Error};
+//!
+//! let limits = DecodeLimits { max_elements: 16, ..DecodeLimits::default() };
+//!
+//! // A count prefix of 1 000 000 followed by nothing.
+//! let bytes = [0x00, 0x0F, 0x42, 0x40];
+//! let mut de = Deserializer::new(&bytes).with_limits(limits);
+//! assert_eq!(
+//!     Vec::<u32>::deserialize(&mut de),
+//!     Err(Error::LengthOverflow { max: 16, got: 1_000_000 }),
+//! );
+//! ```
+//!
+//! Every violation is reported as [`Error::LengthOverflow`](crate::Error::LengthOverflow)
+//! carrying the configured maximum and the value that exceeded it.
+
+/// Upper bounds enforced by [`Deserializer`](crate::Deserializer) and
+/// [`ReaderDeserializer`](crate::ReaderDeserializer).
+///
+/// The default is [`DecodeLimits::UNLIMITED`]; override individual fields with
+/// struct update syntax.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecodeLimits {
+    /// Maximum byte length of an XDR `string`.
+    pub max_string_len: u32,
+    /// Maximum byte length of variable-length `opaque` data.
+    pub max_opaque_len: u32,
+    /// Maximum element count of a variable-length array or map, and node
+    /// count of a [linked list](crate::linked_list).
+    pub max_elements: u32,
+    /// Maximum nesting depth of structures, unions, arrays and optional-data.
+    pub max_depth: u32,
+    /// Maximum number of input bytes a single decode may consume.
+    pub max_bytes: usize,
+}
+
+impl DecodeLimits {
+    /// No limits beyond what the XDR encoding itself can express.
+    pub const UNLIMITED: DecodeLimits = DecodeLimits {
+        max_string_len: u32::MAX,
+        max_opaque_len: u32::MAX,
+        max_elements: u32::MAX,
+        max_depth: u32::MAX,
+        max_bytes: usize::MAX,
+    };
+}
+
+impl Default for DecodeLimits {
+    fn default() -> Self {
+        DecodeLimits::UNLIMITED
+    }
+}
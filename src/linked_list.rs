@@ -0,0 +1,145 @@
+//! Serde helper module for XDR linked lists (RFC 4506 §4.19).
+//!
+//! XDR has no list type of its own. Protocols build lists out of
+//! optional-data: each node holds an item and a pointer to the next node,
+//! and a null pointer ends the list.
+//!
+//! ```text
+//! struct entry {
+//!     unsigned int fileid;
+//!     string name<>;
+//!     entry *next;
+//! };
+//! ```
+//!
+//! Mirrored directly in Rust, that is an `Option<Box<Entry>>` chain: awkward
+//! to build and walk, and decoded recursively, one stack frame per node. This
+//! module encodes a `Vec<T>` in the same wire format and decodes it in a
+//! loop, so lists of any length decode in constant stack space.
+//!
+//! # Usage
+//!
+//! Annotate a `Vec<T>` field with `#[serde(with = "xdr_serde::linked_list")]`,
+//! where `T` holds the node's fields other than the pointer:
+//!
+//! ```rust
+//! use serde::{Deserialize, Serialize};
+//! use xdr_serde::{from_bytes, to_bytes};
+//!
+//! #[derive(Debug, PartialEq, Serialize, Deserialize)]
+//! struct Entry {
+//!     fileid: u32,
+//!     name: String,
+//! }
+//!
+//! // struct dirlist { entry *entries; bool eof; };
+//! #[derive(Debug, PartialEq, Serialize, Deserialize)]
+//! struct DirList {
+//!     #[serde(with = "xdr_serde::linked_list")]
+//!     entries: Vec<Entry>,
+//!     eof: bool,
+//! }
+//!
+//! let list = DirList {
+//!     entries: vec![
+//!         Entry { fileid: 1, name: ".".into() },
+//!         Entry { fileid: 2, name: "..".into() },
+//!     ],
+//!     eof: true,
+//! };
+//! let bytes = to_bytes(&list).unwrap();
+//! // TRUE, entry (4 + 8), TRUE, entry (4 + 8), FALSE, eof
+//! assert_eq!(bytes.len(), 4 + 12 + 4 + 12 + 4 + 4);
+//! assert_eq!(from_bytes::<DirList>(&bytes).unwrap(), list);
+//! ```
+//!
+//! A list that is a whole value rather than a field, such as a procedure
+//! result of type `entry *`, is wrapped in a single-field tuple struct:
+//!
+//! ```rust
+//! # use serde::{Deserialize, Serialize};
+//! #[derive(Serialize, Deserialize)]
+//! struct Entries(#[serde(with = "xdr_serde::linked_list")] Vec<u32>);
+//! ```
+//!
+//! # Wire format
+//!
+//! Each item is preceded by the boolean TRUE (`00 00 00 01`) and the list is
+//! ended by FALSE (`00 00 00 00`); an empty list is a lone FALSE. This is
+//! byte-for-byte the encoding of the nested `Option<Box<Node>>` form.
+//!
+//! # Limits
+//!
+//! The list is decoded as one level of nesting, however long it is, so
+//! [`DecodeLimits::max_depth`](crate::DecodeLimits::max_depth) does not cap
+//! its length. Its nodes count against
+//! [`max_elements`](crate::DecodeLimits::max_elements) instead, as the
+//! elements of a counted array do: the node past the limit fails with
+//! [`Error::LengthOverflow`](crate::Error::LengthOverflow) before its item
+//! is decoded.
+
+use alloc::vec::Vec;
+use core::fmt;
+use core::marker::PhantomData;
+use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeTuple, Serializer};
+
+/// Serialize `items` as an XDR linked list: TRUE and the item for each
+/// element, then FALSE.
+pub fn serialize<T, S>(items: &[T], serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: Serialize,
+    S: Serializer,
+{
+    // XDR has no length prefix here, but other formats may want the count.
+    let mut tuple = serializer.serialize_tuple(2 * items.len() + 1)?;
+    for item in items {
+        tuple.serialize_element(&true)?;
+        tuple.serialize_element(item)?;
+    }
+    tuple.serialize_element(&false)?;
+    tuple.end()
+}
+
+/// Deserialize an XDR linked list into a `Vec`, iteratively.
+///
+/// A pointer discriminant other than 0 or 1 is rejected with
+/// [`Error::InvalidBool`](crate::Error::InvalidBool).
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    // The XDR deserializers yield elements until the visitor stops asking,
+    // so the length is only an upper bound. The name tells them to count the
+    // nodes against `max_elements`.
+    deserializer.deserialize_tuple_struct(
+        crate::LINKED_LIST_TOKEN,
+        usize::MAX,
+        LinkedListVisitor(PhantomData),
+    )
+}
+
+struct LinkedListVisitor<T>(PhantomData<T>);
+
+impl<'de, T: Deserialize<'de>> Visitor<'de> for LinkedListVisitor<T> {
+    type Value = Vec<T>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("an XDR linked list")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Vec<T>, A::Error> {
+        let mut items = Vec::new();
+        loop {
+            match seq.next_element::<bool>()? {
+                Some(true) => match seq.next_element()? {
+                    Some(item) => items.push(item),
+                    None => return Err(de::Error::invalid_length(2 * items.len() + 1, &self)),
+                },
+                Some(false) => return Ok(items),
+                None => return Err(de::Error::invalid_length(2 * items.len(), &self)),
+            }
+        }
+    }
+}
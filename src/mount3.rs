@@ -0,0 +1,293 @@
+//! MOUNT version 3 protocol types (RFC 1813 appendix I). Requires the
+//! `mount3` feature.
+//!
+//! NFSv3 clients use the MOUNT program to turn an exported path into the
+//! root file handle of the export, and to list what a server exports. The
+//! protocol's three linked lists — `exports`, each export's `groups`, and
+//! `mountlist` — are `Vec`s, encoded with [`linked_list`](crate::linked_list).
+//!
+//! ```rust
+//! use xdr_serde::mount3::{ExportNode, Exports};
+//! use xdr_serde::{from_bytes, to_bytes};
+//!
+//! // The reply to MOUNTPROC3_EXPORT: /srv exported to two networks.
+//! let exports = Exports(vec![
+//!     ExportNode::new("/srv", &["10.0.0.0/8", "192.168.1.0/24"]).unwrap(),
+//! ]);
+//! let bytes = to_bytes(&exports).unwrap();
+//! // more, ex_dir, (more, name) twice, no more groups, no more exports
+//! assert_eq!(bytes.len(), 4 + 8 + (4 + 16) + (4 + 20) + 4 + 4);
+//!
+//! let decoded: Exports = from_bytes(&bytes).unwrap();
+//! let srv = decoded.get("/srv").unwrap();
+//! assert_eq!(srv.groups().collect::<Vec<_>>(), ["10.0.0.0/8", "192.168.1.0/24"]);
+//! ```
+
+use crate::bounded::{BoundedBytes, BoundedString};
+use crate::error::Result;
+use crate::status_union::{self, ArmBody, StatusUnion};
+use crate::xdr_enum;
+use alloc::vec::Vec;
+use serde::de::{Deserializer, SeqAccess};
+use serde::ser::{self, Serializer};
+use serde::{Deserialize, Serialize};
+
+/// The MOUNT program number.
+pub const MOUNT_PROGRAM: u32 = 100005;
+
+/// The MOUNT protocol version described here.
+pub const MOUNT_V3: u32 = 3;
+
+/// The maximum length of a [`Dirpath`], in bytes.
+pub const MNTPATHLEN: u32 = 1024;
+
+/// The maximum length of a [`Name`], in bytes.
+pub const MNTNAMLEN: u32 = 255;
+
+/// The maximum size of a [`Fhandle3`], in bytes.
+pub const FHSIZE3: u32 = 64;
+
+/// Do nothing.
+pub const MOUNTPROC3_NULL: u32 = 0;
+/// Mount a [`Dirpath`]; returns [`Mountres3`].
+pub const MOUNTPROC3_MNT: u32 = 1;
+/// List the clients' mounts; returns [`MountList`].
+pub const MOUNTPROC3_DUMP: u32 = 2;
+/// Remove the caller's mount of a [`Dirpath`]; returns `void`.
+pub const MOUNTPROC3_UMNT: u32 = 3;
+/// Remove all of the caller's mounts; returns `void`.
+pub const MOUNTPROC3_UMNTALL: u32 = 4;
+/// List the exports; returns [`Exports`].
+pub const MOUNTPROC3_EXPORT: u32 = 5;
+
+/// `typedef string dirpath<MNTPATHLEN>`: a path on the server.
+pub type Dirpath = BoundedString<MNTPATHLEN>;
+
+/// `typedef string name<MNTNAMLEN>`: a host or group name.
+pub type Name = BoundedString<MNTNAMLEN>;
+
+/// `typedef opaque fhandle3<FHSIZE3>`: the NFSv3 file handle of an export's
+/// root, with the same encoding as `nfs_fh3`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Fhandle3(pub BoundedBytes<FHSIZE3>);
+
+impl Fhandle3 {
+    /// A file handle holding `data`.
+    ///
+    /// Fails with [`Error::LengthOverflow`](crate::Error::LengthOverflow) if
+    /// `data` is longer than [`FHSIZE3`].
+    pub fn new(data: Vec<u8>) -> Result<Self> {
+        Ok(Fhandle3(BoundedBytes::new(data)?))
+    }
+}
+
+xdr_enum! {
+    /// `enum mountstat3`
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum Mountstat3 {
+        /// `MNT3_OK`
+        Ok = 0,
+        Perm = 1,
+        NoEnt = 2,
+        Io = 5,
+        Acces = 13,
+        NotDir = 20,
+        Inval = 22,
+        NameTooLong = 63,
+        NotSupp = 10004,
+        ServerFault = 10006,
+    }
+}
+
+/// `struct mountres3_ok`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Mountres3Ok {
+    pub fhandle: Fhandle3,
+    /// The RPC security flavors the server accepts for the export, most
+    /// preferred first; values of [`AuthFlavor`](crate::rpc::AuthFlavor)
+    /// and others.
+    pub auth_flavors: Vec<i32>,
+}
+
+/// `union mountres3`: the result of `MOUNTPROC3_MNT`.
+///
+/// ```text
+/// union mountres3 switch (mountstat3 fhs_status) {
+/// case MNT3_OK:
+///     mountres3_ok mountinfo;
+/// default:
+///     void;
+/// };
+/// ```
+///
+/// Encoding an `Err` with status [`Mountstat3::Ok`] fails with
+/// [`Error::Message`](crate::Error::Message).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Mountres3 {
+    /// `MNT3_OK` and the export's root file handle.
+    Ok(Mountres3Ok),
+    /// An error status.
+    Err(Mountstat3),
+}
+
+impl Mountres3 {
+    /// The `fhs_status` discriminant.
+    pub fn status(&self) -> Mountstat3 {
+        match self {
+            Mountres3::Ok(_) => Mountstat3::Ok,
+            Mountres3::Err(status) => *status,
+        }
+    }
+
+    /// Convert into a `Result`.
+    pub fn into_result(self) -> core::result::Result<Mountres3Ok, Mountstat3> {
+        match self {
+            Mountres3::Ok(mountinfo) => Ok(mountinfo),
+            Mountres3::Err(status) => Err(status),
+        }
+    }
+}
+
+impl Serialize for Mountres3 {
+    fn serialize<S: Serializer>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error> {
+        match self {
+            Mountres3::Ok(mountinfo) => {
+                status_union::serialize(serializer, &Mountstat3::Ok, mountinfo)
+            }
+            Mountres3::Err(Mountstat3::Ok) => {
+                Err(ser::Error::custom("MNT3_OK without a mountinfo body"))
+            }
+            Mountres3::Err(status) => status_union::serialize_void(serializer, status),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Mountres3 {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> core::result::Result<Self, D::Error> {
+        status_union::deserialize(deserializer)
+    }
+}
+
+impl<'de> StatusUnion<'de> for Mountres3 {
+    type Status = Mountstat3;
+    const EXPECTING: &'static str = "a mountres3";
+
+    fn arm<A: SeqAccess<'de>>(
+        status: Mountstat3,
+        body: ArmBody<A>,
+    ) -> core::result::Result<Self, A::Error> {
+        Ok(match status {
+            Mountstat3::Ok => Mountres3::Ok(body.read()?),
+            status => Mountres3::Err(status),
+        })
+    }
+}
+
+// ── Lists ─────────────────────────────────────────────────────────────────
+
+/// `struct mountbody`, without its `ml_next` pointer: a client's mount.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct MountBody {
+    pub ml_hostname: Name,
+    pub ml_directory: Dirpath,
+}
+
+/// `mountlist`: the mounts returned by `MOUNTPROC3_DUMP`.
+///
+/// ```text
+/// typedef struct mountbody *mountlist;
+///
+/// struct mountbody {
+///     name      ml_hostname;
+///     dirpath   ml_directory;
+///     mountlist ml_next;
+/// };
+/// ```
+///
+/// Encoded as a [linked list](crate::linked_list); an empty list is a null
+/// pointer.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MountList(#[serde(with = "crate::linked_list")] pub Vec<MountBody>);
+
+impl MountList {
+    /// The directories `hostname` has mounted.
+    pub fn directories<'a>(&'a self, hostname: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        self.0
+            .iter()
+            .filter(move |mount| mount.ml_hostname.as_str() == hostname)
+            .map(|mount| mount.ml_directory.as_str())
+    }
+}
+
+/// `struct exportnode`, without its `ex_next` pointer: an exported
+/// directory and who may mount it.
+///
+/// ```text
+/// typedef struct groupnode *groups;
+///
+/// struct groupnode {
+///     name   gr_name;
+///     groups gr_next;
+/// };
+///
+/// struct exportnode {
+///     dirpath ex_dir;
+///     groups  ex_groups;
+///     exports ex_next;
+/// };
+/// ```
+///
+/// `ex_groups` holds the `gr_name` of each `groupnode`: host names,
+/// netgroups or networks, as the server chooses to describe them. An empty
+/// list usually means any host may mount the directory.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ExportNode {
+    pub ex_dir: Dirpath,
+    #[serde(with = "crate::linked_list")]
+    pub ex_groups: Vec<Name>,
+}
+
+impl ExportNode {
+    /// An export of `dir` to `groups`.
+    ///
+    /// Fails with [`Error::LengthOverflow`](crate::Error::LengthOverflow) if
+    /// `dir` is longer than [`MNTPATHLEN`] or a group longer than
+    /// [`MNTNAMLEN`].
+    pub fn new(dir: &str, groups: &[&str]) -> Result<Self> {
+        Ok(ExportNode {
+            ex_dir: Dirpath::try_from(dir)?,
+            ex_groups: groups
+                .iter()
+                .map(|&group| Name::try_from(group))
+                .collect::<Result<_>>()?,
+        })
+    }
+
+    /// The group names, in list order.
+    pub fn groups(&self) -> impl Iterator<Item = &str> {
+        self.ex_groups.iter().map(|group| group.as_str())
+    }
+}
+
+/// `exports`: the export list returned by `MOUNTPROC3_EXPORT`.
+///
+/// ```text
+/// typedef struct exportnode *exports;
+/// ```
+///
+/// Encoded as a [linked list](crate::linked_list) of [`ExportNode`]s, each
+/// carrying its own linked list of groups.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Exports(#[serde(with = "crate::linked_list")] pub Vec<ExportNode>);
+
+impl Exports {
+    /// The export of `dir`, if any.
+    pub fn get(&self, dir: &str) -> Option<&ExportNode> {
+        self.0.iter().find(|export| export.ex_dir.as_str() == dir)
+    }
+
+    /// The exported directories, in list order.
+    pub fn dirs(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().map(|export| export.ex_dir.as_str())
+    }
+}
@@ -0,0 +1,1132 @@
+//! NFS version 3 protocol types (RFC 1813). Requires the `nfs3` feature.
+//!
+//! Every argument and result type of the 22 NFSv3 procedures, named after
+//! the RFC's XDR definitions (`READDIR3resok` is [`Readdir3Resok`]), with the
+//! RFC's discriminants. Procedure results are [`Nfs3Res`] values: the
+//! `resok` body on `NFS3_OK` and the `resfail` body with the error status
+//! otherwise.
+//!
+//! ```rust
+//! use xdr_serde::nfs3::*;
+//! use xdr_serde::{from_bytes, to_bytes};
+//!
+//! let args = Lookup3Args {
+//!     what: Diropargs3 { dir: NfsFh3::new(vec![1; 32]).unwrap(), name: "README".into() },
+//! };
+//! assert_eq!(to_bytes(&args).unwrap().len(), 4 + 32 + 4 + 8);
+//!
+//! // A failed LOOKUP: NFS3ERR_NOENT, then the directory's post_op_attr.
+//! let reply = [0, 0, 0, 2, 0, 0, 0, 0];
+//! let res: Lookup3Res = from_bytes(&reply).unwrap();
+//! assert_eq!(res.status(), Nfsstat3::NoEnt);
+//! assert_eq!(res, Nfs3Res::Err(Nfsstat3::NoEnt, Lookup3Resfail { dir_attributes: None }));
+//! ```
+//!
+//! Boolean-switched unions such as `post_op_attr` and `set_mode3` are
+//! `Option`s, which have the same encoding. Linked lists (`entry3`,
+//! `entryplus3`) are `Vec`s through [`linked_list`](crate::linked_list).
+//! File names and paths are `String`s, so a name that is not valid UTF-8
+//! fails to decode with [`Error::InvalidString`](crate::Error::InvalidString).
+
+use crate::ByteBuf;
+use crate::bounded::BoundedBytes;
+use crate::error::Result;
+use crate::status_union::{self, ArmBody, StatusUnion};
+use crate::xdr_enum;
+use alloc::string::String;
+use alloc::vec::Vec;
+use serde::de::{Deserializer, SeqAccess};
+use serde::ser::{self, Serializer};
+use serde::{Deserialize, Serialize};
+
+/// The NFS program number.
+pub const NFS_PROGRAM: u32 = 100003;
+
+/// The NFS protocol version described here.
+pub const NFS_V3: u32 = 3;
+
+/// The maximum size of a file handle, in bytes.
+pub const NFS3_FHSIZE: u32 = 64;
+
+/// The size of a [`Cookieverf3`], in bytes.
+pub const NFS3_COOKIEVERFSIZE: usize = 8;
+
+/// The size of a [`Createverf3`], in bytes.
+pub const NFS3_CREATEVERFSIZE: usize = 8;
+
+/// The size of a [`Writeverf3`], in bytes.
+pub const NFS3_WRITEVERFSIZE: usize = 8;
+
+/// Procedure 0: do nothing.
+pub const NFSPROC3_NULL: u32 = 0;
+/// Procedure 1: takes [`Getattr3Args`], returns [`Getattr3Res`].
+pub const NFSPROC3_GETATTR: u32 = 1;
+/// Procedure 2: takes [`Setattr3Args`], returns [`Setattr3Res`].
+pub const NFSPROC3_SETATTR: u32 = 2;
+/// Procedure 3: takes [`Lookup3Args`], returns [`Lookup3Res`].
+pub const NFSPROC3_LOOKUP: u32 = 3;
+/// Procedure 4: takes [`Access3Args`], returns [`Access3Res`].
+pub const NFSPROC3_ACCESS: u32 = 4;
+/// Procedure 5: takes [`Readlink3Args`], returns [`Readlink3Res`].
+pub const NFSPROC3_READLINK: u32 = 5;
+/// Procedure 6: takes [`Read3Args`], returns [`Read3Res`].
+pub const NFSPROC3_READ: u32 = 6;
+/// Procedure 7: takes [`Write3Args`], returns [`Write3Res`].
+pub const NFSPROC3_WRITE: u32 = 7;
+/// Procedure 8: takes [`Create3Args`], returns [`Create3Res`].
+pub const NFSPROC3_CREATE: u32 = 8;
+/// Procedure 9: takes [`Mkdir3Args`], returns [`Mkdir3Res`].
+pub const NFSPROC3_MKDIR: u32 = 9;
+/// Procedure 10: takes [`Symlink3Args`], returns [`Symlink3Res`].
+pub const NFSPROC3_SYMLINK: u32 = 10;
+/// Procedure 11: takes [`Mknod3Args`], returns [`Mknod3Res`].
+pub const NFSPROC3_MKNOD: u32 = 11;
+/// Procedure 12: takes [`Remove3Args`], returns [`Remove3Res`].
+pub const NFSPROC3_REMOVE: u32 = 12;
+/// Procedure 13: takes [`Rmdir3Args`], returns [`Rmdir3Res`].
+pub const NFSPROC3_RMDIR: u32 = 13;
+/// Procedure 14: takes [`Rename3Args`], returns [`Rename3Res`].
+pub const NFSPROC3_RENAME: u32 = 14;
+/// Procedure 15: takes [`Link3Args`], returns [`Link3Res`].
+pub const NFSPROC3_LINK: u32 = 15;
+/// Procedure 16: takes [`Readdir3Args`], returns [`Readdir3Res`].
+pub const NFSPROC3_READDIR: u32 = 16;
+/// Procedure 17: takes [`Readdirplus3Args`], returns [`Readdirplus3Res`].
+pub const NFSPROC3_READDIRPLUS: u32 = 17;
+/// Procedure 18: takes [`Fsstat3Args`], returns [`Fsstat3Res`].
+pub const NFSPROC3_FSSTAT: u32 = 18;
+/// Procedure 19: takes [`Fsinfo3Args`], returns [`Fsinfo3Res`].
+pub const NFSPROC3_FSINFO: u32 = 19;
+/// Procedure 20: takes [`Pathconf3Args`], returns [`Pathconf3Res`].
+pub const NFSPROC3_PATHCONF: u32 = 20;
+/// Procedure 21: takes [`Commit3Args`], returns [`Commit3Res`].
+pub const NFSPROC3_COMMIT: u32 = 21;
+
+/// [`Access3Args::access`] bit: read data or list a directory.
+pub const ACCESS3_READ: u32 = 0x0001;
+/// [`Access3Args::access`] bit: look up a name in a directory.
+pub const ACCESS3_LOOKUP: u32 = 0x0002;
+/// [`Access3Args::access`] bit: rewrite data or modify a directory.
+pub const ACCESS3_MODIFY: u32 = 0x0004;
+/// [`Access3Args::access`] bit: write past the end of a file or add to a
+/// directory.
+pub const ACCESS3_EXTEND: u32 = 0x0008;
+/// [`Access3Args::access`] bit: delete a directory entry.
+pub const ACCESS3_DELETE: u32 = 0x0010;
+/// [`Access3Args::access`] bit: execute a file or search a directory.
+pub const ACCESS3_EXECUTE: u32 = 0x0020;
+
+/// [`Fsinfo3Resok::properties`] bit: the file system supports hard links.
+pub const FSF3_LINK: u32 = 0x0001;
+/// [`Fsinfo3Resok::properties`] bit: the file system supports symbolic links.
+pub const FSF3_SYMLINK: u32 = 0x0002;
+/// [`Fsinfo3Resok::properties`] bit: `PATHCONF` results are the same for
+/// every file.
+pub const FSF3_HOMOGENEOUS: u32 = 0x0008;
+/// [`Fsinfo3Resok::properties`] bit: the server can set times with
+/// `SETATTR`.
+pub const FSF3_CANSETTIME: u32 = 0x0010;
+
+/// `filename3`: a directory entry name.
+pub type Filename3 = String;
+
+/// `nfspath3`: the target of a symbolic link.
+pub type Nfspath3 = String;
+
+/// Variable-length opaque data with no bound: file contents.
+pub type Opaque3 = ByteBuf;
+
+xdr_enum! {
+    /// `enum nfsstat3`: the status of every procedure result.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum Nfsstat3 {
+        /// `NFS3_OK`
+        Ok = 0,
+        /// `NFS3ERR_PERM`: not the owner.
+        Perm = 1,
+        /// `NFS3ERR_NOENT`: no such file or directory.
+        NoEnt = 2,
+        /// `NFS3ERR_IO`: a hard I/O error.
+        Io = 5,
+        /// `NFS3ERR_NXIO`: no such device or address.
+        NxIo = 6,
+        /// `NFS3ERR_ACCES`: permission denied.
+        Acces = 13,
+        /// `NFS3ERR_EXIST`: the file exists.
+        Exist = 17,
+        /// `NFS3ERR_XDEV`: a cross-device hard link.
+        XDev = 18,
+        /// `NFS3ERR_NODEV`: no such device.
+        NoDev = 19,
+        /// `NFS3ERR_NOTDIR`: not a directory.
+        NotDir = 20,
+        /// `NFS3ERR_ISDIR`: is a directory.
+        IsDir = 21,
+        /// `NFS3ERR_INVAL`: an invalid argument.
+        Inval = 22,
+        /// `NFS3ERR_FBIG`: the file would be too large.
+        FBig = 27,
+        /// `NFS3ERR_NOSPC`: no space left on the device.
+        NoSpc = 28,
+        /// `NFS3ERR_ROFS`: a read-only file system.
+        RoFs = 30,
+        /// `NFS3ERR_MLINK`: too many hard links.
+        MLink = 31,
+        /// `NFS3ERR_NAMETOOLONG`: the name is too long.
+        NameTooLong = 63,
+        /// `NFS3ERR_NOTEMPTY`: the directory is not empty.
+        NotEmpty = 66,
+        /// `NFS3ERR_DQUOT`: the quota is exceeded.
+        DQuot = 69,
+        /// `NFS3ERR_STALE`: the file handle no longer refers to a file.
+        Stale = 70,
+        /// `NFS3ERR_REMOTE`: too many levels of remote in the path.
+        Remote = 71,
+        /// `NFS3ERR_BADHANDLE`: an illegal file handle.
+        BadHandle = 10001,
+        /// `NFS3ERR_NOT_SYNC`: the `SETATTR` guard did not match.
+        NotSync = 10002,
+        /// `NFS3ERR_BAD_COOKIE`: the `READDIR` cookie is stale.
+        BadCookie = 10003,
+        /// `NFS3ERR_NOTSUPP`: the operation is not supported.
+        NotSupp = 10004,
+        /// `NFS3ERR_TOOSMALL`: the buffer or request is too small.
+        TooSmall = 10005,
+        /// `NFS3ERR_SERVERFAULT`: an error not covered by the other codes.
+        ServerFault = 10006,
+        /// `NFS3ERR_BADTYPE`: the server cannot create objects of this type.
+        BadType = 10007,
+        /// `NFS3ERR_JUKEBOX`: the request could not complete in time; retry
+        /// later.
+        Jukebox = 10008,
+    }
+}
+
+xdr_enum! {
+    /// `enum ftype3`: the type of a file.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum Ftype3 {
+        /// `NF3REG`: a regular file.
+        Reg = 1,
+        /// `NF3DIR`: a directory.
+        Dir = 2,
+        /// `NF3BLK`: a block special device.
+        Blk = 3,
+        /// `NF3CHR`: a character special device.
+        Chr = 4,
+        /// `NF3LNK`: a symbolic link.
+        Lnk = 5,
+        /// `NF3SOCK`: a socket.
+        Sock = 6,
+        /// `NF3FIFO`: a named pipe.
+        Fifo = 7,
+    }
+}
+
+/// The result of an NFSv3 procedure.
+///
+/// ```text
+/// union XXX3res switch (nfsstat3 status) {
+/// case NFS3_OK:
+///     XXX3resok resok;
+/// default:
+///     XXX3resfail resfail;
+/// };
+/// ```
+///
+/// Procedures whose `default` arm is `void` use `F = ()`. Encoding an `Err`
+/// with status [`Nfsstat3::Ok`] fails with
+/// [`Error::Message`](crate::Error::Message), since it would decode as an
+/// `Ok`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Nfs3Res<T, F = ()> {
+    /// `NFS3_OK` and the `resok` body.
+    Ok(T),
+    /// An error status and the `resfail` body.
+    Err(Nfsstat3, F),
+}
+
+impl<T, F> Nfs3Res<T, F> {
+    /// The `status` discriminant.
+    pub fn status(&self) -> Nfsstat3 {
+        match self {
+            Nfs3Res::Ok(_) => Nfsstat3::Ok,
+            Nfs3Res::Err(status, _) => *status,
+        }
+    }
+
+    /// Convert into a `Result`, keeping the failure body with the status.
+    pub fn into_result(self) -> core::result::Result<T, (Nfsstat3, F)> {
+        match self {
+            Nfs3Res::Ok(resok) => Ok(resok),
+            Nfs3Res::Err(status, resfail) => Err((status, resfail)),
+        }
+    }
+}
+
+impl<T: Serialize, F: Serialize> Serialize for Nfs3Res<T, F> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error> {
+        match self {
+            Nfs3Res::Ok(resok) => status_union::serialize(serializer, &Nfsstat3::Ok, resok),
+            Nfs3Res::Err(Nfsstat3::Ok, _) => Err(ser::Error::custom("NFS3_OK with a resfail body")),
+            Nfs3Res::Err(status, resfail) => status_union::serialize(serializer, status, resfail),
+        }
+    }
+}
+
+impl<'de, T: Deserialize<'de>, F: Deserialize<'de>> Deserialize<'de> for Nfs3Res<T, F> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> core::result::Result<Self, D::Error> {
+        status_union::deserialize(deserializer)
+    }
+}
+
+impl<'de, T: Deserialize<'de>, F: Deserialize<'de>> StatusUnion<'de> for Nfs3Res<T, F> {
+    type Status = Nfsstat3;
+    const EXPECTING: &'static str = "an NFSv3 result";
+
+    fn arm<A: SeqAccess<'de>>(
+        status: Nfsstat3,
+        body: ArmBody<A>,
+    ) -> core::result::Result<Self, A::Error> {
+        Ok(match status {
+            Nfsstat3::Ok => Nfs3Res::Ok(body.read()?),
+            status => Nfs3Res::Err(status, body.read()?),
+        })
+    }
+}
+
+/// `struct specdata3`: the major and minor numbers of a device.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Specdata3 {
+    pub specdata1: u32,
+    pub specdata2: u32,
+}
+
+/// `struct nfs_fh3`: a file handle, at most [`NFS3_FHSIZE`] bytes.
+///
+/// ```text
+/// struct nfs_fh3 {
+///     opaque data<NFS3_FHSIZE>;
+/// };
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct NfsFh3 {
+    pub data: BoundedBytes<NFS3_FHSIZE>,
+}
+
+impl NfsFh3 {
+    /// A file handle holding `data`.
+    ///
+    /// Fails with [`Error::LengthOverflow`](crate::Error::LengthOverflow) if `data` is longer than
+    /// [`NFS3_FHSIZE`].
+    pub fn new(data: Vec<u8>) -> Result<Self> {
+        Ok(NfsFh3 {
+            data: BoundedBytes::new(data)?,
+        })
+    }
+}
+
+/// `struct nfstime3`: a time in seconds and nanoseconds since the epoch.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Nfstime3 {
+    pub seconds: u32,
+    pub nseconds: u32,
+}
+
+/// `struct fattr3`: the attributes of a file.
+///
+/// ```text
+/// struct fattr3 {
+///     ftype3     type;
+///     mode3      mode;
+///     uint32     nlink;
+///     uid3       uid;
+///     gid3       gid;
+///     size3      size;
+///     size3      used;
+///     specdata3  rdev;
+///     uint64     fsid;
+///     fileid3    fileid;
+///     nfstime3   atime;
+///     nfstime3   mtime;
+///     nfstime3   ctime;
+/// };
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Fattr3 {
+    pub r#type: Ftype3,
+    pub mode: u32,
+    pub nlink: u32,
+    pub uid: u32,
+    pub gid: u32,
+    pub size: u64,
+    pub used: u64,
+    pub rdev: Specdata3,
+    pub fsid: u64,
+    pub fileid: u64,
+    pub atime: Nfstime3,
+    pub mtime: Nfstime3,
+    pub ctime: Nfstime3,
+}
+
+/// `union post_op_attr switch (bool attributes_follow)`: attributes the
+/// server may omit.
+pub type PostOpAttr = Option<Fattr3>;
+
+/// `struct wcc_attr`: the attributes checked for weak cache consistency.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct WccAttr {
+    pub size: u64,
+    pub mtime: Nfstime3,
+    pub ctime: Nfstime3,
+}
+
+/// `union pre_op_attr switch (bool attributes_follow)`.
+pub type PreOpAttr = Option<WccAttr>;
+
+/// `struct wcc_data`: attributes before and after an operation that changed
+/// an object.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct WccData {
+    pub before: PreOpAttr,
+    pub after: PostOpAttr,
+}
+
+/// `union post_op_fh3 switch (bool handle_follows)`.
+pub type PostOpFh3 = Option<NfsFh3>;
+
+xdr_enum! {
+    /// `enum time_how`: how `SETATTR` sets a time.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum TimeHow {
+        DontChange = 0,
+        SetToServerTime = 1,
+        SetToClientTime = 2,
+    }
+}
+
+/// `union set_mode3 switch (bool set_it)`.
+pub type SetMode3 = Option<u32>;
+
+/// `union set_uid3 switch (bool set_it)`.
+pub type SetUid3 = Option<u32>;
+
+/// `union set_gid3 switch (bool set_it)`.
+pub type SetGid3 = Option<u32>;
+
+/// `union set_size3 switch (bool set_it)`.
+pub type SetSize3 = Option<u64>;
+
+xdr_enum! {
+    /// `union set_atime switch (time_how set_it)`, switched on [`TimeHow`].
+    ///
+    /// ```text
+    /// union set_atime switch (time_how set_it) {
+    /// case SET_TO_CLIENT_TIME:
+    ///     nfstime3 atime;
+    /// default:
+    ///     void;
+    /// };
+    /// ```
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+    pub enum SetAtime {
+        #[default]
+        DontChange = 0,
+        SetToServerTime = 1,
+        SetToClientTime(Nfstime3) = 2,
+    }
+}
+
+xdr_enum! {
+    /// `union set_mtime switch (time_how set_it)`, switched on [`TimeHow`].
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+    pub enum SetMtime {
+        #[default]
+        DontChange = 0,
+        SetToServerTime = 1,
+        SetToClientTime(Nfstime3) = 2,
+    }
+}
+
+/// `struct sattr3`: the attributes to set; `None` and `DontChange` leave
+/// an attribute unchanged.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Sattr3 {
+    pub mode: SetMode3,
+    pub uid: SetUid3,
+    pub gid: SetGid3,
+    pub size: SetSize3,
+    pub atime: SetAtime,
+    pub mtime: SetMtime,
+}
+
+/// `struct diropargs3`: a name in a directory.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Diropargs3 {
+    pub dir: NfsFh3,
+    pub name: Filename3,
+}
+
+macro_rules! verifier {
+    ($(#[$meta:meta])* $name:ident, $size:ident) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+        pub struct $name(#[serde(with = "crate::fixed_opaque")] pub [u8; $size]);
+    };
+}
+
+verifier!(
+    /// `typedef opaque cookieverf3[NFS3_COOKIEVERFSIZE]`: validates
+    /// `READDIR` cookies.
+    Cookieverf3,
+    NFS3_COOKIEVERFSIZE
+);
+verifier!(
+    /// `typedef opaque createverf3[NFS3_CREATEVERFSIZE]`: identifies an
+    /// exclusive `CREATE`.
+    Createverf3,
+    NFS3_CREATEVERFSIZE
+);
+verifier!(
+    /// `typedef opaque writeverf3[NFS3_WRITEVERFSIZE]`: changes when the
+    /// server loses uncommitted writes.
+    Writeverf3,
+    NFS3_WRITEVERFSIZE
+);
+
+// ── GETATTR ───────────────────────────────────────────────────────────────
+
+/// `struct GETATTR3args`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Getattr3Args {
+    pub object: NfsFh3,
+}
+
+/// `struct GETATTR3resok`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Getattr3Resok {
+    pub obj_attributes: Fattr3,
+}
+
+/// `union GETATTR3res`
+pub type Getattr3Res = Nfs3Res<Getattr3Resok>;
+
+// ── SETATTR ───────────────────────────────────────────────────────────────
+
+/// `union sattrguard3 switch (bool check)`: the `ctime` the object must
+/// still have for `SETATTR` to proceed.
+pub type Sattrguard3 = Option<Nfstime3>;
+
+/// `struct SETATTR3args`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Setattr3Args {
+    pub object: NfsFh3,
+    pub new_attributes: Sattr3,
+    pub guard: Sattrguard3,
+}
+
+/// `struct SETATTR3resok`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Setattr3Resok {
+    pub obj_wcc: WccData,
+}
+
+/// `struct SETATTR3resfail`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Setattr3Resfail {
+    pub obj_wcc: WccData,
+}
+
+/// `union SETATTR3res`
+pub type Setattr3Res = Nfs3Res<Setattr3Resok, Setattr3Resfail>;
+
+// ── LOOKUP ────────────────────────────────────────────────────────────────
+
+/// `struct LOOKUP3args`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Lookup3Args {
+    pub what: Diropargs3,
+}
+
+/// `struct LOOKUP3resok`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Lookup3Resok {
+    pub object: NfsFh3,
+    pub obj_attributes: PostOpAttr,
+    pub dir_attributes: PostOpAttr,
+}
+
+/// `struct LOOKUP3resfail`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Lookup3Resfail {
+    pub dir_attributes: PostOpAttr,
+}
+
+/// `union LOOKUP3res`
+pub type Lookup3Res = Nfs3Res<Lookup3Resok, Lookup3Resfail>;
+
+// ── ACCESS ────────────────────────────────────────────────────────────────
+
+/// `struct ACCESS3args`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Access3Args {
+    pub object: NfsFh3,
+    /// `ACCESS3_*` bits to check.
+    pub access: u32,
+}
+
+/// `struct ACCESS3resok`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Access3Resok {
+    pub obj_attributes: PostOpAttr,
+    /// The `ACCESS3_*` bits granted.
+    pub access: u32,
+}
+
+/// `struct ACCESS3resfail`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Access3Resfail {
+    pub obj_attributes: PostOpAttr,
+}
+
+/// `union ACCESS3res`
+pub type Access3Res = Nfs3Res<Access3Resok, Access3Resfail>;
+
+// ── READLINK ──────────────────────────────────────────────────────────────
+
+/// `struct READLINK3args`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Readlink3Args {
+    pub symlink: NfsFh3,
+}
+
+/// `struct READLINK3resok`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Readlink3Resok {
+    pub symlink_attributes: PostOpAttr,
+    pub data: Nfspath3,
+}
+
+/// `struct READLINK3resfail`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Readlink3Resfail {
+    pub symlink_attributes: PostOpAttr,
+}
+
+/// `union READLINK3res`
+pub type Readlink3Res = Nfs3Res<Readlink3Resok, Readlink3Resfail>;
+
+// ── READ ──────────────────────────────────────────────────────────────────
+
+/// `struct READ3args`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Read3Args {
+    pub file: NfsFh3,
+    pub offset: u64,
+    pub count: u32,
+}
+
+/// `struct READ3resok`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Read3Resok {
+    pub file_attributes: PostOpAttr,
+    pub count: u32,
+    pub eof: bool,
+    pub data: Opaque3,
+}
+
+/// `struct READ3resfail`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Read3Resfail {
+    pub file_attributes: PostOpAttr,
+}
+
+/// `union READ3res`
+pub type Read3Res = Nfs3Res<Read3Resok, Read3Resfail>;
+
+// ── WRITE ─────────────────────────────────────────────────────────────────
+
+xdr_enum! {
+    /// `enum stable_how`: how far written data must reach stable storage
+    /// before the reply.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum StableHow {
+        Unstable = 0,
+        DataSync = 1,
+        FileSync = 2,
+    }
+}
+
+/// `struct WRITE3args`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Write3Args {
+    pub file: NfsFh3,
+    pub offset: u64,
+    pub count: u32,
+    pub stable: StableHow,
+    pub data: Opaque3,
+}
+
+/// `struct WRITE3resok`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Write3Resok {
+    pub file_wcc: WccData,
+    pub count: u32,
+    pub committed: StableHow,
+    pub verf: Writeverf3,
+}
+
+/// `struct WRITE3resfail`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Write3Resfail {
+    pub file_wcc: WccData,
+}
+
+/// `union WRITE3res`
+pub type Write3Res = Nfs3Res<Write3Resok, Write3Resfail>;
+
+// ── CREATE ────────────────────────────────────────────────────────────────
+
+xdr_enum! {
+    /// `enum createmode3`
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum Createmode3 {
+        Unchecked = 0,
+        Guarded = 1,
+        Exclusive = 2,
+    }
+}
+
+xdr_enum! {
+    /// `union createhow3 switch (createmode3 mode)`, switched on
+    /// [`Createmode3`].
+    ///
+    /// ```text
+    /// union createhow3 switch (createmode3 mode) {
+    /// case UNCHECKED:
+    /// case GUARDED:
+    ///     sattr3       obj_attributes;
+    /// case EXCLUSIVE:
+    ///     createverf3  verf;
+    /// };
+    /// ```
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum Createhow3 {
+        Unchecked(Sattr3) = 0,
+        Guarded(Sattr3) = 1,
+        Exclusive(Createverf3) = 2,
+    }
+}
+
+/// `struct CREATE3args`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Create3Args {
+    pub r#where: Diropargs3,
+    pub how: Createhow3,
+}
+
+/// `struct CREATE3resok`, also the `resok` of `MKDIR`, `SYMLINK` and `MKNOD`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Create3Resok {
+    pub obj: PostOpFh3,
+    pub obj_attributes: PostOpAttr,
+    pub dir_wcc: WccData,
+}
+
+/// `struct CREATE3resfail`, also the `resfail` of `MKDIR`, `SYMLINK`,
+/// `MKNOD`, `REMOVE` and `RMDIR`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Create3Resfail {
+    pub dir_wcc: WccData,
+}
+
+/// `union CREATE3res`
+pub type Create3Res = Nfs3Res<Create3Resok, Create3Resfail>;
+
+// ── MKDIR ─────────────────────────────────────────────────────────────────
+
+/// `struct MKDIR3args`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Mkdir3Args {
+    pub r#where: Diropargs3,
+    pub attributes: Sattr3,
+}
+
+/// `struct MKDIR3resok`
+pub type Mkdir3Resok = Create3Resok;
+
+/// `struct MKDIR3resfail`
+pub type Mkdir3Resfail = Create3Resfail;
+
+/// `union MKDIR3res`
+pub type Mkdir3Res = Nfs3Res<Mkdir3Resok, Mkdir3Resfail>;
+
+// ── SYMLINK ───────────────────────────────────────────────────────────────
+
+/// `struct symlinkdata3`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Symlinkdata3 {
+    pub symlink_attributes: Sattr3,
+    pub symlink_data: Nfspath3,
+}
+
+/// `struct SYMLINK3args`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Symlink3Args {
+    pub r#where: Diropargs3,
+    pub symlink: Symlinkdata3,
+}
+
+/// `struct SYMLINK3resok`
+pub type Symlink3Resok = Create3Resok;
+
+/// `struct SYMLINK3resfail`
+pub type Symlink3Resfail = Create3Resfail;
+
+/// `union SYMLINK3res`
+pub type Symlink3Res = Nfs3Res<Symlink3Resok, Symlink3Resfail>;
+
+// ── MKNOD ─────────────────────────────────────────────────────────────────
+
+/// `struct devicedata3`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Devicedata3 {
+    pub dev_attributes: Sattr3,
+    pub spec: Specdata3,
+}
+
+xdr_enum! {
+    /// `union mknoddata3 switch (ftype3 type)`, switched on [`Ftype3`].
+    ///
+    /// ```text
+    /// union mknoddata3 switch (ftype3 type) {
+    /// case NF3CHR:
+    /// case NF3BLK:
+    ///     devicedata3  device;
+    /// case NF3SOCK:
+    /// case NF3FIFO:
+    ///     sattr3       pipe_attributes;
+    /// default:
+    ///     void;
+    /// };
+    /// ```
+    ///
+    /// `ftype3` has no other values, so the `default` arm is written out as
+    /// the three remaining types.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum Mknoddata3 {
+        Reg = 1,
+        Dir = 2,
+        Blk(Devicedata3) = 3,
+        Chr(Devicedata3) = 4,
+        Lnk = 5,
+        Sock(Sattr3) = 6,
+        Fifo(Sattr3) = 7,
+    }
+}
+
+/// `struct MKNOD3args`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Mknod3Args {
+    pub r#where: Diropargs3,
+    pub what: Mknoddata3,
+}
+
+/// `struct MKNOD3resok`
+pub type Mknod3Resok = Create3Resok;
+
+/// `struct MKNOD3resfail`
+pub type Mknod3Resfail = Create3Resfail;
+
+/// `union MKNOD3res`
+pub type Mknod3Res = Nfs3Res<Mknod3Resok, Mknod3Resfail>;
+
+// ── REMOVE and RMDIR ──────────────────────────────────────────────────────
+
+/// `struct REMOVE3args`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Remove3Args {
+    pub object: Diropargs3,
+}
+
+/// `struct REMOVE3resok`, also the `resok` of `RMDIR`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Remove3Resok {
+    pub dir_wcc: WccData,
+}
+
+/// `struct REMOVE3resfail`
+pub type Remove3Resfail = Create3Resfail;
+
+/// `union REMOVE3res`
+pub type Remove3Res = Nfs3Res<Remove3Resok, Remove3Resfail>;
+
+/// `struct RMDIR3args`
+pub type Rmdir3Args = Remove3Args;
+
+/// `struct RMDIR3resok`
+pub type Rmdir3Resok = Remove3Resok;
+
+/// `struct RMDIR3resfail`
+pub type Rmdir3Resfail = Create3Resfail;
+
+/// `union RMDIR3res`
+pub type Rmdir3Res = Nfs3Res<Rmdir3Resok, Rmdir3Resfail>;
+
+// ── RENAME ────────────────────────────────────────────────────────────────
+
+/// `struct RENAME3args`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Rename3Args {
+    pub from: Diropargs3,
+    pub to: Diropargs3,
+}
+
+/// `struct RENAME3resok`, also its `resfail`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Rename3Resok {
+    pub fromdir_wcc: WccData,
+    pub todir_wcc: WccData,
+}
+
+/// `struct RENAME3resfail`
+pub type Rename3Resfail = Rename3Resok;
+
+/// `union RENAME3res`
+pub type Rename3Res = Nfs3Res<Rename3Resok, Rename3Resfail>;
+
+// ── LINK ──────────────────────────────────────────────────────────────────
+
+/// `struct LINK3args`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Link3Args {
+    pub file: NfsFh3,
+    pub link: Diropargs3,
+}
+
+/// `struct LINK3resok`, also its `resfail`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Link3Resok {
+    pub file_attributes: PostOpAttr,
+    pub linkdir_wcc: WccData,
+}
+
+/// `struct LINK3resfail`
+pub type Link3Resfail = Link3Resok;
+
+/// `union LINK3res`
+pub type Link3Res = Nfs3Res<Link3Resok, Link3Resfail>;
+
+// ── READDIR ───────────────────────────────────────────────────────────────
+
+/// `struct READDIR3args`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Readdir3Args {
+    pub dir: NfsFh3,
+    pub cookie: u64,
+    pub cookieverf: Cookieverf3,
+    pub count: u32,
+}
+
+/// `struct entry3`, without its `nextentry` pointer: one entry of a
+/// [`Dirlist3`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Entry3 {
+    pub fileid: u64,
+    pub name: Filename3,
+    pub cookie: u64,
+}
+
+/// `struct dirlist3`
+///
+/// ```text
+/// struct dirlist3 {
+///     entry3 *entries;
+///     bool   eof;
+/// };
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Dirlist3 {
+    #[serde(with = "crate::linked_list")]
+    pub entries: Vec<Entry3>,
+    pub eof: bool,
+}
+
+/// `struct READDIR3resok`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Readdir3Resok {
+    pub dir_attributes: PostOpAttr,
+    pub cookieverf: Cookieverf3,
+    pub reply: Dirlist3,
+}
+
+/// `struct READDIR3resfail`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Readdir3Resfail {
+    pub dir_attributes: PostOpAttr,
+}
+
+/// `union READDIR3res`
+pub type Readdir3Res = Nfs3Res<Readdir3Resok, Readdir3Resfail>;
+
+// ── READDIRPLUS ───────────────────────────────────────────────────────────
+
+/// `struct READDIRPLUS3args`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Readdirplus3Args {
+    pub dir: NfsFh3,
+    pub cookie: u64,
+    pub cookieverf: Cookieverf3,
+    pub dircount: u32,
+    pub maxcount: u32,
+}
+
+/// `struct entryplus3`, without its `nextentry` pointer: one entry of a
+/// [`Dirlistplus3`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Entryplus3 {
+    pub fileid: u64,
+    pub name: Filename3,
+    pub cookie: u64,
+    pub name_attributes: PostOpAttr,
+    pub name_handle: PostOpFh3,
+}
+
+/// `struct dirlistplus3`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Dirlistplus3 {
+    #[serde(with = "crate::linked_list")]
+    pub entries: Vec<Entryplus3>,
+    pub eof: bool,
+}
+
+/// `struct READDIRPLUS3resok`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Readdirplus3Resok {
+    pub dir_attributes: PostOpAttr,
+    pub cookieverf: Cookieverf3,
+    pub reply: Dirlistplus3,
+}
+
+/// `struct READDIRPLUS3resfail`
+pub type Readdirplus3Resfail = Readdir3Resfail;
+
+/// `union READDIRPLUS3res`
+pub type Readdirplus3Res = Nfs3Res<Readdirplus3Resok, Readdirplus3Resfail>;
+
+// ── FSSTAT ────────────────────────────────────────────────────────────────
+
+/// `struct FSSTAT3args`, also the arguments of `FSINFO`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Fsstat3Args {
+    pub fsroot: NfsFh3,
+}
+
+/// `struct FSSTAT3resok`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Fsstat3Resok {
+    pub obj_attributes: PostOpAttr,
+    /// Total size, in bytes.
+    pub tbytes: u64,
+    /// Free space, in bytes.
+    pub fbytes: u64,
+    /// Free space available to the caller, in bytes.
+    pub abytes: u64,
+    /// Total file slots.
+    pub tfiles: u64,
+    /// Free file slots.
+    pub ffiles: u64,
+    /// Free file slots available to the caller.
+    pub afiles: u64,
+    /// Seconds for which these values will not change; 0 if volatile.
+    pub invarsec: u32,
+}
+
+/// `struct FSSTAT3resfail`, also the `resfail` of `FSINFO` and `PATHCONF`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Fsstat3Resfail {
+    pub obj_attributes: PostOpAttr,
+}
+
+/// `union FSSTAT3res`
+pub type Fsstat3Res = Nfs3Res<Fsstat3Resok, Fsstat3Resfail>;
+
+// ── FSINFO ────────────────────────────────────────────────────────────────
+
+/// `struct FSINFO3args`
+pub type Fsinfo3Args = Fsstat3Args;
+
+/// `struct FSINFO3resok`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Fsinfo3Resok {
+    pub obj_attributes: PostOpAttr,
+    pub rtmax: u32,
+    pub rtpref: u32,
+    pub rtmult: u32,
+    pub wtmax: u32,
+    pub wtpref: u32,
+    pub wtmult: u32,
+    pub dtpref: u32,
+    pub maxfilesize: u64,
+    pub time_delta: Nfstime3,
+    /// `FSF3_*` bits.
+    pub properties: u32,
+}
+
+/// `struct FSINFO3resfail`
+pub type Fsinfo3Resfail = Fsstat3Resfail;
+
+/// `union FSINFO3res`
+pub type Fsinfo3Res = Nfs3Res<Fsinfo3Resok, Fsinfo3Resfail>;
+
+// ── PATHCONF ──────────────────────────────────────────────────────────────
+
+/// `struct PATHCONF3args`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Pathconf3Args {
+    pub object: NfsFh3,
+}
+
+/// `struct PATHCONF3resok`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Pathconf3Resok {
+    pub obj_attributes: PostOpAttr,
+    pub linkmax: u32,
+    pub name_max: u32,
+    pub no_trunc: bool,
+    pub chown_restricted: bool,
+    pub case_insensitive: bool,
+    pub case_preserving: bool,
+}
+
+/// `struct PATHCONF3resfail`
+pub type Pathconf3Resfail = Fsstat3Resfail;
+
+/// `union PATHCONF3res`
+pub type Pathconf3Res = Nfs3Res<Pathconf3Resok, Pathconf3Resfail>;
+
+// ── COMMIT ────────────────────────────────────────────────────────────────
+
+/// `struct COMMIT3args`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Commit3Args {
+    pub file: NfsFh3,
+    pub offset: u64,
+    pub count: u32,
+}
+
+/// `struct COMMIT3resok`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Commit3Resok {
+    pub file_wcc: WccData,
+    pub verf: Writeverf3,
+}
+
+/// `struct COMMIT3resfail`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Commit3Resfail {
+    pub file_wcc: WccData,
+}
+
+/// `union COMMIT3res`
+pub type Commit3Res = Nfs3Res<Commit3Resok, Commit3Resfail>;
@@ -0,0 +1,366 @@
+//! Typed NFSv4 attributes: [`Attrs4`] packs into and unpacks from a
+//! [`Fattr4`].
+//!
+//! A `fattr4` carries a `bitmap4` of attribute numbers followed by an
+//! `attrlist4` holding the value of each attribute in the bitmap, XDR
+//! encoded back to back in increasing attribute number. [`Attrs4`] has one
+//! `Option` field per attribute of NFSv4.0 (RFC 7530) and NFSv4.1 (RFC
+//! 8881); [`Attrs4::to_fattr4`] sets the bit and encodes the value of each
+//! `Some` field, and [`Attrs4::from_fattr4`] reverses it.
+//!
+//! ```rust
+//! use xdr_serde::nfs4::attr::{Attrs4, FATTR4_SIZE, FATTR4_TYPE, bitmap4};
+//! use xdr_serde::nfs4::NfsFtype4;
+//!
+//! let attrs = Attrs4 { r#type: Some(NfsFtype4::Nf4dir), size: Some(4096), ..Attrs4::default() };
+//! let fattr = attrs.to_fattr4().unwrap();
+//! assert_eq!(fattr.attrmask, bitmap4(&[FATTR4_TYPE, FATTR4_SIZE]));
+//! assert_eq!(fattr.attr_vals.as_slice(), [0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0x10, 0]);
+//! assert_eq!(Attrs4::from_fattr4(&fattr).unwrap(), attrs);
+//! ```
+//!
+//! Decoding fails with [`Error::InvalidDiscriminant`] carrying the attribute
+//! number if the bitmap has a bit set that is not an attribute listed here,
+//! since the values that follow it could not be located. Bytes left over
+//! after the last attribute are also rejected.
+
+use super::{
+    Bitmap4, Fattr4, Fsid4, Layouttype4, NfsFh4, NfsFtype4, Nfsace4, Nfsstat4, Nfstime4, Opaque4,
+    Pathname4, Settime4, Specdata4, Utf8string,
+};
+use crate::error::{Error, Result};
+use crate::ser::Serializer;
+use crate::{from_bytes_partial, xdr_enum};
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+
+/// A `bitmap4` with the given attribute numbers set, with no trailing zero
+/// words.
+pub fn bitmap4(attrs: &[u32]) -> Bitmap4 {
+    let mut bitmap = Vec::new();
+    for &attr in attrs {
+        let word = (attr / 32) as usize;
+        if bitmap.len() <= word {
+            bitmap.resize(word + 1, 0);
+        }
+        bitmap[word] |= 1 << (attr % 32);
+    }
+    bitmap
+}
+
+// ── Attribute value types ─────────────────────────────────────────────────
+
+/// `struct fs_location4`: a server holding a file system, and its path
+/// there.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct FsLocation4 {
+    pub server: Vec<Utf8string>,
+    pub rootpath: Pathname4,
+}
+
+/// `struct fs_locations4`: the `fs_locations` attribute.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct FsLocations4 {
+    pub fs_root: Pathname4,
+    pub locations: Vec<FsLocation4>,
+}
+
+/// `struct nfsacl41`: the `dacl` and `sacl` attributes.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Nfsacl41 {
+    /// `ACL4_*` flag bits.
+    pub na41_flag: u32,
+    pub na41_aces: Vec<Nfsace4>,
+}
+
+/// `struct change_policy4`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ChangePolicy4 {
+    pub cp_major: u64,
+    pub cp_minor: u64,
+}
+
+xdr_enum! {
+    /// `enum fs4_status_type`
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum Fs4StatusType {
+        StatusFixed = 1,
+        StatusUpdated = 2,
+        StatusVersioned = 3,
+        StatusWritable = 4,
+        StatusReferral = 5,
+    }
+}
+
+/// `struct fs4_status`: the `fs_status` attribute.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Fs4Status {
+    pub fss_absent: bool,
+    pub fss_type: Fs4StatusType,
+    pub fss_source: Utf8string,
+    pub fss_current: Utf8string,
+    pub fss_age: i32,
+    pub fss_version: Nfstime4,
+}
+
+/// `struct layouthint4`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Layouthint4 {
+    pub loh_type: Layouttype4,
+    pub loh_body: Opaque4,
+}
+
+/// `struct fs_locations_server4`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct FsLocationsServer4 {
+    pub fls_currency: i32,
+    pub fls_info: Opaque4,
+    pub fls_server: Utf8string,
+}
+
+/// `struct fs_locations_item4`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct FsLocationsItem4 {
+    pub fli_entries: Vec<FsLocationsServer4>,
+    pub fli_rootpath: Pathname4,
+}
+
+/// `struct fs_locations_info4`: the `fs_locations_info` attribute.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct FsLocationsInfo4 {
+    pub fli_flags: u32,
+    pub fli_valid_for: i32,
+    pub fli_fs_root: Pathname4,
+    pub fli_items: Vec<FsLocationsItem4>,
+}
+
+/// `struct threshold_item4`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ThresholdItem4 {
+    pub thi_layout_type: Layouttype4,
+    pub thi_hintset: Bitmap4,
+    pub thi_hintlist: Opaque4,
+}
+
+/// `struct mdsthreshold4`: the `mdsthreshold` attribute.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Mdsthreshold4 {
+    pub mth_hints: Vec<ThresholdItem4>,
+}
+
+/// `struct retention_get4`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct RetentionGet4 {
+    pub rg_duration: u64,
+    /// `nfstime4 rg_begin_time<1>`.
+    pub rg_begin_time: Option<Nfstime4>,
+}
+
+/// `struct retention_set4`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct RetentionSet4 {
+    pub rs_enable: bool,
+    /// `uint64_t rs_duration<1>`.
+    pub rs_duration: Option<u64>,
+}
+
+/// `struct mode_masked4`: the `mode_set_masked` attribute.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ModeMasked4 {
+    /// The mode bits to set.
+    pub mm_value_to_set: u32,
+    /// The mode bits to leave unchanged.
+    pub mm_mask_bits: u32,
+}
+
+// ── Attributes ────────────────────────────────────────────────────────────
+
+/// Defines the `FATTR4_*` constants and [`Attrs4`] from one table, in
+/// increasing attribute number.
+macro_rules! attrs4 {
+    ($( $(#[$meta:meta])* $const:ident = $bit:literal => $field:ident: $ty:ty, )+) => {
+        $(
+            #[doc = concat!("Attribute number ", stringify!($bit), "; see [`Attrs4`].")]
+            pub const $const: u32 = $bit;
+        )+
+
+        /// The attributes of a file, each present or absent.
+        ///
+        /// Field names are the RFCs' attribute names; each field's type is
+        /// the attribute's XDR type.
+        #[derive(Debug, Clone, Default, PartialEq, Eq)]
+        pub struct Attrs4 {
+            $(
+                $(#[$meta])*
+                pub $field: Option<$ty>,
+            )+
+        }
+
+        impl Attrs4 {
+            /// The attribute numbers of the present attributes, as a
+            /// `bitmap4`.
+            pub fn attrmask(&self) -> Bitmap4 {
+                let mut attrs = Vec::new();
+                $( if self.$field.is_some() { attrs.push($bit); } )+
+                bitmap4(&attrs)
+            }
+
+            /// Encode the present attributes as a [`Fattr4`], through the
+            /// crate's [`Serializer`].
+            pub fn to_fattr4(&self) -> Result<Fattr4> {
+                let mut ser = Serializer::new(Vec::new());
+                $(
+                    if let Some(value) = &self.$field {
+                        value.serialize(&mut ser)?;
+                    }
+                )+
+                Ok(Fattr4 {
+                    attrmask: self.attrmask(),
+                    attr_vals: Opaque4::from(ser.into_writer()),
+                })
+            }
+
+            /// Decode the attributes of a [`Fattr4`].
+            ///
+            /// Fails with [`Error::InvalidDiscriminant`] carrying the
+            /// attribute number on an unknown bit, with the usual decoding
+            /// errors if a value is malformed or cut short, and with
+            /// [`Error::Message`] if bytes are left over.
+            pub fn from_fattr4(fattr: &Fattr4) -> Result<Self> {
+                let mut attrs = Attrs4::default();
+                let mut rest = fattr.attr_vals.as_slice();
+                for (word, &bits) in fattr.attrmask.iter().enumerate() {
+                    for bit in (0..32).filter(|bit| bits & (1 << bit) != 0) {
+                        let attr = word as u32 * 32 + bit;
+                        match attr {
+                            $(
+                                $bit => {
+                                    let (value, tail) = from_bytes_partial::<$ty>(rest)?;
+                                    attrs.$field = Some(value);
+                                    rest = tail;
+                                }
+                            )+
+                            _ => return Err(Error::InvalidDiscriminant(attr as i32)),
+                        }
+                    }
+                }
+                if !rest.is_empty() {
+                    return Err(Error::Message(alloc::format!(
+                        "{} bytes after the last attribute",
+                        rest.len()
+                    )));
+                }
+                Ok(attrs)
+            }
+        }
+    };
+}
+
+attrs4! {
+    /// The attributes the server supports.
+    FATTR4_SUPPORTED_ATTRS = 0 => supported_attrs: Bitmap4,
+    FATTR4_TYPE = 1 => r#type: NfsFtype4,
+    /// `FH4_*` bits: when file handles may expire.
+    FATTR4_FH_EXPIRE_TYPE = 2 => fh_expire_type: u32,
+    /// `changeid4`: changes whenever the file's data or attributes do.
+    FATTR4_CHANGE = 3 => change: u64,
+    FATTR4_SIZE = 4 => size: u64,
+    FATTR4_LINK_SUPPORT = 5 => link_support: bool,
+    FATTR4_SYMLINK_SUPPORT = 6 => symlink_support: bool,
+    FATTR4_NAMED_ATTR = 7 => named_attr: bool,
+    FATTR4_FSID = 8 => fsid: Fsid4,
+    FATTR4_UNIQUE_HANDLES = 9 => unique_handles: bool,
+    /// `nfs_lease4`, in seconds.
+    FATTR4_LEASE_TIME = 10 => lease_time: u32,
+    /// The error reading the other attributes of a `READDIR` entry.
+    FATTR4_RDATTR_ERROR = 11 => rdattr_error: Nfsstat4,
+    FATTR4_ACL = 12 => acl: Vec<Nfsace4>,
+    FATTR4_ACLSUPPORT = 13 => aclsupport: u32,
+    FATTR4_ARCHIVE = 14 => archive: bool,
+    FATTR4_CANSETTIME = 15 => cansettime: bool,
+    FATTR4_CASE_INSENSITIVE = 16 => case_insensitive: bool,
+    FATTR4_CASE_PRESERVING = 17 => case_preserving: bool,
+    FATTR4_CHOWN_RESTRICTED = 18 => chown_restricted: bool,
+    FATTR4_FILEHANDLE = 19 => filehandle: NfsFh4,
+    FATTR4_FILEID = 20 => fileid: u64,
+    FATTR4_FILES_AVAIL = 21 => files_avail: u64,
+    FATTR4_FILES_FREE = 22 => files_free: u64,
+    FATTR4_FILES_TOTAL = 23 => files_total: u64,
+    FATTR4_FS_LOCATIONS = 24 => fs_locations: FsLocations4,
+    FATTR4_HIDDEN = 25 => hidden: bool,
+    FATTR4_HOMOGENEOUS = 26 => homogeneous: bool,
+    FATTR4_MAXFILESIZE = 27 => maxfilesize: u64,
+    FATTR4_MAXLINK = 28 => maxlink: u32,
+    FATTR4_MAXNAME = 29 => maxname: u32,
+    FATTR4_MAXREAD = 30 => maxread: u64,
+    FATTR4_MAXWRITE = 31 => maxwrite: u64,
+    FATTR4_MIMETYPE = 32 => mimetype: Utf8string,
+    /// `mode4`: the permission bits.
+    FATTR4_MODE = 33 => mode: u32,
+    FATTR4_NO_TRUNC = 34 => no_trunc: bool,
+    FATTR4_NUMLINKS = 35 => numlinks: u32,
+    FATTR4_OWNER = 36 => owner: Utf8string,
+    FATTR4_OWNER_GROUP = 37 => owner_group: Utf8string,
+    FATTR4_QUOTA_AVAIL_HARD = 38 => quota_avail_hard: u64,
+    FATTR4_QUOTA_AVAIL_SOFT = 39 => quota_avail_soft: u64,
+    FATTR4_QUOTA_USED = 40 => quota_used: u64,
+    FATTR4_RAWDEV = 41 => rawdev: Specdata4,
+    FATTR4_SPACE_AVAIL = 42 => space_avail: u64,
+    FATTR4_SPACE_FREE = 43 => space_free: u64,
+    FATTR4_SPACE_TOTAL = 44 => space_total: u64,
+    FATTR4_SPACE_USED = 45 => space_used: u64,
+    FATTR4_SYSTEM = 46 => system: bool,
+    FATTR4_TIME_ACCESS = 47 => time_access: Nfstime4,
+    /// Write-only: used in `SETATTR`, never returned.
+    FATTR4_TIME_ACCESS_SET = 48 => time_access_set: Settime4,
+    FATTR4_TIME_BACKUP = 49 => time_backup: Nfstime4,
+    FATTR4_TIME_CREATE = 50 => time_create: Nfstime4,
+    FATTR4_TIME_DELTA = 51 => time_delta: Nfstime4,
+    FATTR4_TIME_METADATA = 52 => time_metadata: Nfstime4,
+    FATTR4_TIME_MODIFY = 53 => time_modify: Nfstime4,
+    /// Write-only: used in `SETATTR`, never returned.
+    FATTR4_TIME_MODIFY_SET = 54 => time_modify_set: Settime4,
+    FATTR4_MOUNTED_ON_FILEID = 55 => mounted_on_fileid: u64,
+    /// NFSv4.1
+    FATTR4_DIR_NOTIF_DELAY = 56 => dir_notif_delay: Nfstime4,
+    /// NFSv4.1
+    FATTR4_DIRENT_NOTIF_DELAY = 57 => dirent_notif_delay: Nfstime4,
+    /// NFSv4.1
+    FATTR4_DACL = 58 => dacl: Nfsacl41,
+    /// NFSv4.1
+    FATTR4_SACL = 59 => sacl: Nfsacl41,
+    /// NFSv4.1
+    FATTR4_CHANGE_POLICY = 60 => change_policy: ChangePolicy4,
+    /// NFSv4.1
+    FATTR4_FS_STATUS = 61 => fs_status: Fs4Status,
+    /// NFSv4.1
+    FATTR4_FS_LAYOUT_TYPE = 62 => fs_layout_type: Vec<Layouttype4>,
+    /// NFSv4.1
+    FATTR4_LAYOUT_HINT = 63 => layout_hint: Layouthint4,
+    /// NFSv4.1
+    FATTR4_LAYOUT_TYPE = 64 => layout_type: Vec<Layouttype4>,
+    /// NFSv4.1
+    FATTR4_LAYOUT_BLKSIZE = 65 => layout_blksize: u32,
+    /// NFSv4.1
+    FATTR4_LAYOUT_ALIGNMENT = 66 => layout_alignment: u32,
+    /// NFSv4.1
+    FATTR4_FS_LOCATIONS_INFO = 67 => fs_locations_info: FsLocationsInfo4,
+    /// NFSv4.1
+    FATTR4_MDSTHRESHOLD = 68 => mdsthreshold: Mdsthreshold4,
+    /// NFSv4.1
+    FATTR4_RETENTION_GET = 69 => retention_get: RetentionGet4,
+    /// NFSv4.1
+    FATTR4_RETENTION_SET = 70 => retention_set: RetentionSet4,
+    /// NFSv4.1
+    FATTR4_RETENTEVT_GET = 71 => retentevt_get: RetentionGet4,
+    /// NFSv4.1
+    FATTR4_RETENTEVT_SET = 72 => retentevt_set: RetentionSet4,
+    /// NFSv4.1
+    FATTR4_RETENTION_HOLD = 73 => retention_hold: u64,
+    /// NFSv4.1
+    FATTR4_MODE_SET_MASKED = 74 => mode_set_masked: ModeMasked4,
+    /// NFSv4.1: the attributes that may be set by an `EXCLUSIVE4_1` create.
+    FATTR4_SUPPATTR_EXCLCREAT = 75 => suppattr_exclcreat: Bitmap4,
+    /// NFSv4.1: whether names on the file system may be other than UTF-8.
+    FATTR4_FS_CHARSET_CAP = 76 => fs_charset_cap: u32,
+}
@@ -0,0 +1,2392 @@
+//! NFS version 4.0 and 4.1 protocol types (RFC 7530, RFC 8881). Requires the
+//! `nfs4` feature.
+//!
+//! NFSv4 has a single procedure besides `NULL`: `COMPOUND`, whose
+//! [`Compound4Args`] carry a list of operations ([`NfsArgop4`]) and whose
+//! [`Compound4Res`] carry one result per operation evaluated
+//! ([`NfsResop4`]). Every operation of minor versions 0 and 1 is modelled,
+//! with the RFCs' operation numbers and status codes; types are named after
+//! the RFCs' XDR definitions (`OPEN4resok` is [`Open4Resok`]).
+//!
+//! ```rust
+//! use xdr_serde::nfs4::*;
+//! use xdr_serde::{from_bytes, to_bytes};
+//!
+//! // PUTROOTFH; GETFH; GETATTR(type, size)
+//! let args = Compound4Args {
+//!     tag: String::new(),
+//!     minorversion: 0,
+//!     argarray: vec![
+//!         NfsArgop4::Putrootfh,
+//!         NfsArgop4::Getfh,
+//!         NfsArgop4::Getattr(Getattr4Args { attr_request: vec![0x12] }),
+//!     ],
+//! };
+//! let bytes = to_bytes(&args).unwrap();
+//! assert_eq!(bytes.len(), 4 + 4 + 4 + 4 + 4 + (4 + 4 + 4));
+//! assert_eq!(from_bytes::<Compound4Args>(&bytes).unwrap(), args);
+//!
+//! // The server stopped at PUTROOTFH.
+//! let res = Compound4Res {
+//!     status: Nfsstat4::Wrongsec,
+//!     tag: String::new(),
+//!     resarray: vec![NfsResop4::Putrootfh(Nfs4Res::Err(Nfsstat4::Wrongsec))],
+//! };
+//! assert_eq!(from_bytes::<Compound4Res>(&to_bytes(&res).unwrap()).unwrap(), res);
+//! ```
+//!
+//! Results switched on a status are [`Nfs4Res`] values, or a dedicated enum
+//! such as [`Lock4Res`] where an error status carries a body of its own.
+//! Boolean-switched unions and `<1>` arrays are `Option`s, which have the
+//! same encoding. Attributes ([`Fattr4`]) are a bitmap and the encoded
+//! attribute values; [`attr::Attrs4`] packs and unpacks them.
+//!
+//! The `utf8string` types are `String`s, so a name that is not valid UTF-8
+//! fails to decode with [`Error::InvalidString`](crate::Error::InvalidString).
+//! The callback program (`CB_COMPOUND`) and the operations added by NFSv4.2
+//! are not included.
+
+use crate::ByteBuf;
+use crate::bounded::BoundedBytes;
+use crate::rpc::AuthSysParms;
+use crate::status_union::{self, ArmBody, StatusUnion};
+use crate::xdr_enum;
+use alloc::string::String;
+use alloc::vec::Vec;
+use serde::de::{Deserializer, SeqAccess};
+use serde::ser::{self, Serializer};
+use serde::{Deserialize, Serialize};
+
+pub mod attr;
+
+/// The NFS program number.
+pub const NFS4_PROGRAM: u32 = 100003;
+
+/// The NFS protocol version described here.
+pub const NFS_V4: u32 = 4;
+
+/// Procedure 0: do nothing.
+pub const NFSPROC4_NULL: u32 = 0;
+
+/// Procedure 1: takes [`Compound4Args`], returns [`Compound4Res`].
+pub const NFSPROC4_COMPOUND: u32 = 1;
+
+/// The maximum size of a file handle, in bytes.
+pub const NFS4_FHSIZE: u32 = 128;
+
+/// The size of a [`Verifier4`], in bytes.
+pub const NFS4_VERIFIER_SIZE: usize = 8;
+
+/// The size of [`Stateid4::other`], in bytes.
+pub const NFS4_OTHER_SIZE: usize = 12;
+
+/// The maximum size of client and owner identifiers, in bytes.
+pub const NFS4_OPAQUE_LIMIT: u32 = 1024;
+
+/// The size of a [`Sessionid4`], in bytes.
+pub const NFS4_SESSIONID_SIZE: usize = 16;
+
+/// The size of a [`Deviceid4`], in bytes.
+pub const NFS4_DEVICEID4_SIZE: usize = 16;
+
+/// [`Access4Args::access`] bit: read data or list a directory.
+pub const ACCESS4_READ: u32 = 0x0001;
+/// [`Access4Args::access`] bit: look up a name in a directory.
+pub const ACCESS4_LOOKUP: u32 = 0x0002;
+/// [`Access4Args::access`] bit: rewrite data or modify a directory.
+pub const ACCESS4_MODIFY: u32 = 0x0004;
+/// [`Access4Args::access`] bit: write past the end of a file or add to a
+/// directory.
+pub const ACCESS4_EXTEND: u32 = 0x0008;
+/// [`Access4Args::access`] bit: delete a directory entry.
+pub const ACCESS4_DELETE: u32 = 0x0010;
+/// [`Access4Args::access`] bit: execute a file or search a directory.
+pub const ACCESS4_EXECUTE: u32 = 0x0020;
+
+/// [`Open4Args::share_access`]: read.
+pub const OPEN4_SHARE_ACCESS_READ: u32 = 0x0001;
+/// [`Open4Args::share_access`]: write.
+pub const OPEN4_SHARE_ACCESS_WRITE: u32 = 0x0002;
+/// [`Open4Args::share_access`]: read and write.
+pub const OPEN4_SHARE_ACCESS_BOTH: u32 = 0x0003;
+/// [`Open4Args::share_deny`]: deny others nothing.
+pub const OPEN4_SHARE_DENY_NONE: u32 = 0x0000;
+/// [`Open4Args::share_deny`]: deny others read.
+pub const OPEN4_SHARE_DENY_READ: u32 = 0x0001;
+/// [`Open4Args::share_deny`]: deny others write.
+pub const OPEN4_SHARE_DENY_WRITE: u32 = 0x0002;
+/// [`Open4Args::share_deny`]: deny others read and write.
+pub const OPEN4_SHARE_DENY_BOTH: u32 = 0x0003;
+/// [`Open4Args::share_access`]: the bits naming the delegation wanted.
+pub const OPEN4_SHARE_ACCESS_WANT_DELEG_MASK: u32 = 0xFF00;
+/// [`Open4Args::share_access`]: no delegation preference.
+pub const OPEN4_SHARE_ACCESS_WANT_NO_PREFERENCE: u32 = 0x0000;
+/// [`Open4Args::share_access`]: want a read delegation.
+pub const OPEN4_SHARE_ACCESS_WANT_READ_DELEG: u32 = 0x0100;
+/// [`Open4Args::share_access`]: want a write delegation.
+pub const OPEN4_SHARE_ACCESS_WANT_WRITE_DELEG: u32 = 0x0200;
+/// [`Open4Args::share_access`]: want either delegation.
+pub const OPEN4_SHARE_ACCESS_WANT_ANY_DELEG: u32 = 0x0300;
+/// [`Open4Args::share_access`]: want no delegation.
+pub const OPEN4_SHARE_ACCESS_WANT_NO_DELEG: u32 = 0x0400;
+/// [`Open4Args::share_access`]: cancel a delegation wanted earlier.
+pub const OPEN4_SHARE_ACCESS_WANT_CANCEL: u32 = 0x0500;
+/// [`Open4Args::share_access`] bit: signal when resources allow a delegation.
+pub const OPEN4_SHARE_ACCESS_WANT_SIGNAL_DELEG_WHEN_RESRC_AVAIL: u32 = 0x10000;
+/// [`Open4Args::share_access`] bit: delegate once the file is uncontended.
+pub const OPEN4_SHARE_ACCESS_WANT_PUSH_DELEG_WHEN_UNCONTENDED: u32 = 0x20000;
+
+/// [`Open4Resok::rflags`] bit: confirm with `OPEN_CONFIRM` (NFSv4.0).
+pub const OPEN4_RESULT_CONFIRM: u32 = 0x0002;
+/// [`Open4Resok::rflags`] bit: byte-range locks have POSIX semantics.
+pub const OPEN4_RESULT_LOCKTYPE_POSIX: u32 = 0x0004;
+/// [`Open4Resok::rflags`] bit: the file outlives its last link until closed.
+pub const OPEN4_RESULT_PRESERVE_UNLINKED: u32 = 0x0008;
+/// [`Open4Resok::rflags`] bit: the server may signal freed locks.
+pub const OPEN4_RESULT_MAY_NOTIFY_LOCK: u32 = 0x0020;
+
+/// `eia_flags` and `eir_flags` bit: referrals are supported.
+pub const EXCHGID4_FLAG_SUPP_MOVED_REFER: u32 = 0x0000_0001;
+/// `eia_flags` and `eir_flags` bit: migration is supported.
+pub const EXCHGID4_FLAG_SUPP_MOVED_MIGR: u32 = 0x0000_0002;
+/// `eia_flags` and `eir_flags` bit: bind state to its principal.
+pub const EXCHGID4_FLAG_BIND_PRINC_STATEID: u32 = 0x0000_0100;
+/// `eia_flags` and `eir_flags` bit: act as a server without pNFS.
+pub const EXCHGID4_FLAG_USE_NON_PNFS: u32 = 0x0001_0000;
+/// `eia_flags` and `eir_flags` bit: act as a pNFS metadata server.
+pub const EXCHGID4_FLAG_USE_PNFS_MDS: u32 = 0x0002_0000;
+/// `eia_flags` and `eir_flags` bit: act as a pNFS data server.
+pub const EXCHGID4_FLAG_USE_PNFS_DS: u32 = 0x0004_0000;
+/// `eia_flags` and `eir_flags`: the three `USE_*` bits.
+pub const EXCHGID4_FLAG_MASK_PNFS: u32 = 0x0007_0000;
+/// `eia_flags` bit: update the confirmed record of this client.
+pub const EXCHGID4_FLAG_UPD_CONFIRMED_REC_A: u32 = 0x4000_0000;
+/// `eir_flags` bit: the client ID is already confirmed.
+pub const EXCHGID4_FLAG_CONFIRMED_R: u32 = 0x8000_0000;
+
+/// `csa_flags` and `csr_flags` bit: persist the reply cache.
+pub const CREATE_SESSION4_FLAG_PERSIST: u32 = 0x0001;
+/// `csa_flags` and `csr_flags` bit: use the connection as a backchannel too.
+pub const CREATE_SESSION4_FLAG_CONN_BACK_CHAN: u32 = 0x0002;
+/// `csa_flags` and `csr_flags` bit: switch the connection to RDMA.
+pub const CREATE_SESSION4_FLAG_CONN_RDMA: u32 = 0x0004;
+
+/// [`Sequence4Resok`] `sr_status_flags` bit: no callback path is up.
+pub const SEQ4_STATUS_CB_PATH_DOWN: u32 = 0x0000_0001;
+/// [`Sequence4Resok`] `sr_status_flags` bit: callback GSS contexts expiring.
+pub const SEQ4_STATUS_CB_GSS_CONTEXTS_EXPIRING: u32 = 0x0000_0002;
+/// [`Sequence4Resok`] `sr_status_flags` bit: a callback GSS context expired.
+pub const SEQ4_STATUS_CB_GSS_CONTEXTS_EXPIRED: u32 = 0x0000_0004;
+/// [`Sequence4Resok`] `sr_status_flags` bit: lease expired, all state revoked.
+pub const SEQ4_STATUS_EXPIRED_ALL_STATE_REVOKED: u32 = 0x0000_0008;
+/// [`Sequence4Resok`] `sr_status_flags` bit: lease expired, some state revoked.
+pub const SEQ4_STATUS_EXPIRED_SOME_STATE_REVOKED: u32 = 0x0000_0010;
+/// [`Sequence4Resok`] `sr_status_flags` bit: state revoked by an administrator.
+pub const SEQ4_STATUS_ADMIN_STATE_REVOKED: u32 = 0x0000_0020;
+/// [`Sequence4Resok`] `sr_status_flags` bit: unanswered recall, state revoked.
+pub const SEQ4_STATUS_RECALLABLE_STATE_REVOKED: u32 = 0x0000_0040;
+/// [`Sequence4Resok`] `sr_status_flags` bit: state moved with its file system.
+pub const SEQ4_STATUS_LEASE_MOVED: u32 = 0x0000_0080;
+/// [`Sequence4Resok`] `sr_status_flags` bit: server restarted; reclaim state.
+pub const SEQ4_STATUS_RESTART_RECLAIM_NEEDED: u32 = 0x0000_0100;
+/// [`Sequence4Resok`] `sr_status_flags` bit: no callback path on this session.
+pub const SEQ4_STATUS_CB_PATH_DOWN_SESSION: u32 = 0x0000_0200;
+/// [`Sequence4Resok`] `sr_status_flags` bit: the backchannel failed.
+pub const SEQ4_STATUS_BACKCHANNEL_FAULT: u32 = 0x0000_0400;
+/// [`Sequence4Resok`] `sr_status_flags` bit: a pNFS device ID mapping changed.
+pub const SEQ4_STATUS_DEVID_CHANGED: u32 = 0x0000_0800;
+/// [`Sequence4Resok`] `sr_status_flags` bit: a pNFS device ID was deleted.
+pub const SEQ4_STATUS_DEVID_DELETED: u32 = 0x0000_1000;
+
+/// [`Layouttype4`]: the NFSv4.1 file layout.
+pub const LAYOUT4_NFSV4_1_FILES: u32 = 0x1;
+/// [`Layouttype4`]: the object-based layout.
+pub const LAYOUT4_OSD2_OBJECTS: u32 = 0x2;
+/// [`Layouttype4`]: the block/volume layout.
+pub const LAYOUT4_BLOCK_VOLUME: u32 = 0x3;
+
+/// `utf8str_cs`, `utf8str_cis` and `utf8str_mixed`: UTF-8 text, encoded as
+/// variable-length opaque data.
+pub type Utf8string = String;
+
+/// `component4`: a single path component.
+pub type Component4 = String;
+
+/// `linktext4`: the target of a symbolic link.
+pub type Linktext4 = String;
+
+/// `pathname4`: a path as its components.
+pub type Pathname4 = Vec<Component4>;
+
+/// Variable-length opaque data with no bound.
+pub type Opaque4 = ByteBuf;
+
+/// `bitmap4`: a bit mask over attribute or notification numbers; bit `n`
+/// is bit `n % 32` of word `n / 32`.
+pub type Bitmap4 = Vec<u32>;
+
+/// `attrlist4`: attribute values encoded back to back, in bitmap order.
+pub type Attrlist4 = Opaque4;
+
+/// `sec_oid4`: an ASN.1 object identifier.
+pub type SecOid4 = Opaque4;
+
+/// `gsshandle4_t`: an RPCSEC_GSS context handle.
+pub type Gsshandle4 = Opaque4;
+
+/// `layouttype4`: one of the `LAYOUT4_*` constants. Left open, since
+/// further layout types are defined outside RFC 8881.
+pub type Layouttype4 = u32;
+
+xdr_enum! {
+    /// `enum nfsstat4`: the status of `COMPOUND` and of each operation.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum Nfsstat4 {
+        /// `NFS4_OK`
+        Ok = 0,
+        Perm = 1,
+        Noent = 2,
+        Io = 5,
+        Nxio = 6,
+        Access = 13,
+        Exist = 17,
+        Xdev = 18,
+        Notdir = 20,
+        Isdir = 21,
+        Inval = 22,
+        Fbig = 27,
+        Nospc = 28,
+        Rofs = 30,
+        Mlink = 31,
+        Nametoolong = 63,
+        Notempty = 66,
+        Dquot = 69,
+        Stale = 70,
+        Badhandle = 10001,
+        BadCookie = 10003,
+        Notsupp = 10004,
+        Toosmall = 10005,
+        Serverfault = 10006,
+        Badtype = 10007,
+        Delay = 10008,
+        Same = 10009,
+        Denied = 10010,
+        Expired = 10011,
+        Locked = 10012,
+        Grace = 10013,
+        Fhexpired = 10014,
+        ShareDenied = 10015,
+        Wrongsec = 10016,
+        ClidInuse = 10017,
+        Resource = 10018,
+        Moved = 10019,
+        Nofilehandle = 10020,
+        MinorVersMismatch = 10021,
+        StaleClientid = 10022,
+        StaleStateid = 10023,
+        OldStateid = 10024,
+        BadStateid = 10025,
+        BadSeqid = 10026,
+        NotSame = 10027,
+        LockRange = 10028,
+        Symlink = 10029,
+        Restorefh = 10030,
+        LeaseMoved = 10031,
+        Attrnotsupp = 10032,
+        NoGrace = 10033,
+        ReclaimBad = 10034,
+        ReclaimConflict = 10035,
+        Badxdr = 10036,
+        LocksHeld = 10037,
+        Openmode = 10038,
+        Badowner = 10039,
+        Badchar = 10040,
+        Badname = 10041,
+        BadRange = 10042,
+        LockNotsupp = 10043,
+        OpIllegal = 10044,
+        Deadlock = 10045,
+        FileOpen = 10046,
+        AdminRevoked = 10047,
+        CbPathDown = 10048,
+        Badiomode = 10049,
+        Badlayout = 10050,
+        BadSessionDigest = 10051,
+        Badsession = 10052,
+        Badslot = 10053,
+        CompleteAlready = 10054,
+        ConnNotBoundToSession = 10055,
+        DelegAlreadyWanted = 10056,
+        BackChanBusy = 10057,
+        Layouttrylater = 10058,
+        Layoutunavailable = 10059,
+        NomatchingLayout = 10060,
+        Recallconflict = 10061,
+        UnknownLayouttype = 10062,
+        SeqMisordered = 10063,
+        SequencePos = 10064,
+        ReqTooBig = 10065,
+        RepTooBig = 10066,
+        RepTooBigToCache = 10067,
+        RetryUncachedRep = 10068,
+        UnsafeCompound = 10069,
+        TooManyOps = 10070,
+        OpNotInSession = 10071,
+        HashAlgUnsupp = 10072,
+        ClientidBusy = 10074,
+        PnfsIoHole = 10075,
+        SeqFalseRetry = 10076,
+        BadHighSlot = 10077,
+        Deadsession = 10078,
+        EncrAlgUnsupp = 10079,
+        PnfsNoLayout = 10080,
+        NotOnlyOp = 10081,
+        WrongCred = 10082,
+        WrongType = 10083,
+        DirdelegUnavail = 10084,
+        RejectDeleg = 10085,
+        Returnconflict = 10086,
+        DelegRevoked = 10087,
+    }
+}
+
+/// The result of an operation, switched on its status.
+///
+/// ```text
+/// union XXX4res switch (nfsstat4 status) {
+/// case NFS4_OK:
+///     XXX4resok resok4;
+/// default:
+///     void;
+/// };
+/// ```
+///
+/// Operations that return only a status, such as `PUTFH`, use `T = ()`.
+/// Encoding an `Err` with status [`Nfsstat4::Ok`] fails with
+/// [`Error::Message`](crate::Error::Message), since it would decode as an
+/// `Ok`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Nfs4Res<T> {
+    /// `NFS4_OK` and the `resok4` body.
+    Ok(T),
+    /// An error status.
+    Err(Nfsstat4),
+}
+
+impl<T> Nfs4Res<T> {
+    /// The `status` discriminant.
+    pub fn status(&self) -> Nfsstat4 {
+        match self {
+            Nfs4Res::Ok(_) => Nfsstat4::Ok,
+            Nfs4Res::Err(status) => *status,
+        }
+    }
+
+    /// Convert into a `Result`.
+    pub fn into_result(self) -> core::result::Result<T, Nfsstat4> {
+        match self {
+            Nfs4Res::Ok(resok) => Ok(resok),
+            Nfs4Res::Err(status) => Err(status),
+        }
+    }
+}
+
+impl<T: Serialize> Serialize for Nfs4Res<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error> {
+        match self {
+            Nfs4Res::Ok(resok) => status_union::serialize(serializer, &Nfsstat4::Ok, resok),
+            Nfs4Res::Err(Nfsstat4::Ok) => Err(ser::Error::custom("NFS4_OK without a result")),
+            Nfs4Res::Err(status) => status_union::serialize_void(serializer, status),
+        }
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Nfs4Res<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> core::result::Result<Self, D::Error> {
+        status_union::deserialize(deserializer)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> StatusUnion<'de> for Nfs4Res<T> {
+    type Status = Nfsstat4;
+    const EXPECTING: &'static str = "an NFSv4 result";
+
+    fn arm<A: SeqAccess<'de>>(
+        status: Nfsstat4,
+        body: ArmBody<A>,
+    ) -> core::result::Result<Self, A::Error> {
+        Ok(match status {
+            Nfsstat4::Ok => Nfs4Res::Ok(body.read()?),
+            status => Nfs4Res::Err(status),
+        })
+    }
+}
+
+/// Defines a result union whose `default` arm is void but where some error
+/// statuses carry a body, such as `LOCK4res` with `NFS4ERR_DENIED`.
+macro_rules! nfs4_res {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident {
+            $(#[$okmeta:meta])*
+            Ok($ok:ty),
+            $(
+                $(#[$vmeta:meta])*
+                $variant:ident($ty:ty) = $status:ident,
+            )+
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub enum $name {
+            $(#[$okmeta])*
+            Ok($ok),
+            $(
+                $(#[$vmeta])*
+                $variant($ty),
+            )+
+            /// Any other error status.
+            Err(Nfsstat4),
+        }
+
+        impl $name {
+            /// The `status` discriminant.
+            pub fn status(&self) -> Nfsstat4 {
+                match self {
+                    $name::Ok(_) => Nfsstat4::Ok,
+                    $( $name::$variant(_) => Nfsstat4::$status, )+
+                    $name::Err(status) => *status,
+                }
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(
+                &self,
+                serializer: S,
+            ) -> core::result::Result<S::Ok, S::Error> {
+                match self {
+                    $name::Ok(body) => status_union::serialize(serializer, &Nfsstat4::Ok, body),
+                    $(
+                        $name::$variant(body) => {
+                            status_union::serialize(serializer, &Nfsstat4::$status, body)
+                        }
+                    )+
+                    $name::Err(Nfsstat4::Ok $(| Nfsstat4::$status)+) => {
+                        Err(ser::Error::custom("error status without its result"))
+                    }
+                    $name::Err(status) => status_union::serialize_void(serializer, status),
+                }
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(
+                deserializer: D,
+            ) -> core::result::Result<Self, D::Error> {
+                status_union::deserialize(deserializer)
+            }
+        }
+
+        impl<'de> StatusUnion<'de> for $name {
+            type Status = Nfsstat4;
+            const EXPECTING: &'static str = concat!("a ", stringify!($name));
+
+            fn arm<A: SeqAccess<'de>>(
+                status: Nfsstat4,
+                body: ArmBody<A>,
+            ) -> core::result::Result<Self, A::Error> {
+                Ok(match status {
+                    Nfsstat4::Ok => $name::Ok(body.read()?),
+                    $( Nfsstat4::$status => $name::$variant(body.read()?), )+
+                    status => $name::Err(status),
+                })
+            }
+        }
+    };
+}
+
+macro_rules! fixed_opaque_type {
+    ($(#[$meta:meta])* $name:ident, $size:ident) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+        pub struct $name(#[serde(with = "crate::fixed_opaque")] pub [u8; $size]);
+    };
+}
+
+fixed_opaque_type!(
+    /// `typedef opaque verifier4[NFS4_VERIFIER_SIZE]`.
+    Verifier4,
+    NFS4_VERIFIER_SIZE
+);
+fixed_opaque_type!(
+    /// `typedef opaque sessionid4[NFS4_SESSIONID_SIZE]`: an NFSv4.1 session.
+    Sessionid4,
+    NFS4_SESSIONID_SIZE
+);
+fixed_opaque_type!(
+    /// `typedef opaque deviceid4[NFS4_DEVICEID4_SIZE]`: a pNFS device.
+    Deviceid4,
+    NFS4_DEVICEID4_SIZE
+);
+
+/// `typedef opaque nfs_fh4<NFS4_FHSIZE>`: a file handle, at most
+/// [`NFS4_FHSIZE`] bytes.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct NfsFh4(pub BoundedBytes<NFS4_FHSIZE>);
+
+impl NfsFh4 {
+    /// A file handle holding `data`.
+    ///
+    /// Fails with [`Error::LengthOverflow`](crate::Error::LengthOverflow) if
+    /// `data` is longer than [`NFS4_FHSIZE`].
+    pub fn new(data: Vec<u8>) -> crate::Result<Self> {
+        Ok(NfsFh4(BoundedBytes::new(data)?))
+    }
+}
+
+/// `struct stateid4`: open, lock, delegation or layout state.
+///
+/// ```text
+/// struct stateid4 {
+///     uint32_t seqid;
+///     opaque   other[NFS4_OTHER_SIZE];
+/// };
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Stateid4 {
+    pub seqid: u32,
+    #[serde(with = "crate::fixed_opaque")]
+    pub other: [u8; NFS4_OTHER_SIZE],
+}
+
+/// `struct nfstime4`: a time relative to the epoch.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Nfstime4 {
+    pub seconds: i64,
+    pub nseconds: u32,
+}
+
+xdr_enum! {
+    /// `enum time_how4`
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum TimeHow4 {
+        SetToServerTime4 = 0,
+        SetToClientTime4 = 1,
+    }
+}
+
+xdr_enum! {
+    /// `union settime4 switch (time_how4 set_it)`, switched on [`TimeHow4`].
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum Settime4 {
+        SetToServerTime4 = 0,
+        SetToClientTime4(Nfstime4) = 1,
+    }
+}
+
+/// `struct specdata4`: the major and minor numbers of a device.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Specdata4 {
+    pub specdata1: u32,
+    pub specdata2: u32,
+}
+
+/// `struct fsid4`: a file system identifier.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Fsid4 {
+    pub major: u64,
+    pub minor: u64,
+}
+
+xdr_enum! {
+    /// `enum nfs_ftype4`: the type of a file.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum NfsFtype4 {
+        Nf4reg = 1,
+        Nf4dir = 2,
+        Nf4blk = 3,
+        Nf4chr = 4,
+        Nf4lnk = 5,
+        Nf4sock = 6,
+        Nf4fifo = 7,
+        /// A named attribute directory.
+        Nf4attrdir = 8,
+        /// A named attribute.
+        Nf4namedattr = 9,
+    }
+}
+
+/// `struct fattr4`: a set of attributes.
+///
+/// ```text
+/// struct fattr4 {
+///     bitmap4   attrmask;
+///     attrlist4 attr_vals;
+/// };
+/// ```
+///
+/// `attr_vals` holds the value of each attribute set in `attrmask`, XDR
+/// encoded, in increasing attribute number. See [`attr::Attrs4`] to build
+/// or read one.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Fattr4 {
+    pub attrmask: Bitmap4,
+    pub attr_vals: Attrlist4,
+}
+
+/// `struct change_info4`: a directory's change attribute around an
+/// operation that modified it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ChangeInfo4 {
+    /// Whether `before` and `after` were taken atomically with the change.
+    pub atomic: bool,
+    pub before: u64,
+    pub after: u64,
+}
+
+/// `struct netaddr4`, also `clientaddr4`: a network id and universal
+/// address, as in rpcbind.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Netaddr4 {
+    pub na_r_netid: String,
+    pub na_r_addr: String,
+}
+
+/// `typedef netaddr4 clientaddr4`.
+pub type Clientaddr4 = Netaddr4;
+
+/// `struct cb_client4`: the NFSv4.0 callback program and its address.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct CbClient4 {
+    pub cb_program: u32,
+    pub cb_location: Netaddr4,
+}
+
+/// `struct nfs_client_id4`: an NFSv4.0 client's identity.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct NfsClientId4 {
+    pub verifier: Verifier4,
+    pub id: BoundedBytes<NFS4_OPAQUE_LIMIT>,
+}
+
+/// `struct state_owner4`, also `open_owner4` and `lock_owner4`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct StateOwner4 {
+    pub clientid: u64,
+    pub owner: BoundedBytes<NFS4_OPAQUE_LIMIT>,
+}
+
+/// `typedef state_owner4 open_owner4`.
+pub type OpenOwner4 = StateOwner4;
+
+/// `typedef state_owner4 lock_owner4`.
+pub type LockOwner4 = StateOwner4;
+
+/// `struct nfsace4`: an access control entry.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Nfsace4 {
+    pub r#type: u32,
+    pub flag: u32,
+    pub access_mask: u32,
+    pub who: Utf8string,
+}
+
+xdr_enum! {
+    /// `enum nfs_lock_type4`
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum NfsLockType4 {
+        ReadLt = 1,
+        WriteLt = 2,
+        /// A blocking read lock.
+        ReadwLt = 3,
+        /// A blocking write lock.
+        WritewLt = 4,
+    }
+}
+
+xdr_enum! {
+    /// `enum stable_how4`
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum StableHow4 {
+        Unstable4 = 0,
+        DataSync4 = 1,
+        FileSync4 = 2,
+    }
+}
+
+xdr_enum! {
+    /// `enum rpc_gss_svc_t`: the RPCSEC_GSS protection service.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum RpcGssSvc {
+        None = 1,
+        Integrity = 2,
+        Privacy = 3,
+    }
+}
+
+// ── ACCESS ────────────────────────────────────────────────────────────────
+
+/// `struct ACCESS4args`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Access4Args {
+    /// `ACCESS4_*` bits to check.
+    pub access: u32,
+}
+
+/// `struct ACCESS4resok`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Access4Resok {
+    /// The `ACCESS4_*` bits the server could check.
+    pub supported: u32,
+    /// The `ACCESS4_*` bits granted.
+    pub access: u32,
+}
+
+/// `union ACCESS4res`
+pub type Access4Res = Nfs4Res<Access4Resok>;
+
+// ── CLOSE ─────────────────────────────────────────────────────────────────
+
+/// `struct CLOSE4args`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Close4Args {
+    pub seqid: u32,
+    pub open_stateid: Stateid4,
+}
+
+/// `union CLOSE4res`: the updated open stateid.
+pub type Close4Res = Nfs4Res<Stateid4>;
+
+// ── COMMIT ────────────────────────────────────────────────────────────────
+
+/// `struct COMMIT4args`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Commit4Args {
+    pub offset: u64,
+    pub count: u32,
+}
+
+/// `struct COMMIT4resok`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Commit4Resok {
+    pub writeverf: Verifier4,
+}
+
+/// `union COMMIT4res`
+pub type Commit4Res = Nfs4Res<Commit4Resok>;
+
+// ── CREATE ────────────────────────────────────────────────────────────────
+
+xdr_enum! {
+    /// `union createtype4 switch (nfs_ftype4 type)`, switched on
+    /// [`NfsFtype4`].
+    ///
+    /// ```text
+    /// union createtype4 switch (nfs_ftype4 type) {
+    /// case NF4LNK:
+    ///     linktext4 linkdata;
+    /// case NF4BLK:
+    /// case NF4CHR:
+    ///     specdata4 devdata;
+    /// case NF4SOCK:
+    /// case NF4FIFO:
+    /// case NF4DIR:
+    ///     void;
+    /// default:
+    ///     void;
+    /// };
+    /// ```
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    pub enum Createtype4 {
+        Nf4reg = 1,
+        Nf4dir = 2,
+        Nf4blk(Specdata4) = 3,
+        Nf4chr(Specdata4) = 4,
+        Nf4lnk(Linktext4) = 5,
+        Nf4sock = 6,
+        Nf4fifo = 7,
+        Nf4attrdir = 8,
+        Nf4namedattr = 9,
+    }
+}
+
+/// `struct CREATE4args`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Create4Args {
+    pub objtype: Createtype4,
+    pub objname: Component4,
+    pub createattrs: Fattr4,
+}
+
+/// `struct CREATE4resok`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Create4Resok {
+    pub cinfo: ChangeInfo4,
+    pub attrset: Bitmap4,
+}
+
+/// `union CREATE4res`
+pub type Create4Res = Nfs4Res<Create4Resok>;
+
+// ── DELEGPURGE and DELEGRETURN ────────────────────────────────────────────
+
+/// `struct DELEGPURGE4args`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Delegpurge4Args {
+    pub clientid: u64,
+}
+
+/// `struct DELEGPURGE4res`
+pub type Delegpurge4Res = Nfs4Res<()>;
+
+/// `struct DELEGRETURN4args`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Delegreturn4Args {
+    pub deleg_stateid: Stateid4,
+}
+
+/// `struct DELEGRETURN4res`
+pub type Delegreturn4Res = Nfs4Res<()>;
+
+// ── GETATTR and GETFH ─────────────────────────────────────────────────────
+
+/// `struct GETATTR4args`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Getattr4Args {
+    pub attr_request: Bitmap4,
+}
+
+/// `struct GETATTR4resok`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Getattr4Resok {
+    pub obj_attributes: Fattr4,
+}
+
+/// `union GETATTR4res`
+pub type Getattr4Res = Nfs4Res<Getattr4Resok>;
+
+/// `struct GETFH4resok`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Getfh4Resok {
+    pub object: NfsFh4,
+}
+
+/// `union GETFH4res`
+pub type Getfh4Res = Nfs4Res<Getfh4Resok>;
+
+// ── LINK ──────────────────────────────────────────────────────────────────
+
+/// `struct LINK4args`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Link4Args {
+    pub newname: Component4,
+}
+
+/// `struct LINK4resok`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Link4Resok {
+    pub cinfo: ChangeInfo4,
+}
+
+/// `union LINK4res`
+pub type Link4Res = Nfs4Res<Link4Resok>;
+
+// ── LOCK, LOCKT and LOCKU ─────────────────────────────────────────────────
+
+/// `struct open_to_lock_owner4`: the first lock of a lock owner.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OpenToLockOwner4 {
+    pub open_seqid: u32,
+    pub open_stateid: Stateid4,
+    pub lock_seqid: u32,
+    pub lock_owner: LockOwner4,
+}
+
+/// `struct exist_lock_owner4`: a further lock of a lock owner.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExistLockOwner4 {
+    pub lock_stateid: Stateid4,
+    pub lock_seqid: u32,
+}
+
+xdr_enum! {
+    /// `union locker4 switch (bool new_lock_owner)`.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum Locker4 {
+        /// `FALSE`
+        LockOwner(ExistLockOwner4) = 0,
+        /// `TRUE`
+        OpenOwner(OpenToLockOwner4) = 1,
+    }
+}
+
+/// `struct LOCK4args`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Lock4Args {
+    pub locktype: NfsLockType4,
+    pub reclaim: bool,
+    pub offset: u64,
+    pub length: u64,
+    pub locker: Locker4,
+}
+
+/// `struct LOCK4denied`: the conflicting lock.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Lock4Denied {
+    pub offset: u64,
+    pub length: u64,
+    pub locktype: NfsLockType4,
+    pub owner: LockOwner4,
+}
+
+/// `struct LOCK4resok`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Lock4Resok {
+    pub lock_stateid: Stateid4,
+}
+
+nfs4_res! {
+    /// `union LOCK4res switch (nfsstat4 status)`.
+    pub enum Lock4Res {
+        /// `NFS4_OK`
+        Ok(Lock4Resok),
+        /// `NFS4ERR_DENIED` and the conflicting lock.
+        Denied(Lock4Denied) = Denied,
+    }
+}
+
+/// `struct LOCKT4args`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Lockt4Args {
+    pub locktype: NfsLockType4,
+    pub offset: u64,
+    pub length: u64,
+    pub owner: LockOwner4,
+}
+
+nfs4_res! {
+    /// `union LOCKT4res switch (nfsstat4 status)`.
+    pub enum Lockt4Res {
+        /// `NFS4_OK`: no conflicting lock.
+        Ok(()),
+        /// `NFS4ERR_DENIED` and the conflicting lock.
+        Denied(Lock4Denied) = Denied,
+    }
+}
+
+/// `struct LOCKU4args`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Locku4Args {
+    pub locktype: NfsLockType4,
+    pub seqid: u32,
+    pub lock_stateid: Stateid4,
+    pub offset: u64,
+    pub length: u64,
+}
+
+/// `union LOCKU4res`: the updated lock stateid.
+pub type Locku4Res = Nfs4Res<Stateid4>;
+
+// ── LOOKUP, LOOKUPP, NVERIFY and VERIFY ───────────────────────────────────
+
+/// `struct LOOKUP4args`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Lookup4Args {
+    pub objname: Component4,
+}
+
+/// `struct LOOKUP4res`
+pub type Lookup4Res = Nfs4Res<()>;
+
+/// `struct LOOKUPP4res`
+pub type Lookupp4Res = Nfs4Res<()>;
+
+/// `struct NVERIFY4args`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Nverify4Args {
+    pub obj_attributes: Fattr4,
+}
+
+/// `struct NVERIFY4res`
+pub type Nverify4Res = Nfs4Res<()>;
+
+/// `struct VERIFY4args`
+pub type Verify4Args = Nverify4Args;
+
+/// `struct VERIFY4res`
+pub type Verify4Res = Nfs4Res<()>;
+
+// ── OPEN ──────────────────────────────────────────────────────────────────
+
+xdr_enum! {
+    /// `enum createmode4`
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum Createmode4 {
+        Unchecked4 = 0,
+        Guarded4 = 1,
+        Exclusive4 = 2,
+        /// NFSv4.1
+        Exclusive4_1 = 3,
+    }
+}
+
+/// `struct creatverfattr`: the `EXCLUSIVE4_1` verifier and attributes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Creatverfattr {
+    pub cva_verf: Verifier4,
+    pub cva_attrs: Fattr4,
+}
+
+xdr_enum! {
+    /// `union createhow4 switch (createmode4 mode)`, switched on
+    /// [`Createmode4`].
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum Createhow4 {
+        Unchecked4(Fattr4) = 0,
+        Guarded4(Fattr4) = 1,
+        Exclusive4(Verifier4) = 2,
+        Exclusive4_1(Creatverfattr) = 3,
+    }
+}
+
+xdr_enum! {
+    /// `enum opentype4`
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum Opentype4 {
+        Open4Nocreate = 0,
+        Open4Create = 1,
+    }
+}
+
+xdr_enum! {
+    /// `union openflag4 switch (opentype4 opentype)`, switched on
+    /// [`Opentype4`].
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum Openflag4 {
+        Open4Nocreate = 0,
+        Open4Create(Createhow4) = 1,
+    }
+}
+
+xdr_enum! {
+    /// `enum limit_by4`
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum LimitBy4 {
+        NfsLimitSize = 1,
+        NfsLimitBlocks = 2,
+    }
+}
+
+/// `struct nfs_modified_limit4`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct NfsModifiedLimit4 {
+    pub num_blocks: u32,
+    pub bytes_per_block: u32,
+}
+
+xdr_enum! {
+    /// `union nfs_space_limit4 switch (limit_by4 limitby)`, switched on
+    /// [`LimitBy4`]: how far a write delegation lets the client extend the
+    /// file.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum NfsSpaceLimit4 {
+        NfsLimitSize(u64) = 1,
+        NfsLimitBlocks(NfsModifiedLimit4) = 2,
+    }
+}
+
+xdr_enum! {
+    /// `enum open_delegation_type4`
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum OpenDelegationType4 {
+        OpenDelegateNone = 0,
+        OpenDelegateRead = 1,
+        OpenDelegateWrite = 2,
+        /// NFSv4.1
+        OpenDelegateNoneExt = 3,
+    }
+}
+
+xdr_enum! {
+    /// `enum open_claim_type4`
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum OpenClaimType4 {
+        ClaimNull = 0,
+        ClaimPrevious = 1,
+        ClaimDelegateCur = 2,
+        ClaimDelegatePrev = 3,
+        /// NFSv4.1
+        ClaimFh = 4,
+        /// NFSv4.1
+        ClaimDelegCurFh = 5,
+        /// NFSv4.1
+        ClaimDelegPrevFh = 6,
+    }
+}
+
+/// `struct open_claim_delegate_cur4`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OpenClaimDelegateCur4 {
+    pub delegate_stateid: Stateid4,
+    pub file: Component4,
+}
+
+xdr_enum! {
+    /// `union open_claim4 switch (open_claim_type4 claim)`, switched on
+    /// [`OpenClaimType4`]: what is being opened.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum OpenClaim4 {
+        /// A name in the current directory.
+        ClaimNull(Component4) = 0,
+        /// Reclaim after a server restart.
+        ClaimPrevious(OpenDelegationType4) = 1,
+        ClaimDelegateCur(OpenClaimDelegateCur4) = 2,
+        ClaimDelegatePrev(Component4) = 3,
+        /// The current file handle.
+        ClaimFh = 4,
+        ClaimDelegCurFh(Stateid4) = 5,
+        ClaimDelegPrevFh = 6,
+    }
+}
+
+/// `struct OPEN4args`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Open4Args {
+    pub seqid: u32,
+    /// `OPEN4_SHARE_ACCESS_*` bits.
+    pub share_access: u32,
+    /// `OPEN4_SHARE_DENY_*` bits.
+    pub share_deny: u32,
+    pub owner: OpenOwner4,
+    pub openhow: Openflag4,
+    pub claim: OpenClaim4,
+}
+
+/// `struct open_read_delegation4`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OpenReadDelegation4 {
+    pub stateid: Stateid4,
+    pub recall: bool,
+    pub permissions: Nfsace4,
+}
+
+/// `struct open_write_delegation4`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OpenWriteDelegation4 {
+    pub stateid: Stateid4,
+    pub recall: bool,
+    pub space_limit: NfsSpaceLimit4,
+    pub permissions: Nfsace4,
+}
+
+xdr_enum! {
+    /// `enum why_no_delegation4`
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum WhyNoDelegation4 {
+        Wnd4NotWanted = 0,
+        Wnd4Contention = 1,
+        Wnd4Resource = 2,
+        Wnd4NotSuppFtype = 3,
+        Wnd4WriteDelegNotSuppFtype = 4,
+        Wnd4NotSuppUpgrade = 5,
+        Wnd4NotSuppDowngrade = 6,
+        Wnd4Cancelled = 7,
+        Wnd4IsDir = 8,
+    }
+}
+
+xdr_enum! {
+    /// `union open_none_delegation4 switch (why_no_delegation4 ond_why)`,
+    /// switched on [`WhyNoDelegation4`]. The `bool`s say whether the server
+    /// will push or signal a delegation later.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum OpenNoneDelegation4 {
+        Wnd4NotWanted = 0,
+        Wnd4Contention(bool) = 1,
+        Wnd4Resource(bool) = 2,
+        Wnd4NotSuppFtype = 3,
+        Wnd4WriteDelegNotSuppFtype = 4,
+        Wnd4NotSuppUpgrade = 5,
+        Wnd4NotSuppDowngrade = 6,
+        Wnd4Cancelled = 7,
+        Wnd4IsDir = 8,
+    }
+}
+
+xdr_enum! {
+    /// `union open_delegation4 switch (open_delegation_type4
+    /// delegation_type)`, switched on [`OpenDelegationType4`].
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum OpenDelegation4 {
+        OpenDelegateNone = 0,
+        OpenDelegateRead(OpenReadDelegation4) = 1,
+        OpenDelegateWrite(OpenWriteDelegation4) = 2,
+        OpenDelegateNoneExt(OpenNoneDelegation4) = 3,
+    }
+}
+
+/// `struct OPEN4resok`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Open4Resok {
+    pub stateid: Stateid4,
+    pub cinfo: ChangeInfo4,
+    /// `OPEN4_RESULT_*` bits.
+    pub rflags: u32,
+    pub attrset: Bitmap4,
+    pub delegation: OpenDelegation4,
+}
+
+/// `union OPEN4res`
+pub type Open4Res = Nfs4Res<Open4Resok>;
+
+/// `struct OPENATTR4args`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Openattr4Args {
+    pub createdir: bool,
+}
+
+/// `struct OPENATTR4res`
+pub type Openattr4Res = Nfs4Res<()>;
+
+/// `struct OPEN_CONFIRM4args` (NFSv4.0 only)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OpenConfirm4Args {
+    pub open_stateid: Stateid4,
+    pub seqid: u32,
+}
+
+/// `struct OPEN_CONFIRM4resok`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OpenConfirm4Resok {
+    pub open_stateid: Stateid4,
+}
+
+/// `union OPEN_CONFIRM4res`
+pub type OpenConfirm4Res = Nfs4Res<OpenConfirm4Resok>;
+
+/// `struct OPEN_DOWNGRADE4args`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OpenDowngrade4Args {
+    pub open_stateid: Stateid4,
+    pub seqid: u32,
+    pub share_access: u32,
+    pub share_deny: u32,
+}
+
+/// `struct OPEN_DOWNGRADE4resok`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OpenDowngrade4Resok {
+    pub open_stateid: Stateid4,
+}
+
+/// `union OPEN_DOWNGRADE4res`
+pub type OpenDowngrade4Res = Nfs4Res<OpenDowngrade4Resok>;
+
+// ── PUTFH, PUTPUBFH, PUTROOTFH, RESTOREFH and SAVEFH ──────────────────────
+
+/// `struct PUTFH4args`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Putfh4Args {
+    pub object: NfsFh4,
+}
+
+/// `struct PUTFH4res`
+pub type Putfh4Res = Nfs4Res<()>;
+
+/// `struct PUTPUBFH4res`
+pub type Putpubfh4Res = Nfs4Res<()>;
+
+/// `struct PUTROOTFH4res`
+pub type Putrootfh4Res = Nfs4Res<()>;
+
+/// `struct RESTOREFH4res`
+pub type Restorefh4Res = Nfs4Res<()>;
+
+/// `struct SAVEFH4res`
+pub type Savefh4Res = Nfs4Res<()>;
+
+// ── READ ──────────────────────────────────────────────────────────────────
+
+/// `struct READ4args`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Read4Args {
+    pub stateid: Stateid4,
+    pub offset: u64,
+    pub count: u32,
+}
+
+/// `struct READ4resok`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Read4Resok {
+    pub eof: bool,
+    pub data: Opaque4,
+}
+
+/// `union READ4res`
+pub type Read4Res = Nfs4Res<Read4Resok>;
+
+// ── READDIR ───────────────────────────────────────────────────────────────
+
+/// `struct READDIR4args`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Readdir4Args {
+    pub cookie: u64,
+    pub cookieverf: Verifier4,
+    pub dircount: u32,
+    pub maxcount: u32,
+    pub attr_request: Bitmap4,
+}
+
+/// `struct entry4`, without its `nextentry` pointer: one entry of a
+/// [`Dirlist4`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Entry4 {
+    pub cookie: u64,
+    pub name: Component4,
+    pub attrs: Fattr4,
+}
+
+/// `struct dirlist4`
+///
+/// ```text
+/// struct dirlist4 {
+///     entry4 *entries;
+///     bool   eof;
+/// };
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Dirlist4 {
+    #[serde(with = "crate::linked_list")]
+    pub entries: Vec<Entry4>,
+    pub eof: bool,
+}
+
+/// `struct READDIR4resok`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Readdir4Resok {
+    pub cookieverf: Verifier4,
+    pub reply: Dirlist4,
+}
+
+/// `union READDIR4res`
+pub type Readdir4Res = Nfs4Res<Readdir4Resok>;
+
+// ── READLINK ──────────────────────────────────────────────────────────────
+
+/// `struct READLINK4resok`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Readlink4Resok {
+    pub link: Linktext4,
+}
+
+/// `union READLINK4res`
+pub type Readlink4Res = Nfs4Res<Readlink4Resok>;
+
+// ── REMOVE and RENAME ─────────────────────────────────────────────────────
+
+/// `struct REMOVE4args`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Remove4Args {
+    pub target: Component4,
+}
+
+/// `struct REMOVE4resok`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Remove4Resok {
+    pub cinfo: ChangeInfo4,
+}
+
+/// `union REMOVE4res`
+pub type Remove4Res = Nfs4Res<Remove4Resok>;
+
+/// `struct RENAME4args`: rename `oldname` in the saved file handle to
+/// `newname` in the current one.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Rename4Args {
+    pub oldname: Component4,
+    pub newname: Component4,
+}
+
+/// `struct RENAME4resok`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Rename4Resok {
+    pub source_cinfo: ChangeInfo4,
+    pub target_cinfo: ChangeInfo4,
+}
+
+/// `union RENAME4res`
+pub type Rename4Res = Nfs4Res<Rename4Resok>;
+
+// ── RENEW, SETCLIENTID and SETCLIENTID_CONFIRM (NFSv4.0 only) ─────────────
+
+/// `struct RENEW4args`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Renew4Args {
+    pub clientid: u64,
+}
+
+/// `struct RENEW4res`
+pub type Renew4Res = Nfs4Res<()>;
+
+/// `struct SETCLIENTID4args`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Setclientid4Args {
+    pub client: NfsClientId4,
+    pub callback: CbClient4,
+    pub callback_ident: u32,
+}
+
+/// `struct SETCLIENTID4resok`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Setclientid4Resok {
+    pub clientid: u64,
+    pub setclientid_confirm: Verifier4,
+}
+
+nfs4_res! {
+    /// `union SETCLIENTID4res switch (nfsstat4 status)`.
+    pub enum Setclientid4Res {
+        /// `NFS4_OK`
+        Ok(Setclientid4Resok),
+        /// `NFS4ERR_CLID_INUSE` and the address of the client holding the
+        /// id.
+        ClidInuse(Clientaddr4) = ClidInuse,
+    }
+}
+
+/// `struct SETCLIENTID_CONFIRM4args`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SetclientidConfirm4Args {
+    pub clientid: u64,
+    pub setclientid_confirm: Verifier4,
+}
+
+/// `struct SETCLIENTID_CONFIRM4res`
+pub type SetclientidConfirm4Res = Nfs4Res<()>;
+
+// ── SECINFO and SECINFO_NO_NAME ───────────────────────────────────────────
+
+/// `struct rpcsec_gss_info`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct RpcsecGssInfo {
+    pub oid: SecOid4,
+    pub qop: u32,
+    pub service: RpcGssSvc,
+}
+
+xdr_enum! {
+    /// `union secinfo4 switch (uint32_t flavor)`: an acceptable RPC
+    /// security flavor. Flavors other than these three are kept as
+    /// `Other`.
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    pub enum Secinfo4 {
+        /// `AUTH_NONE`
+        AuthNone = 0,
+        /// `AUTH_SYS`
+        AuthSys = 1,
+        /// `RPCSEC_GSS`
+        RpcsecGss(RpcsecGssInfo) = 6,
+        _ => Other(i32),
+    }
+}
+
+/// `struct SECINFO4args`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Secinfo4Args {
+    pub name: Component4,
+}
+
+/// `typedef secinfo4 SECINFO4resok<>`: the flavors, most preferred first.
+pub type Secinfo4Resok = Vec<Secinfo4>;
+
+/// `union SECINFO4res`
+pub type Secinfo4Res = Nfs4Res<Secinfo4Resok>;
+
+xdr_enum! {
+    /// `enum secinfo_style4`: the argument of `SECINFO_NO_NAME`.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum SecinfoStyle4 {
+        /// The current file handle.
+        CurrentFh = 0,
+        /// The parent of the current file handle.
+        Parent = 1,
+    }
+}
+
+/// `typedef secinfo_style4 SECINFO_NO_NAME4args`
+pub type SecinfoNoName4Args = SecinfoStyle4;
+
+/// `typedef SECINFO4res SECINFO_NO_NAME4res`
+pub type SecinfoNoName4Res = Secinfo4Res;
+
+// ── SETATTR ───────────────────────────────────────────────────────────────
+
+/// `struct SETATTR4args`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Setattr4Args {
+    pub stateid: Stateid4,
+    pub obj_attributes: Fattr4,
+}
+
+/// `struct SETATTR4res`: unlike other results, the attributes set are
+/// returned whatever the status.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Setattr4Res {
+    pub status: Nfsstat4,
+    pub attrsset: Bitmap4,
+}
+
+// ── WRITE ─────────────────────────────────────────────────────────────────
+
+/// `struct WRITE4args`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Write4Args {
+    pub stateid: Stateid4,
+    pub offset: u64,
+    pub stable: StableHow4,
+    pub data: Opaque4,
+}
+
+/// `struct WRITE4resok`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Write4Resok {
+    pub count: u32,
+    pub committed: StableHow4,
+    pub writeverf: Verifier4,
+}
+
+/// `union WRITE4res`
+pub type Write4Res = Nfs4Res<Write4Resok>;
+
+// ── RELEASE_LOCKOWNER (NFSv4.0 only) ──────────────────────────────────────
+
+/// `struct RELEASE_LOCKOWNER4args`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReleaseLockowner4Args {
+    pub lock_owner: LockOwner4,
+}
+
+/// `struct RELEASE_LOCKOWNER4res`
+pub type ReleaseLockowner4Res = Nfs4Res<()>;
+
+// ── BACKCHANNEL_CTL and BIND_CONN_TO_SESSION ──────────────────────────────
+
+/// `struct gss_cb_handles4`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GssCbHandles4 {
+    pub gcbp_service: RpcGssSvc,
+    pub gcbp_handle_from_server: Gsshandle4,
+    pub gcbp_handle_from_client: Gsshandle4,
+}
+
+xdr_enum! {
+    /// `union callback_sec_parms4 switch (uint32_t cb_secflavor)`: how the
+    /// server authenticates callbacks.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum CallbackSecParms4 {
+        /// `AUTH_NONE`
+        AuthNone = 0,
+        /// `AUTH_SYS`
+        AuthSys(AuthSysParms) = 1,
+        /// `RPCSEC_GSS`
+        RpcsecGss(GssCbHandles4) = 6,
+    }
+}
+
+/// `struct BACKCHANNEL_CTL4args`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackchannelCtl4Args {
+    pub bca_cb_program: u32,
+    pub bca_sec_parms: Vec<CallbackSecParms4>,
+}
+
+/// `struct BACKCHANNEL_CTL4res`
+pub type BackchannelCtl4Res = Nfs4Res<()>;
+
+xdr_enum! {
+    /// `enum channel_dir_from_client4`
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum ChannelDirFromClient4 {
+        Cdfc4Fore = 0x1,
+        Cdfc4Back = 0x2,
+        Cdfc4ForeOrBoth = 0x3,
+        Cdfc4BackOrBoth = 0x7,
+    }
+}
+
+xdr_enum! {
+    /// `enum channel_dir_from_server4`
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum ChannelDirFromServer4 {
+        Cdfs4Fore = 0x1,
+        Cdfs4Back = 0x2,
+        Cdfs4Both = 0x3,
+    }
+}
+
+/// `struct BIND_CONN_TO_SESSION4args`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BindConnToSession4Args {
+    pub bctsa_sessid: Sessionid4,
+    pub bctsa_dir: ChannelDirFromClient4,
+    pub bctsa_use_conn_in_rdma_mode: bool,
+}
+
+/// `struct BIND_CONN_TO_SESSION4resok`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BindConnToSession4Resok {
+    pub bctsr_sessid: Sessionid4,
+    pub bctsr_dir: ChannelDirFromServer4,
+    pub bctsr_use_conn_in_rdma_mode: bool,
+}
+
+/// `union BIND_CONN_TO_SESSION4res`
+pub type BindConnToSession4Res = Nfs4Res<BindConnToSession4Resok>;
+
+// ── EXCHANGE_ID ───────────────────────────────────────────────────────────
+
+/// `struct client_owner4`: an NFSv4.1 client's identity.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ClientOwner4 {
+    pub co_verifier: Verifier4,
+    pub co_ownerid: BoundedBytes<NFS4_OPAQUE_LIMIT>,
+}
+
+/// `struct server_owner4`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ServerOwner4 {
+    pub so_minor_id: u64,
+    pub so_major_id: BoundedBytes<NFS4_OPAQUE_LIMIT>,
+}
+
+/// `struct state_protect_ops4`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct StateProtectOps4 {
+    pub spo_must_enforce: Bitmap4,
+    pub spo_must_allow: Bitmap4,
+}
+
+/// `struct ssv_sp_parms4`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SsvSpParms4 {
+    pub ssp_ops: StateProtectOps4,
+    pub ssp_hash_algs: Vec<SecOid4>,
+    pub ssp_encr_algs: Vec<SecOid4>,
+    pub ssp_window: u32,
+    pub ssp_num_gss_handles: u32,
+}
+
+xdr_enum! {
+    /// `enum state_protect_how4`
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum StateProtectHow4 {
+        Sp4None = 0,
+        Sp4MachCred = 1,
+        Sp4Ssv = 2,
+    }
+}
+
+xdr_enum! {
+    /// `union state_protect4_a switch (state_protect_how4 spa_how)`,
+    /// switched on [`StateProtectHow4`].
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    pub enum StateProtect4A {
+        Sp4None = 0,
+        Sp4MachCred(StateProtectOps4) = 1,
+        Sp4Ssv(SsvSpParms4) = 2,
+    }
+}
+
+/// `struct nfs_impl_id4`: the name and build date of an implementation.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct NfsImplId4 {
+    pub nii_domain: Utf8string,
+    pub nii_name: Utf8string,
+    pub nii_date: Nfstime4,
+}
+
+/// `struct EXCHANGE_ID4args`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExchangeId4Args {
+    pub eia_clientowner: ClientOwner4,
+    /// `EXCHGID4_FLAG_*` bits.
+    pub eia_flags: u32,
+    pub eia_state_protect: StateProtect4A,
+    /// `nfs_impl_id4 eia_client_impl_id<1>`.
+    pub eia_client_impl_id: Option<NfsImplId4>,
+}
+
+/// `struct ssv_prot_info4`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SsvProtInfo4 {
+    pub spi_ops: StateProtectOps4,
+    pub spi_hash_alg: u32,
+    pub spi_encr_alg: u32,
+    pub spi_ssv_len: u32,
+    pub spi_window: u32,
+    pub spi_handles: Vec<Gsshandle4>,
+}
+
+xdr_enum! {
+    /// `union state_protect4_r switch (state_protect_how4 spr_how)`,
+    /// switched on [`StateProtectHow4`].
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    pub enum StateProtect4R {
+        Sp4None = 0,
+        Sp4MachCred(StateProtectOps4) = 1,
+        Sp4Ssv(SsvProtInfo4) = 2,
+    }
+}
+
+/// `struct EXCHANGE_ID4resok`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExchangeId4Resok {
+    pub eir_clientid: u64,
+    pub eir_sequenceid: u32,
+    pub eir_flags: u32,
+    pub eir_state_protect: StateProtect4R,
+    pub eir_server_owner: ServerOwner4,
+    pub eir_server_scope: BoundedBytes<NFS4_OPAQUE_LIMIT>,
+    /// `nfs_impl_id4 eir_server_impl_id<1>`.
+    pub eir_server_impl_id: Option<NfsImplId4>,
+}
+
+/// `union EXCHANGE_ID4res`
+pub type ExchangeId4Res = Nfs4Res<ExchangeId4Resok>;
+
+// ── CREATE_SESSION and DESTROY_SESSION ────────────────────────────────────
+
+/// `struct channel_attrs4`: the limits of a session's fore or back
+/// channel.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ChannelAttrs4 {
+    pub ca_headerpadsize: u32,
+    pub ca_maxrequestsize: u32,
+    pub ca_maxresponsesize: u32,
+    pub ca_maxresponsesize_cached: u32,
+    pub ca_maxoperations: u32,
+    pub ca_maxrequests: u32,
+    /// `uint32_t ca_rdma_ird<1>`.
+    pub ca_rdma_ird: Option<u32>,
+}
+
+/// `struct CREATE_SESSION4args`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CreateSession4Args {
+    pub csa_clientid: u64,
+    pub csa_sequence: u32,
+    /// `CREATE_SESSION4_FLAG_*` bits.
+    pub csa_flags: u32,
+    pub csa_fore_chan_attrs: ChannelAttrs4,
+    pub csa_back_chan_attrs: ChannelAttrs4,
+    pub csa_cb_program: u32,
+    pub csa_sec_parms: Vec<CallbackSecParms4>,
+}
+
+/// `struct CREATE_SESSION4resok`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CreateSession4Resok {
+    pub csr_sessionid: Sessionid4,
+    pub csr_sequence: u32,
+    pub csr_flags: u32,
+    pub csr_fore_chan_attrs: ChannelAttrs4,
+    pub csr_back_chan_attrs: ChannelAttrs4,
+}
+
+/// `union CREATE_SESSION4res`
+pub type CreateSession4Res = Nfs4Res<CreateSession4Resok>;
+
+/// `struct DESTROY_SESSION4args`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DestroySession4Args {
+    pub dsa_sessionid: Sessionid4,
+}
+
+/// `struct DESTROY_SESSION4res`
+pub type DestroySession4Res = Nfs4Res<()>;
+
+// ── FREE_STATEID and TEST_STATEID ─────────────────────────────────────────
+
+/// `struct FREE_STATEID4args`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FreeStateid4Args {
+    pub fsa_stateid: Stateid4,
+}
+
+/// `struct FREE_STATEID4res`
+pub type FreeStateid4Res = Nfs4Res<()>;
+
+/// `struct TEST_STATEID4args`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TestStateid4Args {
+    pub ts_stateids: Vec<Stateid4>,
+}
+
+/// `struct TEST_STATEID4resok`: one status per stateid tested.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TestStateid4Resok {
+    pub tsr_status_codes: Vec<Nfsstat4>,
+}
+
+/// `union TEST_STATEID4res`
+pub type TestStateid4Res = Nfs4Res<TestStateid4Resok>;
+
+// ── GET_DIR_DELEGATION ────────────────────────────────────────────────────
+
+/// `struct GET_DIR_DELEGATION4args`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GetDirDelegation4Args {
+    pub gdda_signal_deleg_avail: bool,
+    pub gdda_notification_types: Bitmap4,
+    pub gdda_child_attr_delay: Nfstime4,
+    pub gdda_dir_attr_delay: Nfstime4,
+    pub gdda_child_attributes: Bitmap4,
+    pub gdda_dir_attributes: Bitmap4,
+}
+
+/// `struct GET_DIR_DELEGATION4resok`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GetDirDelegation4Resok {
+    pub gddr_cookieverf: Verifier4,
+    pub gddr_stateid: Stateid4,
+    pub gddr_notification: Bitmap4,
+    pub gddr_child_attributes: Bitmap4,
+    pub gddr_dir_attributes: Bitmap4,
+}
+
+xdr_enum! {
+    /// `union GET_DIR_DELEGATION4res_non_fatal switch (gddrnf4_status
+    /// gddrnf_status)`: a delegation, or whether the server will signal
+    /// when one is available.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum GetDirDelegation4ResNonFatal {
+        /// `GDD4_OK`
+        Gdd4Ok(GetDirDelegation4Resok) = 0,
+        /// `GDD4_UNAVAIL`
+        Gdd4Unavail(bool) = 1,
+    }
+}
+
+/// `union GET_DIR_DELEGATION4res`
+pub type GetDirDelegation4Res = Nfs4Res<GetDirDelegation4ResNonFatal>;
+
+// ── GETDEVICEINFO and GETDEVICELIST ───────────────────────────────────────
+
+/// `struct device_addr4`: a layout-specific device address.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeviceAddr4 {
+    pub da_layout_type: Layouttype4,
+    pub da_addr_body: Opaque4,
+}
+
+/// `struct GETDEVICEINFO4args`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Getdeviceinfo4Args {
+    pub gdia_device_id: Deviceid4,
+    pub gdia_layout_type: Layouttype4,
+    pub gdia_maxcount: u32,
+    pub gdia_notify_types: Bitmap4,
+}
+
+/// `struct GETDEVICEINFO4resok`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Getdeviceinfo4Resok {
+    pub gdir_device_addr: DeviceAddr4,
+    pub gdir_notification: Bitmap4,
+}
+
+nfs4_res! {
+    /// `union GETDEVICEINFO4res switch (nfsstat4 gdir_status)`.
+    pub enum Getdeviceinfo4Res {
+        /// `NFS4_OK`
+        Ok(Getdeviceinfo4Resok),
+        /// `NFS4ERR_TOOSMALL` and the `gdia_maxcount` needed.
+        Toosmall(u32) = Toosmall,
+    }
+}
+
+/// `struct GETDEVICELIST4args`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Getdevicelist4Args {
+    pub gdla_layout_type: Layouttype4,
+    pub gdla_maxdevices: u32,
+    pub gdla_cookie: u64,
+    pub gdla_cookieverf: Verifier4,
+}
+
+/// `struct GETDEVICELIST4resok`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Getdevicelist4Resok {
+    pub gdlr_cookie: u64,
+    pub gdlr_cookieverf: Verifier4,
+    pub gdlr_deviceid_list: Vec<Deviceid4>,
+    pub gdlr_eof: bool,
+}
+
+/// `union GETDEVICELIST4res`
+pub type Getdevicelist4Res = Nfs4Res<Getdevicelist4Resok>;
+
+// ── LAYOUTCOMMIT, LAYOUTGET and LAYOUTRETURN ──────────────────────────────
+
+/// `struct layoutupdate4`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Layoutupdate4 {
+    pub lou_type: Layouttype4,
+    pub lou_body: Opaque4,
+}
+
+/// `struct LAYOUTCOMMIT4args`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Layoutcommit4Args {
+    pub loca_offset: u64,
+    pub loca_length: u64,
+    pub loca_reclaim: bool,
+    pub loca_stateid: Stateid4,
+    /// `union newoffset4 switch (bool no_newoffset)`.
+    pub loca_last_write_offset: Option<u64>,
+    /// `union newtime4 switch (bool nt_timechanged)`.
+    pub loca_time_modify: Option<Nfstime4>,
+    pub loca_layoutupdate: Layoutupdate4,
+}
+
+/// `struct LAYOUTCOMMIT4resok`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Layoutcommit4Resok {
+    /// `union newsize4 switch (bool ns_sizechanged)`.
+    pub locr_newsize: Option<u64>,
+}
+
+/// `union LAYOUTCOMMIT4res`
+pub type Layoutcommit4Res = Nfs4Res<Layoutcommit4Resok>;
+
+xdr_enum! {
+    /// `enum layoutiomode4`
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum Layoutiomode4 {
+        Read = 1,
+        Rw = 2,
+        Any = 3,
+    }
+}
+
+/// `struct LAYOUTGET4args`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Layoutget4Args {
+    pub loga_signal_layout_avail: bool,
+    pub loga_layout_type: Layouttype4,
+    pub loga_iomode: Layoutiomode4,
+    pub loga_offset: u64,
+    pub loga_length: u64,
+    pub loga_minlength: u64,
+    pub loga_stateid: Stateid4,
+    pub loga_maxcount: u32,
+}
+
+/// `struct layout_content4`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LayoutContent4 {
+    pub loc_type: Layouttype4,
+    pub loc_body: Opaque4,
+}
+
+/// `struct layout4`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Layout4 {
+    pub lo_offset: u64,
+    pub lo_length: u64,
+    pub lo_iomode: Layoutiomode4,
+    pub lo_content: LayoutContent4,
+}
+
+/// `struct LAYOUTGET4resok`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Layoutget4Resok {
+    pub logr_return_on_close: bool,
+    pub logr_stateid: Stateid4,
+    pub logr_layout: Vec<Layout4>,
+}
+
+nfs4_res! {
+    /// `union LAYOUTGET4res switch (nfsstat4 logr_status)`.
+    pub enum Layoutget4Res {
+        /// `NFS4_OK`
+        Ok(Layoutget4Resok),
+        /// `NFS4ERR_LAYOUTTRYLATER` and whether the server will signal when
+        /// the layout is available.
+        Layouttrylater(bool) = Layouttrylater,
+    }
+}
+
+xdr_enum! {
+    /// `enum layoutreturn_type4`
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum LayoutreturnType4 {
+        File = 1,
+        Fsid = 2,
+        All = 3,
+    }
+}
+
+/// `struct layoutreturn_file4`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LayoutreturnFile4 {
+    pub lrf_offset: u64,
+    pub lrf_length: u64,
+    pub lrf_stateid: Stateid4,
+    pub lrf_body: Opaque4,
+}
+
+xdr_enum! {
+    /// `union layoutreturn4 switch (layoutreturn_type4 lr_returntype)`,
+    /// switched on [`LayoutreturnType4`].
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum Layoutreturn4 {
+        File(LayoutreturnFile4) = 1,
+        Fsid = 2,
+        All = 3,
+    }
+}
+
+/// `struct LAYOUTRETURN4args`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Layoutreturn4Args {
+    pub lora_reclaim: bool,
+    pub lora_layout_type: Layouttype4,
+    pub lora_iomode: Layoutiomode4,
+    pub lora_layoutreturn: Layoutreturn4,
+}
+
+/// `union LAYOUTRETURN4res`: the layout stateid, if any layout remains
+/// (`union layoutreturn_stateid switch (bool lrs_present)`).
+pub type Layoutreturn4Res = Nfs4Res<Option<Stateid4>>;
+
+// ── SEQUENCE ──────────────────────────────────────────────────────────────
+
+/// `struct SEQUENCE4args`: the first operation of every NFSv4.1 `COMPOUND`
+/// on a session.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Sequence4Args {
+    pub sa_sessionid: Sessionid4,
+    pub sa_sequenceid: u32,
+    pub sa_slotid: u32,
+    pub sa_highest_slotid: u32,
+    pub sa_cachethis: bool,
+}
+
+/// `struct SEQUENCE4resok`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Sequence4Resok {
+    pub sr_sessionid: Sessionid4,
+    pub sr_sequenceid: u32,
+    pub sr_slotid: u32,
+    pub sr_highest_slotid: u32,
+    pub sr_target_highest_slotid: u32,
+    /// `SEQ4_STATUS_*` bits.
+    pub sr_status_flags: u32,
+}
+
+/// `union SEQUENCE4res`
+pub type Sequence4Res = Nfs4Res<Sequence4Resok>;
+
+// ── SET_SSV ───────────────────────────────────────────────────────────────
+
+/// `struct SET_SSV4args`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SetSsv4Args {
+    pub ssa_ssv: Opaque4,
+    pub ssa_digest: Opaque4,
+}
+
+/// `struct SET_SSV4resok`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SetSsv4Resok {
+    pub ssr_digest: Opaque4,
+}
+
+/// `union SET_SSV4res`
+pub type SetSsv4Res = Nfs4Res<SetSsv4Resok>;
+
+// ── WANT_DELEGATION ───────────────────────────────────────────────────────
+
+xdr_enum! {
+    /// `union deleg_claim4 switch (open_claim_type4 dc_claim)`, switched on
+    /// [`OpenClaimType4`].
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum DelegClaim4 {
+        ClaimPrevious(OpenDelegationType4) = 1,
+        ClaimFh = 4,
+        ClaimDelegPrevFh = 6,
+    }
+}
+
+/// `struct WANT_DELEGATION4args`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WantDelegation4Args {
+    /// `OPEN4_SHARE_ACCESS_WANT_*` bits.
+    pub wda_want: u32,
+    pub wda_claim: DelegClaim4,
+}
+
+/// `union WANT_DELEGATION4res`
+pub type WantDelegation4Res = Nfs4Res<OpenDelegation4>;
+
+// ── DESTROY_CLIENTID and RECLAIM_COMPLETE ─────────────────────────────────
+
+/// `struct DESTROY_CLIENTID4args`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DestroyClientid4Args {
+    pub dca_clientid: u64,
+}
+
+/// `struct DESTROY_CLIENTID4res`
+pub type DestroyClientid4Res = Nfs4Res<()>;
+
+/// `struct RECLAIM_COMPLETE4args`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReclaimComplete4Args {
+    /// Whether reclaim is complete for the current file system only.
+    pub rca_one_fs: bool,
+}
+
+/// `struct RECLAIM_COMPLETE4res`
+pub type ReclaimComplete4Res = Nfs4Res<()>;
+
+/// `struct ILLEGAL4res`: the result of an unknown operation.
+pub type Illegal4Res = Nfs4Res<()>;
+
+// ── COMPOUND ──────────────────────────────────────────────────────────────
+
+xdr_enum! {
+    /// `union nfs_argop4 switch (nfs_opnum4 argop)`: one operation of a
+    /// `COMPOUND` request. The discriminant is the operation number.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum NfsArgop4 {
+        Access(Access4Args) = 3,
+        Close(Close4Args) = 4,
+        Commit(Commit4Args) = 5,
+        Create(Create4Args) = 6,
+        Delegpurge(Delegpurge4Args) = 7,
+        Delegreturn(Delegreturn4Args) = 8,
+        Getattr(Getattr4Args) = 9,
+        Getfh = 10,
+        Link(Link4Args) = 11,
+        Lock(Lock4Args) = 12,
+        Lockt(Lockt4Args) = 13,
+        Locku(Locku4Args) = 14,
+        Lookup(Lookup4Args) = 15,
+        Lookupp = 16,
+        Nverify(Nverify4Args) = 17,
+        Open(Open4Args) = 18,
+        Openattr(Openattr4Args) = 19,
+        OpenConfirm(OpenConfirm4Args) = 20,
+        OpenDowngrade(OpenDowngrade4Args) = 21,
+        Putfh(Putfh4Args) = 22,
+        Putpubfh = 23,
+        Putrootfh = 24,
+        Read(Read4Args) = 25,
+        Readdir(Readdir4Args) = 26,
+        Readlink = 27,
+        Remove(Remove4Args) = 28,
+        Rename(Rename4Args) = 29,
+        Renew(Renew4Args) = 30,
+        Restorefh = 31,
+        Savefh = 32,
+        Secinfo(Secinfo4Args) = 33,
+        Setattr(Setattr4Args) = 34,
+        Setclientid(Setclientid4Args) = 35,
+        SetclientidConfirm(SetclientidConfirm4Args) = 36,
+        Verify(Verify4Args) = 37,
+        Write(Write4Args) = 38,
+        ReleaseLockowner(ReleaseLockowner4Args) = 39,
+        BackchannelCtl(BackchannelCtl4Args) = 40,
+        BindConnToSession(BindConnToSession4Args) = 41,
+        ExchangeId(ExchangeId4Args) = 42,
+        CreateSession(CreateSession4Args) = 43,
+        DestroySession(DestroySession4Args) = 44,
+        FreeStateid(FreeStateid4Args) = 45,
+        GetDirDelegation(GetDirDelegation4Args) = 46,
+        Getdeviceinfo(Getdeviceinfo4Args) = 47,
+        Getdevicelist(Getdevicelist4Args) = 48,
+        Layoutcommit(Layoutcommit4Args) = 49,
+        Layoutget(Layoutget4Args) = 50,
+        Layoutreturn(Layoutreturn4Args) = 51,
+        SecinfoNoName(SecinfoNoName4Args) = 52,
+        Sequence(Sequence4Args) = 53,
+        SetSsv(SetSsv4Args) = 54,
+        TestStateid(TestStateid4Args) = 55,
+        WantDelegation(WantDelegation4Args) = 56,
+        DestroyClientid(DestroyClientid4Args) = 57,
+        ReclaimComplete(ReclaimComplete4Args) = 58,
+        /// `OP_ILLEGAL`: never valid in a request, but encodable for
+        /// testing servers.
+        Illegal = 10044,
+    }
+}
+
+xdr_enum! {
+    /// `union nfs_resop4 switch (nfs_opnum4 resop)`: the result of one
+    /// operation of a `COMPOUND` request, under the same operation number.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum NfsResop4 {
+        Access(Access4Res) = 3,
+        Close(Close4Res) = 4,
+        Commit(Commit4Res) = 5,
+        Create(Create4Res) = 6,
+        Delegpurge(Delegpurge4Res) = 7,
+        Delegreturn(Delegreturn4Res) = 8,
+        Getattr(Getattr4Res) = 9,
+        Getfh(Getfh4Res) = 10,
+        Link(Link4Res) = 11,
+        Lock(Lock4Res) = 12,
+        Lockt(Lockt4Res) = 13,
+        Locku(Locku4Res) = 14,
+        Lookup(Lookup4Res) = 15,
+        Lookupp(Lookupp4Res) = 16,
+        Nverify(Nverify4Res) = 17,
+        Open(Open4Res) = 18,
+        Openattr(Openattr4Res) = 19,
+        OpenConfirm(OpenConfirm4Res) = 20,
+        OpenDowngrade(OpenDowngrade4Res) = 21,
+        Putfh(Putfh4Res) = 22,
+        Putpubfh(Putpubfh4Res) = 23,
+        Putrootfh(Putrootfh4Res) = 24,
+        Read(Read4Res) = 25,
+        Readdir(Readdir4Res) = 26,
+        Readlink(Readlink4Res) = 27,
+        Remove(Remove4Res) = 28,
+        Rename(Rename4Res) = 29,
+        Renew(Renew4Res) = 30,
+        Restorefh(Restorefh4Res) = 31,
+        Savefh(Savefh4Res) = 32,
+        Secinfo(Secinfo4Res) = 33,
+        Setattr(Setattr4Res) = 34,
+        Setclientid(Setclientid4Res) = 35,
+        SetclientidConfirm(SetclientidConfirm4Res) = 36,
+        Verify(Verify4Res) = 37,
+        Write(Write4Res) = 38,
+        ReleaseLockowner(ReleaseLockowner4Res) = 39,
+        BackchannelCtl(BackchannelCtl4Res) = 40,
+        BindConnToSession(BindConnToSession4Res) = 41,
+        ExchangeId(ExchangeId4Res) = 42,
+        CreateSession(CreateSession4Res) = 43,
+        DestroySession(DestroySession4Res) = 44,
+        FreeStateid(FreeStateid4Res) = 45,
+        GetDirDelegation(GetDirDelegation4Res) = 46,
+        Getdeviceinfo(Getdeviceinfo4Res) = 47,
+        Getdevicelist(Getdevicelist4Res) = 48,
+        Layoutcommit(Layoutcommit4Res) = 49,
+        Layoutget(Layoutget4Res) = 50,
+        Layoutreturn(Layoutreturn4Res) = 51,
+        SecinfoNoName(SecinfoNoName4Res) = 52,
+        Sequence(Sequence4Res) = 53,
+        SetSsv(SetSsv4Res) = 54,
+        TestStateid(TestStateid4Res) = 55,
+        WantDelegation(WantDelegation4Res) = 56,
+        DestroyClientid(DestroyClientid4Res) = 57,
+        ReclaimComplete(ReclaimComplete4Res) = 58,
+        Illegal(Illegal4Res) = 10044,
+    }
+}
+
+impl NfsResop4 {
+    /// The status of the operation.
+    pub fn status(&self) -> Nfsstat4 {
+        use NfsResop4::*;
+        match self {
+            Access(r) => r.status(),
+            Close(r) | Locku(r) => r.status(),
+            Commit(r) => r.status(),
+            Create(r) => r.status(),
+            Delegpurge(r)
+            | Delegreturn(r)
+            | Lookup(r)
+            | Lookupp(r)
+            | Nverify(r)
+            | Openattr(r)
+            | Putfh(r)
+            | Putpubfh(r)
+            | Putrootfh(r)
+            | Renew(r)
+            | Restorefh(r)
+            | Savefh(r)
+            | SetclientidConfirm(r)
+            | Verify(r)
+            | ReleaseLockowner(r)
+            | BackchannelCtl(r)
+            | DestroySession(r)
+            | FreeStateid(r)
+            | DestroyClientid(r)
+            | ReclaimComplete(r)
+            | Illegal(r) => r.status(),
+            Getattr(r) => r.status(),
+            Getfh(r) => r.status(),
+            Link(r) => r.status(),
+            Lock(r) => r.status(),
+            Lockt(r) => r.status(),
+            Open(r) => r.status(),
+            OpenConfirm(r) => r.status(),
+            OpenDowngrade(r) => r.status(),
+            Read(r) => r.status(),
+            Readdir(r) => r.status(),
+            Readlink(r) => r.status(),
+            Remove(r) => r.status(),
+            Rename(r) => r.status(),
+            Secinfo(r) | SecinfoNoName(r) => r.status(),
+            Setattr(r) => r.status,
+            Setclientid(r) => r.status(),
+            Write(r) => r.status(),
+            BindConnToSession(r) => r.status(),
+            ExchangeId(r) => r.status(),
+            CreateSession(r) => r.status(),
+            GetDirDelegation(r) => r.status(),
+            Getdeviceinfo(r) => r.status(),
+            Getdevicelist(r) => r.status(),
+            Layoutcommit(r) => r.status(),
+            Layoutget(r) => r.status(),
+            Layoutreturn(r) => r.status(),
+            Sequence(r) => r.status(),
+            SetSsv(r) => r.status(),
+            TestStateid(r) => r.status(),
+            WantDelegation(r) => r.status(),
+        }
+    }
+}
+
+/// `struct COMPOUND4args`: the arguments of [`NFSPROC4_COMPOUND`].
+///
+/// ```text
+/// struct COMPOUND4args {
+///     utf8str_cs tag;
+///     uint32_t   minorversion;
+///     nfs_argop4 argarray<>;
+/// };
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Compound4Args {
+    /// Echoed in the reply; otherwise ignored by the server.
+    pub tag: Utf8string,
+    /// 0 for NFSv4.0, 1 for NFSv4.1.
+    pub minorversion: u32,
+    pub argarray: Vec<NfsArgop4>,
+}
+
+/// `struct COMPOUND4res`: the results of [`NFSPROC4_COMPOUND`].
+///
+/// ```text
+/// struct COMPOUND4res {
+///     nfsstat4   status;
+///     utf8str_cs tag;
+///     nfs_resop4 resarray<>;
+/// };
+/// ```
+///
+/// The server stops at the first operation that fails, so `resarray` may
+/// be shorter than the request's `argarray`; `status` is the status of its
+/// last element.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Compound4Res {
+    pub status: Nfsstat4,
+    pub tag: Utf8string,
+    pub resarray: Vec<NfsResop4>,
+}
@@ -0,0 +1,363 @@
+//! Network Lock Manager version 4 protocol types (X/Open XNFS, as used with
+//! NFSv3). Requires the `nlm4` feature.
+//!
+//! NFSv3 has no locking of its own: clients take byte-range locks and DOS
+//! share reservations through the NLM program, and the servers learn of
+//! client restarts through the status monitor (the `nsm` feature). NLM
+//! version 4 is version 3 with 64-bit offsets and lengths and the NFSv3
+//! file handle. Every call carries a `cookie` that the server returns in its
+//! reply, which matters for the `_MSG` procedures, whose results come back
+//! as separate `_RES` calls.
+//!
+//! ```rust
+//! use xdr_serde::nlm4::{Nlm4Lock, Nlm4Lockargs, Nlm4Res, Nlm4Stats};
+//! use xdr_serde::{from_bytes, to_bytes};
+//!
+//! let args = Nlm4Lockargs {
+//!     cookie: vec![0, 0, 0, 1].try_into().unwrap(),
+//!     block: false,
+//!     exclusive: true,
+//!     alock: Nlm4Lock {
+//!         caller_name: "client".try_into().unwrap(),
+//!         fh: vec![0xfe; 8].try_into().unwrap(),
+//!         oh: b"1234@client".to_vec().try_into().unwrap(),
+//!         svid: 1234,
+//!         l_offset: 0,
+//!         l_len: 0,
+//!     },
+//!     reclaim: false,
+//!     state: 3,
+//! };
+//! assert_eq!(from_bytes::<Nlm4Lockargs>(&to_bytes(&args).unwrap()).unwrap(), args);
+//!
+//! let res: Nlm4Res = from_bytes(&[0, 0, 0, 4, 0, 0, 0, 1, 0, 0, 0, 0]).unwrap();
+//! assert_eq!(res.stat, Nlm4Stats::Granted);
+//! ```
+
+use crate::bounded::{BoundedBytes, BoundedString};
+use crate::xdr_enum;
+use serde::{Deserialize, Serialize};
+
+/// The NLM program number.
+pub const NLM_PROG: u32 = 100021;
+
+/// The NLM protocol version described here.
+pub const NLM4_VERS: u32 = 4;
+
+/// The maximum length of a caller name, in bytes.
+pub const LM_MAXSTRLEN: u32 = 1024;
+
+/// The maximum length of [`Nlm4Notify::name`], in bytes.
+pub const LM_MAXNAMELEN: u32 = LM_MAXSTRLEN + 1;
+
+/// The maximum size of a [`Netobj`], in bytes.
+pub const MAXNETOBJ_SZ: u32 = 1024;
+
+/// Do nothing.
+pub const NLMPROC4_NULL: u32 = 0;
+/// Test for a conflicting lock; takes [`Nlm4Testargs`], returns
+/// [`Nlm4Testres`].
+pub const NLMPROC4_TEST: u32 = 1;
+/// Take a lock; takes [`Nlm4Lockargs`], returns [`Nlm4Res`].
+pub const NLMPROC4_LOCK: u32 = 2;
+/// Cancel a blocked lock request; takes [`Nlm4Cancargs`], returns
+/// [`Nlm4Res`].
+pub const NLMPROC4_CANCEL: u32 = 3;
+/// Release a lock; takes [`Nlm4Unlockargs`], returns [`Nlm4Res`].
+pub const NLMPROC4_UNLOCK: u32 = 4;
+/// Server to client: a blocked lock was granted; takes [`Nlm4Testargs`],
+/// returns [`Nlm4Res`].
+pub const NLMPROC4_GRANTED: u32 = 5;
+/// [`NLMPROC4_TEST`] without a reply; the result is sent as
+/// [`NLMPROC4_TEST_RES`].
+pub const NLMPROC4_TEST_MSG: u32 = 6;
+/// [`NLMPROC4_LOCK`] without a reply.
+pub const NLMPROC4_LOCK_MSG: u32 = 7;
+/// [`NLMPROC4_CANCEL`] without a reply.
+pub const NLMPROC4_CANCEL_MSG: u32 = 8;
+/// [`NLMPROC4_UNLOCK`] without a reply.
+pub const NLMPROC4_UNLOCK_MSG: u32 = 9;
+/// [`NLMPROC4_GRANTED`] without a reply.
+pub const NLMPROC4_GRANTED_MSG: u32 = 10;
+/// The result of [`NLMPROC4_TEST_MSG`]; takes [`Nlm4Testres`].
+pub const NLMPROC4_TEST_RES: u32 = 11;
+/// The result of [`NLMPROC4_LOCK_MSG`]; takes [`Nlm4Res`].
+pub const NLMPROC4_LOCK_RES: u32 = 12;
+/// The result of [`NLMPROC4_CANCEL_MSG`]; takes [`Nlm4Res`].
+pub const NLMPROC4_CANCEL_RES: u32 = 13;
+/// The result of [`NLMPROC4_UNLOCK_MSG`]; takes [`Nlm4Res`].
+pub const NLMPROC4_UNLOCK_RES: u32 = 14;
+/// The result of [`NLMPROC4_GRANTED_MSG`]; takes [`Nlm4Res`].
+pub const NLMPROC4_GRANTED_RES: u32 = 15;
+/// Take a share reservation; takes [`Nlm4Shareargs`], returns
+/// [`Nlm4Shareres`].
+pub const NLMPROC4_SHARE: u32 = 20;
+/// Release a share reservation; takes [`Nlm4Shareargs`], returns
+/// [`Nlm4Shareres`].
+pub const NLMPROC4_UNSHARE: u32 = 21;
+/// [`NLMPROC4_LOCK`] for a client that does not run a status monitor.
+pub const NLMPROC4_NM_LOCK: u32 = 22;
+/// Release every lock of a client; takes [`Nlm4Notify`], returns `void`.
+pub const NLMPROC4_FREE_ALL: u32 = 23;
+
+/// `typedef opaque netobj<MAXNETOBJ_SZ>`: cookies, file handles and owner
+/// handles.
+pub type Netobj = BoundedBytes<MAXNETOBJ_SZ>;
+
+/// `string caller_name<LM_MAXSTRLEN>`: the client's host name.
+pub type CallerName = BoundedString<LM_MAXSTRLEN>;
+
+xdr_enum! {
+    /// `enum nlm4_stats`: the outcome of a lock request.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum Nlm4Stats {
+        /// `NLM4_GRANTED`
+        Granted = 0,
+        /// `NLM4_DENIED`: a conflicting lock is held.
+        Denied = 1,
+        /// `NLM4_DENIED_NOLOCKS`: the server is out of resources.
+        DeniedNolocks = 2,
+        /// `NLM4_BLOCKED`: the server will call `GRANTED` later.
+        Blocked = 3,
+        /// `NLM4_DENIED_GRACE_PERIOD`: only reclaims are accepted.
+        DeniedGracePeriod = 4,
+        /// `NLM4_DEADLCK`
+        Deadlck = 5,
+        /// `NLM4_ROFS`
+        Rofs = 6,
+        /// `NLM4_STALE_FH`
+        StaleFh = 7,
+        /// `NLM4_FBIG`: the range is beyond what the server supports.
+        Fbig = 8,
+        /// `NLM4_FAILED`
+        Failed = 9,
+    }
+}
+
+/// `struct nlm4_holder`: the holder of a conflicting lock.
+///
+/// ```text
+/// struct nlm4_holder {
+///     bool     exclusive;
+///     int32    svid;
+///     netobj   oh;
+///     uint64   l_offset;
+///     uint64   l_len;
+/// };
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Nlm4Holder {
+    pub exclusive: bool,
+    /// The holder's process id.
+    pub svid: i32,
+    /// The holder's owner handle.
+    pub oh: Netobj,
+    pub l_offset: u64,
+    /// Zero means to the end of the file.
+    pub l_len: u64,
+}
+
+xdr_enum! {
+    /// `union nlm4_testrply switch (nlm4_stats stat)`, switched on
+    /// [`Nlm4Stats`]: the conflicting lock, if `TEST` found one.
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    pub enum Nlm4Testrply {
+        Granted = 0,
+        Denied(Nlm4Holder) = 1,
+        DeniedNolocks = 2,
+        Blocked = 3,
+        DeniedGracePeriod = 4,
+        Deadlck = 5,
+        Rofs = 6,
+        StaleFh = 7,
+        Fbig = 8,
+        Failed = 9,
+    }
+}
+
+impl Nlm4Testrply {
+    /// The `stat` discriminant.
+    pub fn stat(&self) -> Nlm4Stats {
+        match self {
+            Nlm4Testrply::Granted => Nlm4Stats::Granted,
+            Nlm4Testrply::Denied(_) => Nlm4Stats::Denied,
+            Nlm4Testrply::DeniedNolocks => Nlm4Stats::DeniedNolocks,
+            Nlm4Testrply::Blocked => Nlm4Stats::Blocked,
+            Nlm4Testrply::DeniedGracePeriod => Nlm4Stats::DeniedGracePeriod,
+            Nlm4Testrply::Deadlck => Nlm4Stats::Deadlck,
+            Nlm4Testrply::Rofs => Nlm4Stats::Rofs,
+            Nlm4Testrply::StaleFh => Nlm4Stats::StaleFh,
+            Nlm4Testrply::Fbig => Nlm4Stats::Fbig,
+            Nlm4Testrply::Failed => Nlm4Stats::Failed,
+        }
+    }
+}
+
+/// `struct nlm4_res`: the result of `LOCK`, `CANCEL`, `UNLOCK` and
+/// `GRANTED`.
+///
+/// ```text
+/// struct nlm4_stat {
+///     nlm4_stats stat;
+/// };
+///
+/// struct nlm4_res {
+///     netobj    cookie;
+///     nlm4_stat stat;
+/// };
+/// ```
+///
+/// The single-field `nlm4_stat` has the same encoding as [`Nlm4Stats`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Nlm4Res {
+    pub cookie: Netobj,
+    pub stat: Nlm4Stats,
+}
+
+/// `struct nlm4_testres`: the result of `TEST`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Nlm4Testres {
+    pub cookie: Netobj,
+    pub stat: Nlm4Testrply,
+}
+
+/// `struct nlm4_lock`: a byte range of a file and its owner.
+///
+/// ```text
+/// struct nlm4_lock {
+///     string   caller_name<LM_MAXSTRLEN>;
+///     netobj   fh;
+///     netobj   oh;
+///     int32    svid;
+///     uint64   l_offset;
+///     uint64   l_len;
+/// };
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Nlm4Lock {
+    pub caller_name: CallerName,
+    /// The NFSv3 file handle.
+    pub fh: Netobj,
+    /// The owner handle, unique to the owner on the client.
+    pub oh: Netobj,
+    /// The owner's process id.
+    pub svid: i32,
+    pub l_offset: u64,
+    /// Zero means to the end of the file.
+    pub l_len: u64,
+}
+
+/// `struct nlm4_lockargs`: the arguments of `LOCK`.
+///
+/// ```text
+/// struct nlm4_lockargs {
+///     netobj    cookie;
+///     bool      block;
+///     bool      exclusive;
+///     nlm4_lock alock;
+///     bool      reclaim;
+///     int32     state;
+/// };
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Nlm4Lockargs {
+    pub cookie: Netobj,
+    /// Whether to wait for a conflicting lock to be released.
+    pub block: bool,
+    pub exclusive: bool,
+    pub alock: Nlm4Lock,
+    /// Whether this reclaims a lock held before the server restarted.
+    pub reclaim: bool,
+    /// The client's status monitor state number.
+    pub state: i32,
+}
+
+/// `struct nlm4_cancargs`: the arguments of `CANCEL`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Nlm4Cancargs {
+    pub cookie: Netobj,
+    pub block: bool,
+    pub exclusive: bool,
+    pub alock: Nlm4Lock,
+}
+
+/// `struct nlm4_testargs`: the arguments of `TEST` and `GRANTED`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Nlm4Testargs {
+    pub cookie: Netobj,
+    pub exclusive: bool,
+    pub alock: Nlm4Lock,
+}
+
+/// `struct nlm4_unlockargs`: the arguments of `UNLOCK`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Nlm4Unlockargs {
+    pub cookie: Netobj,
+    pub alock: Nlm4Lock,
+}
+
+// ── Share reservations ────────────────────────────────────────────────────
+
+xdr_enum! {
+    /// `enum fsh4_mode`: the access a share reservation denies others.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum Fsh4Mode {
+        /// `fsm_DN`: deny none.
+        Dn = 0,
+        /// `fsm_DR`: deny read.
+        Dr = 1,
+        /// `fsm_DW`: deny write.
+        Dw = 2,
+        /// `fsm_DRW`: deny read and write.
+        Drw = 3,
+    }
+}
+
+xdr_enum! {
+    /// `enum fsh4_access`: the access a share reservation requests.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum Fsh4Access {
+        /// `fsa_NONE`
+        None = 0,
+        /// `fsa_R`
+        R = 1,
+        /// `fsa_W`
+        W = 2,
+        /// `fsa_RW`
+        Rw = 3,
+    }
+}
+
+/// `struct nlm4_share`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Nlm4Share {
+    pub caller_name: CallerName,
+    pub fh: Netobj,
+    pub oh: Netobj,
+    pub mode: Fsh4Mode,
+    pub access: Fsh4Access,
+}
+
+/// `struct nlm4_shareargs`: the arguments of `SHARE` and `UNSHARE`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Nlm4Shareargs {
+    pub cookie: Netobj,
+    pub share: Nlm4Share,
+    pub reclaim: bool,
+}
+
+/// `struct nlm4_shareres`: the result of `SHARE` and `UNSHARE`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Nlm4Shareres {
+    pub cookie: Netobj,
+    pub stat: Nlm4Stats,
+    pub sequence: i32,
+}
+
+/// `struct nlm4_notify`: the arguments of `FREE_ALL`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Nlm4Notify {
+    /// The host whose locks are released.
+    pub name: BoundedString<LM_MAXNAMELEN>,
+    pub state: i32,
+}
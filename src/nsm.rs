@@ -0,0 +1,170 @@
+//! Network Status Monitor protocol types (X/Open XNFS). Requires the `nsm`
+//! feature.
+//!
+//! The status monitor (`statd`) tracks which hosts hold locks. A lock
+//! manager asks its local monitor to watch a peer with `SM_MON`; when the
+//! peer restarts and sends `SM_NOTIFY`, the monitor calls back the lock
+//! manager's `my_proc` with a [`Status`] so the peer's locks can be released
+//! or reclaimed. Each host's state number is odd while it is up and
+//! incremented on every restart and shutdown.
+//!
+//! ```rust
+//! use xdr_serde::nsm::{Mon, MonId, MyId, Res, SmStatRes};
+//! use xdr_serde::{from_bytes, to_bytes};
+//!
+//! let mon = Mon {
+//!     mon_id: MonId {
+//!         mon_name: "server".try_into().unwrap(),
+//!         my_id: MyId { my_name: "client".try_into().unwrap(), my_prog: 100021, my_vers: 4, my_proc: 16 },
+//!     },
+//!     r#priv: [0; 16],
+//! };
+//! let bytes = to_bytes(&mon).unwrap();
+//! assert_eq!(bytes.len(), (4 + 8) + (4 + 8) + 12 + 16);
+//! assert_eq!(from_bytes::<Mon>(&bytes).unwrap(), mon);
+//!
+//! let res: SmStatRes = from_bytes(&[0, 0, 0, 0, 0, 0, 0, 7]).unwrap();
+//! assert_eq!(res, SmStatRes { res_stat: Res::StatSucc, state: 7 });
+//! ```
+
+use crate::bounded::BoundedString;
+use crate::xdr_enum;
+use serde::{Deserialize, Serialize};
+
+/// The status monitor program number.
+pub const SM_PROG: u32 = 100024;
+
+/// The status monitor protocol version described here.
+pub const SM_VERS: u32 = 1;
+
+/// The maximum length of a host name, in bytes.
+pub const SM_MAXSTRLEN: u32 = 1024;
+
+/// The size of the `priv` field of [`Mon`] and [`Status`], in bytes.
+pub const SM_PRIV_SIZE: usize = 16;
+
+/// Ask whether a host is monitored; takes [`SmName`], returns
+/// [`SmStatRes`].
+pub const SM_STAT: u32 = 1;
+/// Start monitoring a host; takes [`Mon`], returns [`SmStatRes`].
+pub const SM_MON: u32 = 2;
+/// Stop monitoring a host; takes [`MonId`], returns [`SmStat`].
+pub const SM_UNMON: u32 = 3;
+/// Stop monitoring every host for a caller; takes [`MyId`], returns
+/// [`SmStat`].
+pub const SM_UNMON_ALL: u32 = 4;
+/// Simulate a crash of the local host; `void` to `void`.
+pub const SM_SIMU_CRASH: u32 = 5;
+/// A host restarted; takes [`StatChge`], returns `void`.
+pub const SM_NOTIFY: u32 = 6;
+
+/// `string<SM_MAXSTRLEN>`: a host name.
+pub type SmString = BoundedString<SM_MAXSTRLEN>;
+
+/// `struct sm_name`: the arguments of `SM_STAT`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SmName {
+    pub mon_name: SmString,
+}
+
+/// `struct my_id`: the local procedure to call back when a monitored host
+/// restarts.
+///
+/// ```text
+/// struct my_id {
+///     string my_name<SM_MAXSTRLEN>;
+///     int    my_prog;
+///     int    my_vers;
+///     int    my_proc;
+/// };
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct MyId {
+    pub my_name: SmString,
+    pub my_prog: i32,
+    pub my_vers: i32,
+    pub my_proc: i32,
+}
+
+/// `struct mon_id`: a monitored host and who to tell about it.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct MonId {
+    pub mon_name: SmString,
+    pub my_id: MyId,
+}
+
+/// `struct mon`: the arguments of `SM_MON`.
+///
+/// ```text
+/// struct mon {
+///     mon_id mon_id;
+///     opaque priv[16];
+/// };
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Mon {
+    pub mon_id: MonId,
+    /// Returned unchanged in the callback's [`Status`].
+    #[serde(with = "crate::fixed_opaque")]
+    pub r#priv: [u8; SM_PRIV_SIZE],
+}
+
+/// `struct stat_chge`: the arguments of `SM_NOTIFY`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct StatChge {
+    pub mon_name: SmString,
+    pub state: i32,
+}
+
+/// `struct sm_stat`: the result of `SM_UNMON` and `SM_UNMON_ALL`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SmStat {
+    /// The local host's state number.
+    pub state: i32,
+}
+
+xdr_enum! {
+    /// `enum res`
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum Res {
+        /// `stat_succ`
+        StatSucc = 0,
+        /// `stat_fail`
+        StatFail = 1,
+    }
+}
+
+/// `struct sm_stat_res`: the result of `SM_STAT` and `SM_MON`.
+///
+/// ```text
+/// struct sm_stat_res {
+///     res res_stat;
+///     int state;
+/// };
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SmStatRes {
+    pub res_stat: Res,
+    /// The local host's state number.
+    pub state: i32,
+}
+
+/// `struct status`: the arguments of the callback to [`MyId::my_proc`].
+///
+/// ```text
+/// struct status {
+///     string mon_name<SM_MAXSTRLEN>;
+///     int    state;
+///     opaque priv[16];
+/// };
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Status {
+    /// The host that restarted.
+    pub mon_name: SmString,
+    /// Its new state number.
+    pub state: i32,
+    /// The `priv` field of the [`Mon`] passed to `SM_MON`.
+    #[serde(with = "crate::fixed_opaque")]
+    pub r#priv: [u8; SM_PRIV_SIZE],
+}
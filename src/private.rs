@@ -0,0 +1,99 @@
+//! Support code for this crate's macros and for `xdr-serde-derive`. Not public
+//! API: anything here may change in any release.
+//!
+//! The derive expands each annotated field to one of the wrappers below, so
+//! that the generated impls reach the same code paths as
+//! [`fixed_opaque`](crate::fixed_opaque) and [`bounded`](crate::bounded).
+
+pub use serde;
+
+use crate::bounded::ByteBuf;
+use crate::fixed_opaque::XdrFixedOpaque;
+use alloc::vec::Vec;
+use core::fmt;
+use serde::de::{self, Deserialize, DeserializeSeed, Deserializer, Visitor};
+use serde::ser::{Serialize, Serializer};
+
+/// `#[xdr(fixed)]` on the serializing side.
+pub struct SerFixed<'a, T>(pub &'a T);
+
+impl<T: XdrFixedOpaque> Serialize for SerFixed<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        crate::fixed_opaque::serialize(self.0, serializer)
+    }
+}
+
+/// `#[xdr(fixed)]` on the deserializing side.
+pub struct DeFixed<T>(pub T);
+
+impl<'de, T: XdrFixedOpaque + Deserialize<'de>> Deserialize<'de> for DeFixed<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        crate::fixed_opaque::deserialize(deserializer).map(DeFixed)
+    }
+}
+
+/// `#[xdr(max = N)]` on the serializing side.
+pub struct SerBounded<'a, const N: u32, T: ?Sized>(pub &'a T);
+
+impl<const N: u32, T: Serialize + ?Sized> Serialize for SerBounded<'_, N, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        crate::bounded::serialize::<N, _, _>(self.0, serializer)
+    }
+}
+
+/// `#[xdr(max = N)]` on the deserializing side.
+pub struct DeBounded<const N: u32, T>(pub T);
+
+impl<'de, const N: u32, T: Deserialize<'de>> Deserialize<'de> for DeBounded<N, T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        crate::bounded::deserialize::<N, _, _>(deserializer).map(DeBounded)
+    }
+}
+
+/// `#[xdr(opaque)]` on the serializing side: a byte slice as XDR opaque.
+pub struct SerOpaque<'a>(pub &'a [u8]);
+
+impl Serialize for SerOpaque<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(self.0)
+    }
+}
+
+/// `#[xdr(opaque)]` on the deserializing side.
+pub struct DeOpaque(pub Vec<u8>);
+
+impl<'de> Deserialize<'de> for DeOpaque {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        ByteBuf::deserialize(deserializer).map(|ByteBuf(v)| DeOpaque(v))
+    }
+}
+
+/// Reads a struct field name as the field's index, for `visit_map`. Unknown
+/// names read as `None`, so their values can be skipped.
+pub struct FieldSeed(pub &'static [&'static str]);
+
+impl<'de> DeserializeSeed<'de> for FieldSeed {
+    type Value = Option<usize>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Option<usize>, D::Error> {
+        deserializer.deserialize_identifier(self)
+    }
+}
+
+impl<'de> Visitor<'de> for FieldSeed {
+    type Value = Option<usize>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a field name")
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Option<usize>, E> {
+        Ok(usize::try_from(v).ok().filter(|&i| i < self.0.len()))
+    }
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Option<usize>, E> {
+        Ok(self.0.iter().position(|&name| name == v))
+    }
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Option<usize>, E> {
+        Ok(self.0.iter().position(|&name| name.as_bytes() == v))
+    }
+}
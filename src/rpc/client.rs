@@ -0,0 +1,362 @@
+//! A minimal ONC RPC client. Requires the `std` feature.
+//!
+//! A [`Client`] calls procedures of one version of one program over TCP, with
+//! record marking, or UDP, one message per datagram. Each call gets a fresh
+//! transaction id (XID); replies carrying any other XID are discarded, so
+//! late replies to earlier calls are never mistaken for the current one. Over
+//! UDP an unanswered call is retransmitted with the same XID.
+//!
+//! ```rust,no_run
+//! use xdr_serde::rpc::client::Client;
+//!
+//! // Ask the portmapper for the port of NFSv3 over TCP.
+//! let mut client = Client::udp("127.0.0.1:111", 100000, 2)?;
+//! let port: u32 = client.call(3, &(100003u32, 3u32, 6u32, 0u32))?;
+//! # Ok::<(), xdr_serde::rpc::client::CallError>(())
+//! ```
+//!
+//! A reply other than success is returned as the matching [`CallError`]
+//! variant. A TCP connection whose framing is lost, because a reply timed out
+//! or failed to arrive in full, is closed; later calls on the client fail.
+
+use super::record::{RecordReader, RecordWriter};
+use super::{
+    AuthStat, CallBody, MismatchInfo, MsgBody, OpaqueAuth, RejectedReply, ReplyBody, ReplyData,
+    RpcMsg,
+};
+use crate::error::Error;
+use crate::{from_bytes, from_bytes_partial, to_bytes};
+use alloc::vec::Vec;
+use core::fmt;
+use core::time::Duration;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::io::{self, BufReader, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs, UdpSocket};
+use std::time::{Instant, SystemTime};
+
+/// Default for [`Client::with_timeout`].
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Default for [`Client::with_retries`].
+pub const DEFAULT_RETRIES: u32 = 4;
+
+/// Why a call did not produce results.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CallError {
+    /// Encoding, decoding or I/O failed.
+    Xdr(Error),
+    /// No reply arrived in time.
+    TimedOut,
+    /// The server does not export the program.
+    ProgUnavail,
+    /// The server does not support the version; carries those it does.
+    ProgMismatch(MismatchInfo),
+    /// The program does not support the procedure.
+    ProcUnavail,
+    /// The server could not decode the arguments.
+    GarbageArgs,
+    /// The server hit a system error.
+    SystemErr,
+    /// The server accepted the call with an `accept_stat` outside RFC 5531.
+    UnknownAcceptStat(i32),
+    /// The server rejected the RPC version; carries those it supports.
+    RpcMismatch(MismatchInfo),
+    /// The server rejected the credential or verifier.
+    AuthError(AuthStat),
+}
+
+impl fmt::Display for CallError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CallError::Xdr(e) => write!(f, "{}", e),
+            CallError::TimedOut => write!(f, "RPC call timed out"),
+            CallError::ProgUnavail => write!(f, "program unavailable"),
+            CallError::ProgMismatch(m) => {
+                write!(
+                    f,
+                    "program version mismatch (supported {}-{})",
+                    m.low, m.high
+                )
+            }
+            CallError::ProcUnavail => write!(f, "procedure unavailable"),
+            CallError::GarbageArgs => write!(f, "server could not decode arguments"),
+            CallError::SystemErr => write!(f, "server system error"),
+            CallError::UnknownAcceptStat(stat) => write!(f, "unknown accept_stat {}", stat),
+            CallError::RpcMismatch(m) => {
+                write!(f, "RPC version mismatch (supported {}-{})", m.low, m.high)
+            }
+            CallError::AuthError(stat) => write!(f, "authentication error: {:?}", stat),
+        }
+    }
+}
+
+impl core::error::Error for CallError {}
+
+impl From<Error> for CallError {
+    fn from(e: Error) -> Self {
+        CallError::Xdr(e)
+    }
+}
+
+impl From<io::Error> for CallError {
+    /// Read timeouts become [`CallError::TimedOut`].
+    fn from(e: io::Error) -> Self {
+        if is_timeout(&e) {
+            CallError::TimedOut
+        } else {
+            CallError::Xdr(e.into())
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Transport {
+    /// `None` once the connection has been closed after a failed exchange.
+    Tcp(Option<TcpConnection>),
+    Udp(UdpSocket),
+}
+
+#[derive(Debug)]
+struct TcpConnection {
+    reader: RecordReader<TcpRead>,
+    writer: RecordWriter<TcpStream>,
+    /// Whether the stream is between records in both directions.
+    in_sync: bool,
+}
+
+/// The read half of a TCP connection, noting read timeouts, which
+/// [`RecordReader`] reports as a plain [`Error::Io`].
+#[derive(Debug)]
+struct TcpRead {
+    stream: BufReader<TcpStream>,
+    timed_out: bool,
+}
+
+impl Read for TcpRead {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let result = self.stream.read(buf);
+        if let Err(e) = &result {
+            self.timed_out |= is_timeout(e);
+        }
+        result
+    }
+}
+
+fn is_timeout(e: &io::Error) -> bool {
+    matches!(
+        e.kind(),
+        io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+    )
+}
+
+/// Calls procedures of one program version on a server.
+#[derive(Debug)]
+pub struct Client {
+    transport: Transport,
+    prog: u32,
+    vers: u32,
+    cred: OpaqueAuth,
+    next_xid: u32,
+    timeout: Duration,
+    retries: u32,
+}
+
+impl Client {
+    /// Connect over TCP to version `vers` of program `prog` at `addr`.
+    pub fn tcp<A: ToSocketAddrs>(addr: A, prog: u32, vers: u32) -> Result<Self, CallError> {
+        let stream = TcpStream::connect(addr)?;
+        let reader = RecordReader::new(TcpRead {
+            stream: BufReader::new(stream.try_clone()?),
+            timed_out: false,
+        });
+        Ok(Client::new(
+            Transport::Tcp(Some(TcpConnection {
+                reader,
+                writer: RecordWriter::new(stream),
+                in_sync: true,
+            })),
+            prog,
+            vers,
+        ))
+    }
+
+    /// Call version `vers` of program `prog` at `addr` over UDP.
+    pub fn udp<A: ToSocketAddrs>(addr: A, prog: u32, vers: u32) -> Result<Self, CallError> {
+        let addr = addr
+            .to_socket_addrs()?
+            .next()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "no address"))?;
+        let local = if addr.is_ipv4() {
+            "0.0.0.0:0"
+        } else {
+            "[::]:0"
+        };
+        let socket = UdpSocket::bind(local)?;
+        socket.connect(addr)?;
+        Ok(Client::new(Transport::Udp(socket), prog, vers))
+    }
+
+    fn new(transport: Transport, prog: u32, vers: u32) -> Self {
+        // Start from a time-derived XID so that a restarted client does not
+        // reuse the XIDs of its previous run.
+        let seed = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map_or(0, |d| d.subsec_nanos() ^ d.as_secs() as u32);
+        Client {
+            transport,
+            prog,
+            vers,
+            cred: OpaqueAuth::none(),
+            next_xid: seed,
+            timeout: DEFAULT_TIMEOUT,
+            retries: DEFAULT_RETRIES,
+        }
+    }
+
+    /// Send `cred` as the credential of every call, instead of `AUTH_NONE`.
+    pub fn with_credential(mut self, cred: OpaqueAuth) -> Self {
+        self.cred = cred;
+        self
+    }
+
+    /// Wait `timeout` for each reply. Over UDP this is the interval between
+    /// retransmissions; over TCP it is the socket read timeout, and a call
+    /// that exceeds it fails with [`CallError::TimedOut`] and closes the
+    /// connection.
+    ///
+    /// # Panics
+    ///
+    /// If `timeout` is zero, which sockets do not accept as a timeout.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        assert!(!timeout.is_zero(), "timeout must be greater than zero");
+        self.timeout = timeout;
+        self
+    }
+
+    /// Retransmit an unanswered UDP call up to `retries` times before
+    /// failing with [`CallError::TimedOut`]. Ignored over TCP.
+    pub fn with_retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Call procedure `proc` with `args`, decoding the results as `R`.
+    pub fn call<A: Serialize, R: DeserializeOwned>(
+        &mut self,
+        proc: u32,
+        args: &A,
+    ) -> Result<R, CallError> {
+        let xid = self.next_xid;
+        self.next_xid = self.next_xid.wrapping_add(1);
+        let call = RpcMsg {
+            xid,
+            body: MsgBody::Call(CallBody {
+                cred: self.cred.clone(),
+                ..CallBody::new(self.prog, self.vers, proc)
+            }),
+        };
+        let mut request = to_bytes(&call)?;
+        request.extend(to_bytes(args)?);
+
+        let reply = match &mut self.transport {
+            Transport::Tcp(connection) => {
+                let conn = connection.as_mut().ok_or_else(|| {
+                    io::Error::new(io::ErrorKind::NotConnected, "connection closed")
+                })?;
+                let reply = conn.exchange(&request, xid, self.timeout);
+                if !conn.in_sync {
+                    // Dropping both halves closes the socket.
+                    *connection = None;
+                }
+                reply?
+            }
+            Transport::Udp(socket) => udp_reply(socket, &request, xid, self.timeout, self.retries)?,
+        };
+        let (header, results) = from_bytes_partial::<RpcMsg>(&reply)?;
+        let MsgBody::Reply(body) = header.body else {
+            unreachable!("checked by is_reply_to")
+        };
+        match body {
+            ReplyBody::Accepted(accepted) => match accepted.reply_data {
+                ReplyData::Success => Ok(from_bytes(results)?),
+                ReplyData::ProgUnavail => Err(CallError::ProgUnavail),
+                ReplyData::ProgMismatch(m) => Err(CallError::ProgMismatch(m)),
+                ReplyData::ProcUnavail => Err(CallError::ProcUnavail),
+                ReplyData::GarbageArgs => Err(CallError::GarbageArgs),
+                ReplyData::SystemErr => Err(CallError::SystemErr),
+                ReplyData::Other(stat) => Err(CallError::UnknownAcceptStat(stat)),
+            },
+            ReplyBody::Denied(RejectedReply::RpcMismatch(m)) => Err(CallError::RpcMismatch(m)),
+            ReplyBody::Denied(RejectedReply::AuthError(stat)) => Err(CallError::AuthError(stat)),
+        }
+    }
+}
+
+/// Whether `message` is a reply with transaction id `xid`. On a stream a
+/// message whose header does not decode is an error rather than someone
+/// else's reply.
+fn is_reply_to(message: &[u8], xid: u32) -> Result<bool, CallError> {
+    let (header, _) = from_bytes_partial::<RpcMsg>(message)?;
+    Ok(header.xid == xid && matches!(header.body, MsgBody::Reply(_)))
+}
+
+impl TcpConnection {
+    /// Send `request` as one record, then read records until the reply to
+    /// `xid` arrives. `in_sync` is left false if the exchange stopped partway
+    /// through a record.
+    fn exchange(
+        &mut self,
+        request: &[u8],
+        xid: u32,
+        timeout: Duration,
+    ) -> Result<Vec<u8>, CallError> {
+        self.in_sync = false;
+        self.writer.write_all(request)?;
+        self.writer.end_record()?;
+        let read = self.reader.get_mut();
+        read.stream.get_ref().set_read_timeout(Some(timeout))?;
+        read.timed_out = false;
+        loop {
+            self.in_sync = false;
+            let record = match self.reader.read_record() {
+                Ok(Some(record)) => record,
+                Ok(None) => return Err(Error::UnexpectedEof.into()),
+                Err(_) if self.reader.get_ref().timed_out => return Err(CallError::TimedOut),
+                Err(e) => return Err(e.into()),
+            };
+            self.in_sync = true;
+            if is_reply_to(&record, xid)? {
+                return Ok(record);
+            }
+        }
+    }
+}
+
+/// Send `request`, retransmitting it until the reply to `xid` arrives.
+fn udp_reply(
+    socket: &UdpSocket,
+    request: &[u8],
+    xid: u32,
+    timeout: Duration,
+    retries: u32,
+) -> Result<Vec<u8>, CallError> {
+    let mut buf = alloc::vec![0; super::server::MAX_DATAGRAM_SIZE];
+    for _ in 0..=retries {
+        socket.send(request)?;
+        let deadline = Instant::now() + timeout;
+        while let Some(remaining) = deadline.checked_duration_since(Instant::now()) {
+            socket.set_read_timeout(Some(remaining.max(Duration::from_millis(1))))?;
+            match socket.recv(&mut buf) {
+                // A datagram whose header does not decode is not our reply.
+                Ok(n) if is_reply_to(&buf[..n], xid).unwrap_or(false) => {
+                    return Ok(buf[..n].to_vec());
+                }
+                Ok(_) => {}
+                Err(e) if is_timeout(&e) => break,
+                Err(e) => return Err(e.into()),
+            }
+        }
+    }
+    Err(CallError::TimedOut)
+}
@@ -0,0 +1,171 @@
+//! A [`tokio_util::codec`] for record-marked XDR messages. Requires the
+//! `tokio` feature.
+//!
+//! [`RecordCodec<T>`] frames messages exactly as [`record`](super::record)
+//! does, for use with `Framed`, `FramedRead` and `FramedWrite`. Each complete
+//! record is decoded into a `T` with the slice [`Deserializer`]; any
+//! `Serialize` value can be encoded as a record.
+//!
+//! ```rust
+//! use bytes::BytesMut;
+//! use tokio_util::codec::{Decoder, Encoder};
+//! use xdr_serde::rpc::codec::RecordCodec;
+//! use xdr_serde::rpc::{CallBody, MsgBody, RpcMsg};
+//!
+//! let call = RpcMsg {
+//!     xid: 1,
+//!     body: MsgBody::Call(CallBody::new(100003, 3, 0)),
+//! };
+//! let mut codec = RecordCodec::<RpcMsg>::new();
+//! let mut buf = BytesMut::new();
+//! codec.encode(&call, &mut buf).unwrap();
+//! assert_eq!(buf[..4], [0x80, 0, 0, 40]);
+//!
+//! assert_eq!(codec.decode(&mut buf).unwrap(), Some(call));
+//! assert!(buf.is_empty());
+//! ```
+//!
+//! Decoding reads `T` from the start of the record and ignores anything after
+//! it, as [`from_bytes`](crate::from_bytes) does.
+
+use super::record::{
+    DEFAULT_MAX_FRAGMENT_SIZE, DEFAULT_MAX_RECORD_SIZE, LAST_FRAGMENT, MAX_FRAGMENT_LEN,
+};
+use crate::de::{Deserializer, length_overflow};
+use crate::error::{Error, Result};
+use crate::limits::DecodeLimits;
+use alloc::vec::Vec;
+use bytes::{Buf, BufMut, BytesMut};
+use core::marker::PhantomData;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use tokio_util::codec::{Decoder, Encoder};
+
+/// Decodes record-marked messages into `T` and encodes values as records.
+///
+/// Records whose total length exceeds the maximum record size fail with
+/// [`Error::LengthOverflow`] as soon as the offending fragment header
+/// arrives, before its data is buffered.
+#[derive(Debug)]
+pub struct RecordCodec<T> {
+    max_record_size: usize,
+    max_fragment_size: usize,
+    limits: DecodeLimits,
+    /// Data of the current record's completed fragments.
+    record: Vec<u8>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> RecordCodec<T> {
+    /// A codec allowing records of up to [`DEFAULT_MAX_RECORD_SIZE`] and
+    /// sending fragments of up to [`DEFAULT_MAX_FRAGMENT_SIZE`].
+    pub fn new() -> Self {
+        RecordCodec {
+            max_record_size: DEFAULT_MAX_RECORD_SIZE,
+            max_fragment_size: DEFAULT_MAX_FRAGMENT_SIZE,
+            limits: DecodeLimits::UNLIMITED,
+            record: Vec::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Reject records longer than `max` bytes, headers excluded.
+    pub fn with_max_record_size(mut self, max: usize) -> Self {
+        self.max_record_size = max;
+        self
+    }
+
+    /// Send fragments of at most `max` bytes, headers excluded.
+    ///
+    /// # Panics
+    ///
+    /// If `max` is zero or greater than [`MAX_FRAGMENT_LEN`].
+    pub fn with_max_fragment_size(mut self, max: usize) -> Self {
+        assert!(
+            max > 0 && max <= MAX_FRAGMENT_LEN as usize,
+            "fragment size must be between 1 and 2^31 - 1"
+        );
+        self.max_fragment_size = max;
+        self
+    }
+
+    /// Enforce `limits` when decoding each record into `T`.
+    pub fn with_limits(mut self, limits: DecodeLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Move the next complete record out of `src`, or return `None` if more
+    /// data is needed.
+    fn decode_record(&mut self, src: &mut BytesMut) -> Result<Option<Vec<u8>>> {
+        while src.len() >= 4 {
+            let header = u32::from_be_bytes([src[0], src[1], src[2], src[3]]);
+            let len = (header & MAX_FRAGMENT_LEN) as usize;
+            let total = self.record.len().saturating_add(len);
+            if total > self.max_record_size {
+                return Err(length_overflow(self.max_record_size, total));
+            }
+            if src.len() < 4 + len {
+                src.reserve(4 + len - src.len());
+                return Ok(None);
+            }
+            src.advance(4);
+            self.record.extend_from_slice(&src.split_to(len));
+            if header & LAST_FRAGMENT != 0 {
+                return Ok(Some(core::mem::take(&mut self.record)));
+            }
+        }
+        Ok(None)
+    }
+}
+
+impl<T> Default for RecordCodec<T> {
+    fn default() -> Self {
+        RecordCodec::new()
+    }
+}
+
+impl<T: DeserializeOwned> Decoder for RecordCodec<T> {
+    type Item = T;
+    type Error = Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<T>> {
+        let Some(record) = self.decode_record(src)? else {
+            return Ok(None);
+        };
+        let mut de = Deserializer::new(&record).with_limits(self.limits);
+        T::deserialize(&mut de).map(Some)
+    }
+
+    /// Fails with [`Error::UnexpectedEof`] if the stream ends partway through
+    /// a record.
+    fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<T>> {
+        match self.decode(src)? {
+            Some(value) => Ok(Some(value)),
+            None if src.is_empty() && self.record.is_empty() => Ok(None),
+            None => Err(Error::UnexpectedEof),
+        }
+    }
+}
+
+impl<T, I: Serialize> Encoder<I> for RecordCodec<T> {
+    type Error = Error;
+
+    fn encode(&mut self, item: I, dst: &mut BytesMut) -> Result<()> {
+        let data = crate::to_bytes(&item)?;
+        let mut fragments = data.chunks(self.max_fragment_size).peekable();
+        if fragments.peek().is_none() {
+            dst.put_u32(LAST_FRAGMENT);
+        }
+        while let Some(fragment) = fragments.next() {
+            let mut header = fragment.len() as u32;
+            if fragments.peek().is_none() {
+                header |= LAST_FRAGMENT;
+            }
+            dst.reserve(4 + fragment.len());
+            dst.put_u32(header);
+            dst.put_slice(fragment);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,484 @@
+//! ONC RPC message types (RFC 5531 §9).
+//!
+//! The types mirror the XDR definitions in the RFC, with the discriminants
+//! it assigns. An RPC message on the wire is an [`RpcMsg`] followed directly
+//! by the procedure's arguments (for a call) or results (for a successful
+//! reply); those are encoded separately:
+//!
+//! ```rust
+//! use xdr_serde::rpc::{CallBody, MsgBody, OpaqueAuth, RpcMsg};
+//! use xdr_serde::{from_bytes_partial, to_bytes};
+//!
+//! // NFSv3 GETATTR (program 100003, version 3, procedure 1).
+//! let call = RpcMsg {
+//!     xid: 0x1234,
+//!     body: MsgBody::Call(CallBody::new(100003, 3, 1)),
+//! };
+//! let mut bytes = to_bytes(&call).unwrap();
+//! assert_eq!(bytes.len(), 40);
+//! bytes.extend(to_bytes(&(7u32, 8u32)).unwrap()); // the arguments
+//!
+//! let (decoded, args) = from_bytes_partial::<RpcMsg>(&bytes).unwrap();
+//! assert_eq!(decoded, call);
+//! assert_eq!(args, [0, 0, 0, 7, 0, 0, 0, 8]);
+//! ```
+//!
+//! Over stream transports messages are framed with record marking; see the
+//! [`record`] module, and `codec` (with the `tokio` feature) for a
+//! `tokio_util` codec.
+
+use crate::bounded::{BoundedBytes, BoundedString, BoundedVec};
+use crate::error::{Error, Result};
+use crate::xdr_enum;
+use crate::{from_bytes, to_bytes};
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "std")]
+pub mod client;
+#[cfg(feature = "tokio")]
+pub mod codec;
+#[cfg(feature = "std")]
+pub mod record;
+pub mod rpcbind;
+#[cfg(feature = "std")]
+pub mod server;
+
+/// The RPC protocol version carried in every call (`rpcvers`).
+pub const RPC_VERSION: u32 = 2;
+
+/// The maximum size of an [`OpaqueAuth`] body, in bytes.
+pub const MAX_AUTH_BYTES: u32 = 400;
+
+/// The maximum length of [`AuthSysParms::machinename`], in bytes.
+pub const MAX_MACHINE_NAME: u32 = 255;
+
+/// The maximum number of supplementary groups in [`AuthSysParms::gids`].
+pub const MAX_AUTH_SYS_GIDS: u32 = 16;
+
+xdr_enum! {
+    /// `enum auth_flavor`: the kind of credential or verifier.
+    ///
+    /// Flavor numbers are assigned by IANA, so values not listed here decode
+    /// as [`AuthFlavor::Other`].
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum AuthFlavor {
+        /// `AUTH_NONE`
+        None = 0,
+        /// `AUTH_SYS`
+        Sys = 1,
+        /// `AUTH_SHORT`
+        Short = 2,
+        /// `AUTH_DH`
+        Dh = 3,
+        /// `RPCSEC_GSS`
+        RpcsecGss = 6,
+        _ =>
+            /// Any other flavor number.
+            Other(i32),
+    }
+}
+
+/// `struct opaque_auth`: a credential or verifier.
+///
+/// ```text
+/// struct opaque_auth {
+///     auth_flavor flavor;
+///     opaque body<400>;
+/// };
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OpaqueAuth {
+    pub flavor: AuthFlavor,
+    pub body: BoundedBytes<MAX_AUTH_BYTES>,
+}
+
+impl OpaqueAuth {
+    /// An `AUTH_NONE` credential or verifier with an empty body.
+    pub fn none() -> Self {
+        OpaqueAuth {
+            flavor: AuthFlavor::None,
+            body: BoundedBytes::default(),
+        }
+    }
+
+    /// A credential or verifier of `flavor`.
+    ///
+    /// Fails with [`Error::LengthOverflow`] if `body` is longer than [`MAX_AUTH_BYTES`].
+    pub fn new(flavor: AuthFlavor, body: Vec<u8>) -> Result<Self> {
+        Ok(OpaqueAuth {
+            flavor,
+            body: BoundedBytes::new(body)?,
+        })
+    }
+}
+
+impl Default for OpaqueAuth {
+    fn default() -> Self {
+        OpaqueAuth::none()
+    }
+}
+
+/// `struct authsys_parms`: the body of an `AUTH_SYS` (formerly `AUTH_UNIX`)
+/// credential (RFC 5531 appendix A).
+///
+/// ```text
+/// struct authsys_parms {
+///     unsigned int stamp;
+///     string machinename<255>;
+///     unsigned int uid;
+///     unsigned int gid;
+///     unsigned int gids<16>;
+/// };
+/// ```
+///
+/// ```rust
+/// use xdr_serde::rpc::{AuthFlavor, AuthSysParms};
+///
+/// let parms = AuthSysParms::new(0, "client", 1000, 1000, &[4, 27]).unwrap();
+/// let cred = parms.to_opaque_auth().unwrap();
+/// assert_eq!(cred.flavor, AuthFlavor::Sys);
+/// assert_eq!(cred.body.len(), 36);
+/// assert_eq!(AuthSysParms::from_opaque_auth(&cred).unwrap(), parms);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuthSysParms {
+    /// An arbitrary id chosen by the caller's machine.
+    pub stamp: u32,
+    /// The caller's host name.
+    pub machinename: BoundedString<MAX_MACHINE_NAME>,
+    /// The caller's effective user id.
+    pub uid: u32,
+    /// The caller's effective group id.
+    pub gid: u32,
+    /// The groups the caller is a member of.
+    pub gids: BoundedVec<u32, MAX_AUTH_SYS_GIDS>,
+}
+
+impl AuthSysParms {
+    /// Build the parameters, checking the bounds on `machinename` and `gids`.
+    ///
+    /// Fails with [`Error::LengthOverflow`] if `machinename` is longer than
+    /// [`MAX_MACHINE_NAME`] bytes or there are more than
+    /// [`MAX_AUTH_SYS_GIDS`] groups.
+    pub fn new(stamp: u32, machinename: &str, uid: u32, gid: u32, gids: &[u32]) -> Result<Self> {
+        Ok(AuthSysParms {
+            stamp,
+            machinename: BoundedString::try_from(machinename)?,
+            uid,
+            gid,
+            gids: BoundedVec::new(gids.to_vec())?,
+        })
+    }
+
+    /// Encode as an `AUTH_SYS` credential.
+    pub fn to_opaque_auth(&self) -> Result<OpaqueAuth> {
+        OpaqueAuth::new(AuthFlavor::Sys, to_bytes(self)?)
+    }
+
+    /// Decode the body of an `AUTH_SYS` credential.
+    ///
+    /// Fails with [`Error::InvalidDiscriminant`] carrying the flavor if `auth`
+    /// is not `AUTH_SYS`, and with the usual decoding errors if the body is
+    /// malformed.
+    pub fn from_opaque_auth(auth: &OpaqueAuth) -> Result<Self> {
+        if auth.flavor != AuthFlavor::Sys {
+            return Err(Error::InvalidDiscriminant(auth.flavor.discriminant()));
+        }
+        from_bytes(auth.body.as_bytes())
+    }
+}
+
+xdr_enum! {
+    /// `enum msg_type`
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum MsgType {
+        Call = 0,
+        Reply = 1,
+    }
+}
+
+xdr_enum! {
+    /// `enum reply_stat`
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum ReplyStat {
+        MsgAccepted = 0,
+        MsgDenied = 1,
+    }
+}
+
+xdr_enum! {
+    /// `enum accept_stat`: the outcome of a call the server accepted.
+    ///
+    /// Values outside RFC 5531 decode as `Other`, so that a reply carrying one
+    /// can still be matched to its call.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum AcceptStat {
+        /// The call executed successfully.
+        Success = 0,
+        /// The remote program is not exported.
+        ProgUnavail = 1,
+        /// The program does not support the requested version.
+        ProgMismatch = 2,
+        /// The program does not support the requested procedure.
+        ProcUnavail = 3,
+        /// The arguments could not be decoded.
+        GarbageArgs = 4,
+        /// A system error, such as a memory allocation failure.
+        SystemErr = 5,
+        _ => Other(i32),
+    }
+}
+
+xdr_enum! {
+    /// `enum reject_stat`: why a call was rejected.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum RejectStat {
+        /// The RPC version number was not 2.
+        RpcMismatch = 0,
+        /// The caller could not be authenticated.
+        AuthError = 1,
+    }
+}
+
+xdr_enum! {
+    /// `enum auth_stat`: why authentication failed.
+    ///
+    /// Values not listed here decode as `Other`, so that a denied reply
+    /// carrying one still reads as an authentication error.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum AuthStat {
+        /// Success.
+        Ok = 0,
+        /// Bad credential (seal broken).
+        BadCred = 1,
+        /// The client must begin a new session.
+        RejectedCred = 2,
+        /// Bad verifier (seal broken).
+        BadVerf = 3,
+        /// The verifier expired or was replayed.
+        RejectedVerf = 4,
+        /// Rejected for security reasons.
+        TooWeak = 5,
+        /// Bogus response verifier.
+        InvalidResp = 6,
+        /// Reason unknown.
+        Failed = 7,
+        /// Kerberos generic error.
+        KerbGeneric = 8,
+        /// The credential's time expired.
+        TimeExpire = 9,
+        /// Problem with the ticket file.
+        TktFile = 10,
+        /// Cannot decode the authenticator.
+        Decode = 11,
+        /// Wrong network address in the ticket.
+        NetAddr = 12,
+        /// No credentials for the user (RPCSEC_GSS).
+        RpcsecGssCredProblem = 13,
+        /// Problem with the context (RPCSEC_GSS).
+        RpcsecGssCtxProblem = 14,
+        _ => Other(i32),
+    }
+}
+
+/// The lowest and highest versions supported, sent with `PROG_MISMATCH` and
+/// `RPC_MISMATCH`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct MismatchInfo {
+    pub low: u32,
+    pub high: u32,
+}
+
+/// `struct rpc_msg`: the header of every call and reply.
+///
+/// ```text
+/// struct rpc_msg {
+///     unsigned int xid;
+///     union switch (msg_type mtype) {
+///     case CALL:
+///         call_body cbody;
+///     case REPLY:
+///         reply_body rbody;
+///     } body;
+/// };
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RpcMsg {
+    /// Transaction id, echoed in the reply.
+    pub xid: u32,
+    pub body: MsgBody,
+}
+
+xdr_enum! {
+    /// The `body` union of [`RpcMsg`], switched on [`MsgType`].
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum MsgBody {
+        Call(CallBody) = 0,
+        Reply(ReplyBody) = 1,
+    }
+}
+
+impl MsgBody {
+    /// The `mtype` discriminant.
+    pub fn msg_type(&self) -> MsgType {
+        match self {
+            MsgBody::Call(_) => MsgType::Call,
+            MsgBody::Reply(_) => MsgType::Reply,
+        }
+    }
+}
+
+/// `struct call_body`. The procedure's arguments follow it on the wire.
+///
+/// ```text
+/// struct call_body {
+///     unsigned int rpcvers;       /* must be equal to two (2) */
+///     unsigned int prog;
+///     unsigned int vers;
+///     unsigned int proc;
+///     opaque_auth  cred;
+///     opaque_auth  verf;
+///     /* procedure-specific parameters start here */
+/// };
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CallBody {
+    pub rpcvers: u32,
+    pub prog: u32,
+    pub vers: u32,
+    pub proc: u32,
+    pub cred: OpaqueAuth,
+    pub verf: OpaqueAuth,
+}
+
+impl CallBody {
+    /// A call to `prog`/`vers`/`proc` with RPC version 2 and `AUTH_NONE`
+    /// credential and verifier.
+    pub fn new(prog: u32, vers: u32, proc: u32) -> Self {
+        CallBody {
+            rpcvers: RPC_VERSION,
+            prog,
+            vers,
+            proc,
+            cred: OpaqueAuth::none(),
+            verf: OpaqueAuth::none(),
+        }
+    }
+}
+
+xdr_enum! {
+    /// `union reply_body`, switched on [`ReplyStat`].
+    ///
+    /// ```text
+    /// union reply_body switch (reply_stat stat) {
+    /// case MSG_ACCEPTED:
+    ///     accepted_reply areply;
+    /// case MSG_DENIED:
+    ///     rejected_reply rreply;
+    /// } reply;
+    /// ```
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum ReplyBody {
+        Accepted(AcceptedReply) = 0,
+        Denied(RejectedReply) = 1,
+    }
+}
+
+impl ReplyBody {
+    /// The `stat` discriminant.
+    pub fn stat(&self) -> ReplyStat {
+        match self {
+            ReplyBody::Accepted(_) => ReplyStat::MsgAccepted,
+            ReplyBody::Denied(_) => ReplyStat::MsgDenied,
+        }
+    }
+}
+
+/// `struct accepted_reply`. On [`ReplyData::Success`] the procedure's
+/// results follow it on the wire.
+///
+/// ```text
+/// struct accepted_reply {
+///     opaque_auth verf;
+///     union switch (accept_stat stat) {
+///     case SUCCESS:
+///         opaque results[0];
+///         /* procedure-specific results start here */
+///     case PROG_MISMATCH:
+///         struct {
+///             unsigned int low;
+///             unsigned int high;
+///         } mismatch_info;
+///     default:
+///         void;
+///     } reply_data;
+/// };
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AcceptedReply {
+    pub verf: OpaqueAuth,
+    pub reply_data: ReplyData,
+}
+
+xdr_enum! {
+    /// The `reply_data` union of [`AcceptedReply`], switched on
+    /// [`AcceptStat`]. Its `default: void` arm is `Other`, carrying the
+    /// unknown `stat`.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum ReplyData {
+        Success = 0,
+        ProgUnavail = 1,
+        ProgMismatch(MismatchInfo) = 2,
+        ProcUnavail = 3,
+        GarbageArgs = 4,
+        SystemErr = 5,
+        _ => Other(i32),
+    }
+}
+
+impl ReplyData {
+    /// The `stat` discriminant.
+    pub fn stat(&self) -> AcceptStat {
+        match self {
+            ReplyData::Success => AcceptStat::Success,
+            ReplyData::ProgUnavail => AcceptStat::ProgUnavail,
+            ReplyData::ProgMismatch(_) => AcceptStat::ProgMismatch,
+            ReplyData::ProcUnavail => AcceptStat::ProcUnavail,
+            ReplyData::GarbageArgs => AcceptStat::GarbageArgs,
+            ReplyData::SystemErr => AcceptStat::SystemErr,
+            ReplyData::Other(stat) => AcceptStat::Other(*stat),
+        }
+    }
+}
+
+xdr_enum! {
+    /// `union rejected_reply`, switched on [`RejectStat`].
+    ///
+    /// ```text
+    /// union rejected_reply switch (reject_stat stat) {
+    /// case RPC_MISMATCH:
+    ///     struct {
+    ///         unsigned int low;
+    ///         unsigned int high;
+    ///     } mismatch_info;
+    /// case AUTH_ERROR:
+    ///     auth_stat stat;
+    /// };
+    /// ```
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum RejectedReply {
+        RpcMismatch(MismatchInfo) = 0,
+        AuthError(AuthStat) = 1,
+    }
+}
+
+impl RejectedReply {
+    /// The `stat` discriminant.
+    pub fn stat(&self) -> RejectStat {
+        match self {
+            RejectedReply::RpcMismatch(_) => RejectStat::RpcMismatch,
+            RejectedReply::AuthError(_) => RejectStat::AuthError,
+        }
+    }
+}
@@ -0,0 +1,288 @@
+//! Record marking for RPC over stream transports (RFC 5531 §11).
+//!
+//! On TCP each RPC message is sent as a *record*: one or more fragments, each
+//! preceded by a 4-byte big-endian header. The low 31 bits of the header give
+//! the fragment length and the high bit marks the record's last fragment.
+//!
+//! [`RecordReader`] reassembles fragments and presents the current record as
+//! a plain [`Read`], so the header and the arguments can be decoded with
+//! [`from_reader`](crate::from_reader) one after the other. [`RecordWriter`]
+//! is a [`Write`] that splits what it is given into fragments:
+//!
+//! ```rust
+//! use xdr_serde::from_reader;
+//! use xdr_serde::rpc::record::{RecordReader, RecordWriter};
+//! use xdr_serde::rpc::{CallBody, MsgBody, RpcMsg};
+//!
+//! let call = RpcMsg {
+//!     xid: 1,
+//!     body: MsgBody::Call(CallBody::new(100003, 3, 0)),
+//! };
+//! let mut writer = RecordWriter::new(Vec::new()).with_max_fragment_size(16);
+//! writer.write_record(&(&call, 42u32)).unwrap();
+//! let stream = writer.into_inner();
+//! assert_eq!(stream.len(), 44 + 3 * 4); // three fragments
+//!
+//! let mut reader = RecordReader::new(&stream[..]);
+//! assert!(reader.next_record().unwrap());
+//! assert_eq!(from_reader::<_, RpcMsg>(&mut reader).unwrap(), call);
+//! assert_eq!(from_reader::<_, u32>(&mut reader).unwrap(), 42);
+//! assert!(!reader.next_record().unwrap()); // clean end of stream
+//! ```
+
+use crate::de::length_overflow;
+use crate::error::{Error, Result};
+use alloc::vec::Vec;
+use serde::Serialize;
+use std::io::{self, Read, Write};
+
+/// The header bit marking the last fragment of a record.
+pub const LAST_FRAGMENT: u32 = 0x8000_0000;
+
+/// The largest fragment length a header can express.
+pub const MAX_FRAGMENT_LEN: u32 = !LAST_FRAGMENT;
+
+/// Default for [`RecordReader::with_max_record_size`].
+pub const DEFAULT_MAX_RECORD_SIZE: usize = 4 * 1024 * 1024;
+
+/// Default for [`RecordWriter::with_max_fragment_size`].
+pub const DEFAULT_MAX_FRAGMENT_SIZE: usize = 64 * 1024;
+
+/// Reads record-marked RPC messages from a byte stream.
+///
+/// Call [`next_record`](RecordReader::next_record) to start each record, then
+/// read its data through the [`Read`] impl, which returns `Ok(0)` once the
+/// record is exhausted. Records whose total length exceeds the maximum fail
+/// with [`Error::LengthOverflow`] before their data is read.
+#[derive(Debug)]
+pub struct RecordReader<R: Read> {
+    reader: R,
+    max_record_size: usize,
+    /// Whether a record has been started and not yet read to the end.
+    in_record: bool,
+    /// Unread bytes in the current fragment.
+    remaining: u32,
+    /// Whether the current fragment is the record's last.
+    last: bool,
+    /// Total length of the fragments of the current record seen so far.
+    record_len: usize,
+}
+
+impl<R: Read> RecordReader<R> {
+    /// Wrap `reader`, allowing records of up to [`DEFAULT_MAX_RECORD_SIZE`].
+    pub fn new(reader: R) -> Self {
+        RecordReader {
+            reader,
+            max_record_size: DEFAULT_MAX_RECORD_SIZE,
+            in_record: false,
+            remaining: 0,
+            last: false,
+            record_len: 0,
+        }
+    }
+
+    /// Reject records longer than `max` bytes, headers excluded.
+    pub fn with_max_record_size(mut self, max: usize) -> Self {
+        self.max_record_size = max;
+        self
+    }
+
+    /// Skip whatever is left of the current record, then start the next one.
+    ///
+    /// Returns `false` if the stream ends cleanly before the next record.
+    /// A stream that ends partway through a record fails with
+    /// [`Error::UnexpectedEof`].
+    pub fn next_record(&mut self) -> Result<bool> {
+        if self.in_record {
+            io::copy(self, &mut io::sink())?;
+        }
+        self.record_len = 0;
+        self.in_record = self.read_header(true)?;
+        Ok(self.in_record)
+    }
+
+    /// Read the next record in full, or `None` at a clean end of stream.
+    pub fn read_record(&mut self) -> Result<Option<Vec<u8>>> {
+        if !self.next_record()? {
+            return Ok(None);
+        }
+        let mut buf = Vec::new();
+        self.read_to_end(&mut buf)?;
+        Ok(Some(buf))
+    }
+
+    /// Consume the reader, returning the underlying stream.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// A reference to the underlying stream.
+    pub fn get_ref(&self) -> &R {
+        &self.reader
+    }
+
+    /// A mutable reference to the underlying stream. Reading from it directly
+    /// desynchronizes the record framing.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.reader
+    }
+
+    /// Read a fragment header. At the start of a record a clean end of stream
+    /// returns `false`; anywhere else it is an error.
+    fn read_header(&mut self, record_start: bool) -> Result<bool> {
+        let mut header = [0u8; 4];
+        let mut filled = 0;
+        while filled < header.len() {
+            match self.reader.read(&mut header[filled..]) {
+                Ok(0) if filled == 0 && record_start => return Ok(false),
+                Ok(0) => return Err(Error::UnexpectedEof),
+                Ok(n) => filled += n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+        let header = u32::from_be_bytes(header);
+        let len = header & MAX_FRAGMENT_LEN;
+        let total = self.record_len.saturating_add(len as usize);
+        if total > self.max_record_size {
+            return Err(length_overflow(self.max_record_size, total));
+        }
+        self.record_len = total;
+        self.remaining = len;
+        self.last = header & LAST_FRAGMENT != 0;
+        Ok(true)
+    }
+}
+
+impl<R: Read> Read for RecordReader<R> {
+    /// Read data from the current record, continuing across fragment
+    /// boundaries. Returns `Ok(0)` at the end of the record, or if no record
+    /// has been started.
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        while self.in_record {
+            if self.remaining > 0 {
+                let n = buf.len().min(self.remaining as usize);
+                let got = self.reader.read(&mut buf[..n])?;
+                if got == 0 {
+                    return Err(Error::UnexpectedEof.into());
+                }
+                self.remaining -= got as u32;
+                return Ok(got);
+            }
+            if self.last {
+                self.in_record = false;
+            } else {
+                self.read_header(false)?;
+            }
+        }
+        Ok(0)
+    }
+}
+
+/// Writes record-marked RPC messages to a byte stream.
+///
+/// Bytes written through the [`Write`] impl are buffered and sent as
+/// fragments of at most the maximum fragment size;
+/// [`end_record`](RecordWriter::end_record) sends the final fragment of the
+/// record. [`flush`](Write::flush) does not end the record.
+#[derive(Debug)]
+pub struct RecordWriter<W: Write> {
+    writer: W,
+    max_fragment_size: usize,
+    buf: Vec<u8>,
+}
+
+impl<W: Write> RecordWriter<W> {
+    /// Wrap `writer`, sending fragments of up to
+    /// [`DEFAULT_MAX_FRAGMENT_SIZE`] bytes.
+    pub fn new(writer: W) -> Self {
+        RecordWriter {
+            writer,
+            max_fragment_size: DEFAULT_MAX_FRAGMENT_SIZE,
+            buf: Vec::new(),
+        }
+    }
+
+    /// Send fragments of at most `max` bytes, headers excluded.
+    ///
+    /// # Panics
+    ///
+    /// If `max` is zero or greater than [`MAX_FRAGMENT_LEN`].
+    pub fn with_max_fragment_size(mut self, max: usize) -> Self {
+        assert!(
+            max > 0 && max <= MAX_FRAGMENT_LEN as usize,
+            "fragment size must be between 1 and 2^31 - 1"
+        );
+        self.max_fragment_size = max;
+        self
+    }
+
+    /// Serialize `value` as the whole of one record.
+    ///
+    /// Anything already written to the current record is sent ahead of it.
+    pub fn write_record<T: Serialize>(&mut self, value: &T) -> Result<()> {
+        crate::to_writer(&mut *self, value)?;
+        self.end_record()
+    }
+
+    /// Send the buffered data as the last fragment of the current record and
+    /// flush the underlying stream. With nothing buffered this sends an empty
+    /// last fragment.
+    pub fn end_record(&mut self) -> Result<()> {
+        let result = self.send_fragment(self.buf.len(), true);
+        self.buf.clear();
+        result?;
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    /// Consume the writer, returning the underlying stream. Data not yet
+    /// ended with [`end_record`](RecordWriter::end_record) is discarded.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    /// A reference to the underlying stream.
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    /// A mutable reference to the underlying stream. Writing to it directly
+    /// corrupts the record framing.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+
+    /// Send the first `len` buffered bytes as one fragment.
+    fn send_fragment(&mut self, len: usize, last: bool) -> io::Result<()> {
+        let mut header = len as u32;
+        if last {
+            header |= LAST_FRAGMENT;
+        }
+        self.writer.write_all(&header.to_be_bytes())?;
+        self.writer.write_all(&self.buf[..len])
+    }
+}
+
+impl<W: Write> Write for RecordWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        // Keep up to a full fragment buffered: it may turn out to be the last.
+        // A full buffer is sent before any of `buf` is taken, so an error
+        // means none of `buf` was accepted.
+        let max = self.max_fragment_size;
+        if self.buf.len() == max && !buf.is_empty() {
+            self.send_fragment(max, false)?;
+            self.buf.clear();
+        }
+        let n = buf.len().min(max - self.buf.len());
+        self.buf.extend_from_slice(&buf[..n]);
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
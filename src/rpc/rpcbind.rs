@@ -0,0 +1,512 @@
+//! Portmapper and rpcbind protocol types (RFC 1833), and [`Rpcbind`], a small
+//! in-process rpcbind service.
+//!
+//! Program 100000 answers to three versions: version 2 is the portmapper,
+//! which maps a program, version and IP protocol to a port; versions 3 and 4
+//! are rpcbind, which maps a program, version and network id (`"tcp"`,
+//! `"udp6"`, …) to a universal address such as `"127.0.0.1.8.1"`.
+//!
+//! ```rust
+//! use xdr_serde::rpc::rpcbind::{IPPROTO_TCP, Mapping, PmapList};
+//! use xdr_serde::{from_bytes, to_bytes};
+//!
+//! // A PMAPPROC_DUMP reply listing one mapping: NFSv3 on TCP port 2049.
+//! let nfs = Mapping { prog: 100003, vers: 3, prot: IPPROTO_TCP, port: 2049 };
+//! let list = PmapList(vec![nfs]);
+//! let bytes = to_bytes(&list).unwrap();
+//! assert_eq!(bytes.len(), 4 + 16 + 4); // more, mapping, no more
+//! assert_eq!(from_bytes::<PmapList>(&bytes).unwrap(), list);
+//! ```
+//!
+//! With the `std` feature, [`Rpcbind`] keeps a registry of services and
+//! answers all three versions through a `Server`, for tests and for hosts
+//! without a system rpcbind.
+
+use crate::ByteBuf;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::net::{IpAddr, SocketAddr};
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "std")]
+use super::server::Server;
+#[cfg(feature = "std")]
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// The well-known port of the portmapper and rpcbind, on TCP and UDP.
+pub const PMAP_PORT: u16 = 111;
+
+/// The portmapper and rpcbind program number.
+pub const PMAP_PROG: u32 = 100000;
+
+/// The portmapper version.
+pub const PMAP_VERS: u32 = 2;
+
+/// The rpcbind program number; the same program as [`PMAP_PROG`].
+pub const RPCBPROG: u32 = PMAP_PROG;
+
+/// rpcbind version 3.
+pub const RPCBVERS: u32 = 3;
+
+/// rpcbind version 4.
+pub const RPCBVERS4: u32 = 4;
+
+/// [`Mapping::prot`] for TCP.
+pub const IPPROTO_TCP: u32 = 6;
+
+/// [`Mapping::prot`] for UDP.
+pub const IPPROTO_UDP: u32 = 17;
+
+/// Portmapper: do nothing.
+pub const PMAPPROC_NULL: u32 = 0;
+/// Portmapper: register a [`Mapping`]; returns `bool`.
+pub const PMAPPROC_SET: u32 = 1;
+/// Portmapper: unregister every mapping of a program version; returns `bool`.
+pub const PMAPPROC_UNSET: u32 = 2;
+/// Portmapper: look up the port of a [`Mapping`]; returns `unsigned int`,
+/// zero if unregistered.
+pub const PMAPPROC_GETPORT: u32 = 3;
+/// Portmapper: list all mappings; returns `pmaplist *`.
+pub const PMAPPROC_DUMP: u32 = 4;
+/// Portmapper: call a procedure on the local host; takes [`CallArgs`],
+/// returns [`CallResult`].
+pub const PMAPPROC_CALLIT: u32 = 5;
+
+/// rpcbind: do nothing.
+pub const RPCBPROC_NULL: u32 = 0;
+/// rpcbind: register an [`Rpcb`]; returns `bool`.
+pub const RPCBPROC_SET: u32 = 1;
+/// rpcbind: unregister an [`Rpcb`]; returns `bool`.
+pub const RPCBPROC_UNSET: u32 = 2;
+/// rpcbind: look up the universal address of an [`Rpcb`]; returns `string`,
+/// empty if unregistered.
+pub const RPCBPROC_GETADDR: u32 = 3;
+/// rpcbind: list all registrations; returns `rpcblist_ptr`.
+pub const RPCBPROC_DUMP: u32 = 4;
+/// rpcbind version 3: call a procedure on the local host; takes
+/// [`CallArgs`], returns [`RmtcallRes`].
+pub const RPCBPROC_CALLIT: u32 = 5;
+/// rpcbind version 4: [`RPCBPROC_CALLIT`] for broadcast.
+pub const RPCBPROC_BCAST: u32 = 5;
+/// rpcbind: the server's time in seconds since the epoch; returns
+/// `unsigned int`.
+pub const RPCBPROC_GETTIME: u32 = 6;
+/// rpcbind: convert a universal address to a [`Netbuf`].
+pub const RPCBPROC_UADDR2TADDR: u32 = 7;
+/// rpcbind: convert a [`Netbuf`] to a universal address.
+pub const RPCBPROC_TADDR2UADDR: u32 = 8;
+/// rpcbind version 4: [`RPCBPROC_GETADDR`], but only for the exact version.
+pub const RPCBPROC_GETVERSADDR: u32 = 9;
+/// rpcbind version 4: [`RPCBPROC_CALLIT`] that replies only on success.
+pub const RPCBPROC_INDIRECT: u32 = 10;
+/// rpcbind version 4: list the addresses of a program on every transport.
+pub const RPCBPROC_GETADDRLIST: u32 = 11;
+/// rpcbind version 4: the server's call statistics.
+pub const RPCBPROC_GETSTAT: u32 = 12;
+
+/// `struct mapping`: a portmapper registration.
+///
+/// ```text
+/// struct mapping {
+///     unsigned int prog;
+///     unsigned int vers;
+///     unsigned int prot;
+///     unsigned int port;
+/// };
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Mapping {
+    pub prog: u32,
+    pub vers: u32,
+    /// [`IPPROTO_TCP`] or [`IPPROTO_UDP`].
+    pub prot: u32,
+    /// Ignored by `PMAPPROC_UNSET` and `PMAPPROC_GETPORT`.
+    pub port: u32,
+}
+
+/// `pmaplist *`: the mappings returned by `PMAPPROC_DUMP`.
+///
+/// ```text
+/// struct pmaplist {
+///     mapping map;
+///     pmaplist *next;
+/// };
+/// ```
+///
+/// Encoded as a [linked list](crate::linked_list) of `struct pmaplist`
+/// links; an empty list is a null pointer.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PmapList(#[serde(with = "crate::linked_list")] pub Vec<Mapping>);
+
+/// `struct call_args`: the arguments of `PMAPPROC_CALLIT`, with the same
+/// encoding as rpcbind's `struct rpcb_rmtcallargs`.
+///
+/// ```text
+/// struct call_args {
+///     unsigned int prog;
+///     unsigned int vers;
+///     unsigned int proc;
+///     opaque args<>;
+/// };
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CallArgs {
+    pub prog: u32,
+    pub vers: u32,
+    pub proc: u32,
+    /// The encoded arguments of the procedure.
+    pub args: ByteBuf,
+}
+
+/// `struct call_result`: the results of `PMAPPROC_CALLIT`.
+///
+/// ```text
+/// struct call_result {
+///     unsigned int port;
+///     opaque res<>;
+/// };
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CallResult {
+    /// The port of the program that was called.
+    pub port: u32,
+    /// The encoded results of the procedure.
+    pub res: ByteBuf,
+}
+
+/// `struct rpcb`: an rpcbind registration.
+///
+/// ```text
+/// struct rpcb {
+///     unsigned int r_prog;
+///     unsigned int r_vers;
+///     string r_netid<>;
+///     string r_addr<>;
+///     string r_owner<>;
+/// };
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Rpcb {
+    pub r_prog: u32,
+    pub r_vers: u32,
+    /// The network id, such as `"tcp"` or `"udp6"`.
+    pub r_netid: String,
+    /// The universal address; see [`to_uaddr`].
+    pub r_addr: String,
+    /// Who made the registration, typically a user name.
+    pub r_owner: String,
+}
+
+/// `rpcblist_ptr`: the registrations returned by `RPCBPROC_DUMP`.
+///
+/// ```text
+/// struct rp__list {
+///     rpcb rpcb_map;
+///     struct rp__list *rpcb_next;
+/// };
+/// typedef rp__list *rpcblist_ptr;
+/// ```
+///
+/// Encoded as a [linked list](crate::linked_list) of `struct rp__list`
+/// links; an empty list is a null pointer.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RpcbList(#[serde(with = "crate::linked_list")] pub Vec<Rpcb>);
+
+/// `struct rpcb_rmtcallres`: the results of `RPCBPROC_CALLIT`.
+///
+/// ```text
+/// struct rpcb_rmtcallres {
+///     string addr<>;
+///     opaque results<>;
+/// };
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RmtcallRes {
+    /// The universal address of the program that was called.
+    pub addr: String,
+    /// The encoded results of the procedure.
+    pub results: ByteBuf,
+}
+
+/// `struct netbuf`: a transport-specific address.
+///
+/// ```text
+/// struct netbuf {
+///     unsigned int maxlen;
+///     opaque buf<>;
+/// };
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Netbuf {
+    pub maxlen: u32,
+    pub buf: ByteBuf,
+}
+
+/// The universal address of `addr` (RFC 5665 §5.2.3): the IP address
+/// followed by the two bytes of the port in decimal, as in
+/// `"192.0.2.1.8.1"` for port 2049.
+pub fn to_uaddr(addr: SocketAddr) -> String {
+    format!("{}.{}.{}", addr.ip(), addr.port() >> 8, addr.port() & 0xFF)
+}
+
+/// Parse a universal address produced by [`to_uaddr`], or `None` if `uaddr`
+/// is not an IPv4 or IPv6 universal address.
+pub fn parse_uaddr(uaddr: &str) -> Option<SocketAddr> {
+    let mut parts = uaddr.rsplitn(3, '.');
+    let low: u8 = parts.next()?.parse().ok()?;
+    let high: u8 = parts.next()?.parse().ok()?;
+    let ip: IpAddr = parts.next()?.parse().ok()?;
+    Some(SocketAddr::new(ip, u16::from_be_bytes([high, low])))
+}
+
+/// The network id of a portmapper protocol.
+#[cfg(feature = "std")]
+fn netid(prot: u32) -> Option<&'static str> {
+    match prot {
+        IPPROTO_TCP => Some("tcp"),
+        IPPROTO_UDP => Some("udp"),
+        _ => None,
+    }
+}
+
+/// The portmapper protocol of a network id.
+#[cfg(feature = "std")]
+fn prot(netid: &str) -> Option<u32> {
+    match netid {
+        "tcp" => Some(IPPROTO_TCP),
+        "udp" => Some(IPPROTO_UDP),
+        _ => None,
+    }
+}
+
+/// An in-process rpcbind: a registry of services, answering portmapper
+/// version 2 and rpcbind versions 3 and 4 through [`Rpcbind::server`].
+/// Requires the `std` feature.
+///
+/// Both protocols share the registry; a portmapper mapping is stored as an
+/// IPv4 `"tcp"` or `"udp"` registration. Clones share the registry, so a
+/// clone can register services while another serves them.
+///
+/// ```rust
+/// use xdr_serde::rpc::rpcbind::{IPPROTO_TCP, IPPROTO_UDP, Mapping, Rpcb, Rpcbind};
+///
+/// let rpcbind = Rpcbind::new();
+/// assert!(rpcbind.set(Rpcb {
+///     r_prog: 100003,
+///     r_vers: 3,
+///     r_netid: "tcp".into(),
+///     r_addr: "127.0.0.1.8.1".into(),
+///     r_owner: "nfsd".into(),
+/// }));
+/// assert!(rpcbind.pmap_set(Mapping { prog: 100005, vers: 3, prot: IPPROTO_UDP, port: 20048 }));
+///
+/// let query = Mapping { prog: 100003, vers: 3, prot: IPPROTO_TCP, port: 0 };
+/// assert_eq!(rpcbind.pmap_getport(&query), 2049);
+/// assert_eq!(rpcbind.dump().len(), 2);
+/// ```
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Default)]
+pub struct Rpcbind {
+    registry: Arc<Mutex<Vec<Rpcb>>>,
+}
+
+#[cfg(feature = "std")]
+impl Rpcbind {
+    /// An rpcbind with nothing registered.
+    pub fn new() -> Self {
+        Rpcbind::default()
+    }
+
+    fn registry(&self) -> MutexGuard<'_, Vec<Rpcb>> {
+        // The registry is consistent after every statement, so a panic
+        // elsewhere cannot leave it half-updated.
+        self.registry
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// `RPCBPROC_SET`: register `rpcb`. Returns `false`, changing nothing,
+    /// if its program, version and network id are already registered.
+    pub fn set(&self, rpcb: Rpcb) -> bool {
+        let mut registry = self.registry();
+        let taken = registry.iter().any(|r| {
+            r.r_prog == rpcb.r_prog && r.r_vers == rpcb.r_vers && r.r_netid == rpcb.r_netid
+        });
+        if !taken {
+            registry.push(rpcb);
+        }
+        !taken
+    }
+
+    /// `RPCBPROC_UNSET`: remove the registrations of the program and version
+    /// of `rpcb` on its network id, or on every network id if that is empty.
+    /// Returns whether any were removed.
+    ///
+    /// Owners are not checked: callers are trusted.
+    pub fn unset(&self, rpcb: &Rpcb) -> bool {
+        let mut registry = self.registry();
+        let before = registry.len();
+        registry.retain(|r| {
+            !(r.r_prog == rpcb.r_prog
+                && r.r_vers == rpcb.r_vers
+                && (rpcb.r_netid.is_empty() || r.r_netid == rpcb.r_netid))
+        });
+        registry.len() != before
+    }
+
+    /// `RPCBPROC_GETADDR`: the universal address of the program and version
+    /// of `rpcb` on its network id, or on any network id if that is empty.
+    ///
+    /// If the version is not registered the address of another version of
+    /// the program is returned, as the protocol specifies; an empty string
+    /// means the program is not registered at all.
+    pub fn getaddr(&self, rpcb: &Rpcb) -> String {
+        let registry = self.registry();
+        let candidates = || {
+            registry.iter().filter(|r| {
+                r.r_prog == rpcb.r_prog && (rpcb.r_netid.is_empty() || r.r_netid == rpcb.r_netid)
+            })
+        };
+        candidates()
+            .find(|r| r.r_vers == rpcb.r_vers)
+            .or_else(|| candidates().next())
+            .map(|r| r.r_addr.clone())
+            .unwrap_or_default()
+    }
+
+    /// `RPCBPROC_GETVERSADDR`: like [`getaddr`](Rpcbind::getaddr), but only
+    /// for the exact version.
+    pub fn getversaddr(&self, rpcb: &Rpcb) -> String {
+        self.registry()
+            .iter()
+            .find(|r| {
+                r.r_prog == rpcb.r_prog
+                    && r.r_vers == rpcb.r_vers
+                    && (rpcb.r_netid.is_empty() || r.r_netid == rpcb.r_netid)
+            })
+            .map(|r| r.r_addr.clone())
+            .unwrap_or_default()
+    }
+
+    /// `RPCBPROC_DUMP`: every registration, in the order they were made.
+    pub fn dump(&self) -> Vec<Rpcb> {
+        self.registry().clone()
+    }
+
+    /// `PMAPPROC_SET`: register `mapping` as a `"tcp"` or `"udp"`
+    /// registration on all IPv4 interfaces. Returns `false` if the program
+    /// version is already registered on the protocol, or the protocol is
+    /// neither TCP nor UDP.
+    pub fn pmap_set(&self, mapping: Mapping) -> bool {
+        let (Some(netid), Ok(port)) = (netid(mapping.prot), u16::try_from(mapping.port)) else {
+            return false;
+        };
+        self.set(Rpcb {
+            r_prog: mapping.prog,
+            r_vers: mapping.vers,
+            r_netid: netid.into(),
+            r_addr: to_uaddr(SocketAddr::from(([0, 0, 0, 0], port))),
+            r_owner: String::new(),
+        })
+    }
+
+    /// `PMAPPROC_UNSET`: remove every registration of the program version,
+    /// on any protocol. Returns whether any were removed.
+    pub fn pmap_unset(&self, mapping: &Mapping) -> bool {
+        self.unset(&Rpcb {
+            r_prog: mapping.prog,
+            r_vers: mapping.vers,
+            ..Rpcb::default()
+        })
+    }
+
+    /// `PMAPPROC_GETPORT`: the port of the program version on the protocol,
+    /// falling back to another version as [`getaddr`](Rpcbind::getaddr)
+    /// does, or zero if the program is not registered on it.
+    pub fn pmap_getport(&self, mapping: &Mapping) -> u32 {
+        let Some(netid) = netid(mapping.prot) else {
+            return 0;
+        };
+        let addr = self.getaddr(&Rpcb {
+            r_prog: mapping.prog,
+            r_vers: mapping.vers,
+            r_netid: netid.into(),
+            ..Rpcb::default()
+        });
+        parse_uaddr(&addr).map_or(0, |addr| u32::from(addr.port()))
+    }
+
+    /// `PMAPPROC_DUMP`: the IPv4 TCP and UDP registrations, as mappings.
+    pub fn pmap_dump(&self) -> Vec<Mapping> {
+        self.registry()
+            .iter()
+            .filter_map(|r| {
+                let prot = prot(&r.r_netid)?;
+                let port = parse_uaddr(&r.r_addr).filter(SocketAddr::is_ipv4)?.port();
+                Some(Mapping {
+                    prog: r.r_prog,
+                    vers: r.r_vers,
+                    prot,
+                    port: u32::from(port),
+                })
+            })
+            .collect()
+    }
+
+    /// A [`Server`] exporting program 100000 versions 2, 3 and 4 backed by
+    /// this registry. More programs can be added to it with
+    /// [`with_procedure`](Server::with_procedure).
+    ///
+    /// The `CALLIT` family, address conversion and statistics procedures
+    /// are not implemented and answer `PROC_UNAVAIL`.
+    pub fn server(&self) -> Server {
+        let mut server = Server::new()
+            .with_procedure(PMAP_PROG, PMAP_VERS, PMAPPROC_NULL, |_, ()| ())
+            .with_procedure(PMAP_PROG, PMAP_VERS, PMAPPROC_SET, {
+                let rpcbind = self.clone();
+                move |_, mapping: Mapping| rpcbind.pmap_set(mapping)
+            })
+            .with_procedure(PMAP_PROG, PMAP_VERS, PMAPPROC_UNSET, {
+                let rpcbind = self.clone();
+                move |_, mapping: Mapping| rpcbind.pmap_unset(&mapping)
+            })
+            .with_procedure(PMAP_PROG, PMAP_VERS, PMAPPROC_GETPORT, {
+                let rpcbind = self.clone();
+                move |_, mapping: Mapping| rpcbind.pmap_getport(&mapping)
+            })
+            .with_procedure(PMAP_PROG, PMAP_VERS, PMAPPROC_DUMP, {
+                let rpcbind = self.clone();
+                move |_, ()| PmapList(rpcbind.pmap_dump())
+            });
+        for vers in [RPCBVERS, RPCBVERS4] {
+            server = server
+                .with_procedure(RPCBPROG, vers, RPCBPROC_NULL, |_, ()| ())
+                .with_procedure(RPCBPROG, vers, RPCBPROC_SET, {
+                    let rpcbind = self.clone();
+                    move |_, rpcb: Rpcb| rpcbind.set(rpcb)
+                })
+                .with_procedure(RPCBPROG, vers, RPCBPROC_UNSET, {
+                    let rpcbind = self.clone();
+                    move |_, rpcb: Rpcb| rpcbind.unset(&rpcb)
+                })
+                .with_procedure(RPCBPROG, vers, RPCBPROC_GETADDR, {
+                    let rpcbind = self.clone();
+                    move |_, rpcb: Rpcb| rpcbind.getaddr(&rpcb)
+                })
+                .with_procedure(RPCBPROG, vers, RPCBPROC_DUMP, {
+                    let rpcbind = self.clone();
+                    move |_, ()| RpcbList(rpcbind.dump())
+                })
+                .with_procedure(RPCBPROG, vers, RPCBPROC_GETTIME, |_, ()| {
+                    std::time::SystemTime::now()
+                        .duration_since(std::time::SystemTime::UNIX_EPOCH)
+                        .map_or(0, |d| d.as_secs() as u32)
+                });
+        }
+        server.with_procedure(RPCBPROG, RPCBVERS4, RPCBPROC_GETVERSADDR, {
+            let rpcbind = self.clone();
+            move |_, rpcb: Rpcb| rpcbind.getversaddr(&rpcb)
+        })
+    }
+}
@@ -0,0 +1,322 @@
+//! A minimal ONC RPC server. Requires the `std` feature.
+//!
+//! A [`Server`] holds one handler per (program, version, procedure). Each
+//! handler takes the decoded arguments and returns the results; the server
+//! decodes calls, picks the handler and builds the reply, answering with
+//! `PROG_UNAVAIL`, `PROG_MISMATCH`, `PROC_UNAVAIL` or `GARBAGE_ARGS` when no
+//! handler applies. Calls with an RPC version other than 2 are denied with
+//! `RPC_MISMATCH`.
+//!
+//! ```rust
+//! use xdr_serde::rpc::server::Server;
+//! use xdr_serde::rpc::{CallBody, MsgBody, ReplyBody, ReplyData, RpcMsg};
+//! use xdr_serde::{from_bytes_partial, to_bytes};
+//!
+//! const PROG: u32 = 0x2000_0001;
+//!
+//! let server = Server::new()
+//!     .with_procedure(PROG, 1, 0, |_, ()| ())
+//!     .with_procedure(PROG, 1, 1, |_, (a, b): (u32, u32)| a + b);
+//!
+//! let call = RpcMsg { xid: 7, body: MsgBody::Call(CallBody::new(PROG, 1, 1)) };
+//! let mut request = to_bytes(&call).unwrap();
+//! request.extend(to_bytes(&(2u32, 3u32)).unwrap());
+//!
+//! let reply = server.dispatch(&request).unwrap();
+//! let (header, results) = from_bytes_partial::<RpcMsg>(&reply).unwrap();
+//! let MsgBody::Reply(ReplyBody::Accepted(accepted)) = header.body else { panic!() };
+//! assert_eq!(accepted.reply_data, ReplyData::Success);
+//! assert_eq!(results, [0, 0, 0, 5]);
+//! ```
+//!
+//! [`serve_tcp`](Server::serve_tcp) and [`serve_udp`](Server::serve_udp)
+//! run the server on a socket; [`serve_stream`](Server::serve_stream) and
+//! [`dispatch`](Server::dispatch) suit other transports.
+
+use super::record::{DEFAULT_MAX_RECORD_SIZE, RecordReader, RecordWriter};
+use super::{
+    AcceptedReply, CallBody, MismatchInfo, MsgBody, OpaqueAuth, RPC_VERSION, RejectedReply,
+    ReplyBody, ReplyData, RpcMsg,
+};
+use crate::de::Deserializer;
+use crate::error::Result;
+use crate::limits::DecodeLimits;
+use crate::{from_bytes_partial, to_bytes};
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::io::{self, BufReader, Read, Write};
+use std::net::{TcpListener, UdpSocket};
+use std::time::Duration;
+
+/// The largest UDP datagram [`Server::serve_udp`] receives.
+pub const MAX_DATAGRAM_SIZE: usize = 65536;
+
+/// How long [`Server::serve_tcp`] waits after a transient `accept` failure.
+const ACCEPT_BACKOFF: Duration = Duration::from_millis(10);
+
+/// What a procedure produced from the raw arguments.
+enum Outcome {
+    Results(Vec<u8>),
+    GarbageArgs,
+    SystemErr,
+}
+
+type Handler = Box<dyn Fn(&CallBody, &[u8], DecodeLimits) -> Outcome + Send + Sync>;
+
+/// Dispatches RPC calls to registered procedure handlers.
+pub struct Server {
+    /// Handlers by program, version and procedure.
+    programs: BTreeMap<u32, BTreeMap<u32, BTreeMap<u32, Handler>>>,
+    max_record_size: usize,
+    limits: DecodeLimits,
+}
+
+impl Server {
+    /// A server with no procedures.
+    pub fn new() -> Self {
+        Server {
+            programs: BTreeMap::new(),
+            max_record_size: DEFAULT_MAX_RECORD_SIZE,
+            limits: DecodeLimits::UNLIMITED,
+        }
+    }
+
+    /// Register `handler` for procedure `proc` of version `vers` of program
+    /// `prog`, replacing any handler already registered for it.
+    ///
+    /// The handler receives the call header, for its credential, and the
+    /// arguments decoded as `T`. Arguments that fail to decode, or exceed the
+    /// server's [limits](Server::with_limits), are answered with
+    /// `GARBAGE_ARGS`; results that fail to encode with `SYSTEM_ERR`.
+    pub fn with_procedure<T, R, F>(mut self, prog: u32, vers: u32, proc: u32, handler: F) -> Self
+    where
+        T: DeserializeOwned,
+        R: Serialize,
+        F: Fn(&CallBody, T) -> R + Send + Sync + 'static,
+    {
+        let handler: Handler = Box::new(move |call, args, limits| {
+            let mut de = Deserializer::new(args).with_limits(limits);
+            let Ok(args) = T::deserialize(&mut de) else {
+                return Outcome::GarbageArgs;
+            };
+            match to_bytes(&handler(call, args)) {
+                Ok(results) => Outcome::Results(results),
+                Err(_) => Outcome::SystemErr,
+            }
+        });
+        self.programs
+            .entry(prog)
+            .or_default()
+            .entry(vers)
+            .or_default()
+            .insert(proc, handler);
+        self
+    }
+
+    /// Reject TCP records longer than `max` bytes, closing the connection.
+    pub fn with_max_record_size(mut self, max: usize) -> Self {
+        self.max_record_size = max;
+        self
+    }
+
+    /// Enforce `limits` when decoding the arguments of every procedure.
+    pub fn with_limits(mut self, limits: DecodeLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Handle one encoded call message, returning the encoded reply.
+    ///
+    /// Returns `None` for input that cannot be answered: a message whose
+    /// header does not decode, or a reply.
+    pub fn dispatch(&self, request: &[u8]) -> Option<Vec<u8>> {
+        let (msg, args) = from_bytes_partial::<RpcMsg>(request).ok()?;
+        let MsgBody::Call(call) = msg.body else {
+            return None;
+        };
+        if call.rpcvers != RPC_VERSION {
+            let mismatch = MismatchInfo {
+                low: RPC_VERSION,
+                high: RPC_VERSION,
+            };
+            return reply(
+                msg.xid,
+                ReplyBody::Denied(RejectedReply::RpcMismatch(mismatch)),
+                &[],
+            );
+        }
+        let (reply_data, results) = match self.call(&call, args) {
+            Ok(results) => (ReplyData::Success, results),
+            Err(reply_data) => (reply_data, Vec::new()),
+        };
+        let accepted = AcceptedReply {
+            verf: OpaqueAuth::none(),
+            reply_data,
+        };
+        reply(msg.xid, ReplyBody::Accepted(accepted), &results)
+    }
+
+    /// Run the procedure named by `call`, or say why it cannot be run.
+    fn call(&self, call: &CallBody, args: &[u8]) -> core::result::Result<Vec<u8>, ReplyData> {
+        let versions = self
+            .programs
+            .get(&call.prog)
+            .ok_or(ReplyData::ProgUnavail)?;
+        let Some(procedures) = versions.get(&call.vers) else {
+            // `versions` is never empty: it was created by a registration.
+            let low = *versions.keys().next().unwrap_or(&0);
+            let high = *versions.keys().next_back().unwrap_or(&0);
+            return Err(ReplyData::ProgMismatch(MismatchInfo { low, high }));
+        };
+        let handler = procedures.get(&call.proc).ok_or(ReplyData::ProcUnavail)?;
+        match handler(call, args, self.limits) {
+            Outcome::Results(results) => Ok(results),
+            Outcome::GarbageArgs => Err(ReplyData::GarbageArgs),
+            Outcome::SystemErr => Err(ReplyData::SystemErr),
+        }
+    }
+
+    /// Serve record-marked calls read from `reader`, writing replies to
+    /// `writer`, until `reader` reaches a clean end of stream.
+    ///
+    /// Fails on I/O errors and on records longer than the maximum record
+    /// size.
+    pub fn serve_stream<R: Read, W: Write>(&self, reader: R, writer: W) -> Result<()> {
+        let mut records = RecordReader::new(reader).with_max_record_size(self.max_record_size);
+        let mut writer = RecordWriter::new(writer);
+        while let Some(request) = records.read_record()? {
+            if let Some(reply) = self.dispatch(&request) {
+                writer.write_all(&reply)?;
+                writer.end_record()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Accept TCP connections on `listener` and serve each on its own thread.
+    ///
+    /// Runs until accepting fails with an error that does not clear by
+    /// itself, then waits for open connections to close. A connection that
+    /// fails is closed without affecting the others, and one that is reset
+    /// before it is accepted, or cannot be accepted for want of descriptors,
+    /// is skipped.
+    ///
+    /// `listener` must be in blocking mode: on a non-blocking listener the
+    /// first `accept` with nothing pending returns the
+    /// [`WouldBlock`](io::ErrorKind::WouldBlock) error.
+    pub fn serve_tcp(&self, listener: &TcpListener) -> Result<()> {
+        std::thread::scope(|scope| {
+            loop {
+                let stream = match listener.accept() {
+                    Ok((stream, _)) => stream,
+                    Err(e) if is_transient(&e) => {
+                        // Give connections time to close and free descriptors.
+                        std::thread::sleep(ACCEPT_BACKOFF);
+                        continue;
+                    }
+                    Err(e) => return Err(e.into()),
+                };
+                let Ok(reader) = stream.try_clone() else {
+                    continue;
+                };
+                scope.spawn(move || self.serve_stream(BufReader::new(reader), stream));
+            }
+        })
+    }
+
+    /// Serve calls arriving as UDP datagrams on `socket`, one message per
+    /// datagram, replying to the sender.
+    ///
+    /// Runs until receiving fails with an error that concerns the socket
+    /// rather than one datagram. A reply that cannot be sent is dropped, as
+    /// a lost datagram would be; the client retransmits.
+    ///
+    /// `socket` must be in blocking mode with no read timeout: a receive that
+    /// would block or times out returns its error.
+    pub fn serve_udp(&self, socket: &UdpSocket) -> Result<()> {
+        let mut buf = alloc::vec![0; MAX_DATAGRAM_SIZE];
+        loop {
+            let (n, peer) = match socket.recv_from(&mut buf) {
+                Ok(received) => received,
+                Err(e) if is_transient_datagram(&e) => continue,
+                Err(e) => return Err(e.into()),
+            };
+            if let Some(reply) = self.dispatch(&buf[..n]) {
+                // A socket that has failed for good fails the next receive.
+                let _ = socket.send_to(&reply, peer);
+            }
+        }
+    }
+}
+
+impl Default for Server {
+    fn default() -> Self {
+        Server::new()
+    }
+}
+
+impl core::fmt::Debug for Server {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let procedures: Vec<(u32, u32, u32)> = self
+            .programs
+            .iter()
+            .flat_map(|(&prog, versions)| {
+                versions.iter().flat_map(move |(&vers, procs)| {
+                    procs.keys().map(move |&proc| (prog, vers, proc))
+                })
+            })
+            .collect();
+        f.debug_struct("Server")
+            .field("procedures", &procedures)
+            .field("max_record_size", &self.max_record_size)
+            .field("limits", &self.limits)
+            .finish()
+    }
+}
+
+/// Encode a reply header followed by `results`.
+fn reply(xid: u32, body: ReplyBody, results: &[u8]) -> Option<Vec<u8>> {
+    let mut bytes = to_bytes(&RpcMsg {
+        xid,
+        body: MsgBody::Reply(body),
+    })
+    .ok()?;
+    bytes.extend_from_slice(results);
+    Some(bytes)
+}
+
+/// Whether an `accept` failure concerns one connection or a shortage that
+/// clears as connections close, rather than the listener itself.
+fn is_transient(e: &io::Error) -> bool {
+    match e.kind() {
+        io::ErrorKind::ConnectionAborted
+        | io::ErrorKind::ConnectionReset
+        | io::ErrorKind::Interrupted
+        | io::ErrorKind::TimedOut => true,
+        // Out of file descriptors.
+        #[cfg(unix)]
+        _ => matches!(e.raw_os_error(), Some(libc::EMFILE | libc::ENFILE)),
+        #[cfg(not(unix))]
+        _ => false,
+    }
+}
+
+/// Whether a `recv_from` failure concerns one datagram, or an ICMP error
+/// left by an earlier send, rather than the socket itself.
+fn is_transient_datagram(e: &io::Error) -> bool {
+    match e.kind() {
+        io::ErrorKind::ConnectionRefused
+        | io::ErrorKind::ConnectionReset
+        | io::ErrorKind::HostUnreachable
+        | io::ErrorKind::NetworkUnreachable
+        | io::ErrorKind::Interrupted => true,
+        // Out of buffer space for the datagram.
+        #[cfg(unix)]
+        _ => e.raw_os_error() == Some(libc::ENOBUFS),
+        #[cfg(not(unix))]
+        _ => false,
+    }
+}
@@ -1,7 +1,9 @@
 //! XDR Serializer (RFC 4506)
 //!
-//! The [`Serializer`] is generic over any `W: std::io::Write`, enabling both
-//! in-memory serialization (`to_bytes`) and streaming serialization (`to_writer`).
+//! The [`Serializer`] is generic over any [`Output`]: every `std::io::Write`,
+//! enabling both in-memory serialization (`to_bytes`) and streaming
+//! serialization (`to_writer`), and [`SliceWriter`] for writing into a fixed
+//! buffer (`to_slice`).
 //!
 //! ## Wire format summary
 //! - All values are big-endian (network byte order)
@@ -16,8 +18,14 @@
 //! - Enums (with data): 4-byte discriminant + encoded arm
 
 use crate::error::{Error, Result};
+use alloc::vec::Vec;
+#[cfg(feature = "futures")]
+use futures_util::io::{AsyncWrite, AsyncWriteExt};
 use serde::ser::{self, Serialize};
+#[cfg(feature = "std")]
 use std::io::Write;
+#[cfg(feature = "tokio")]
+use tokio::io::{AsyncWrite as TokioAsyncWrite, AsyncWriteExt as _};
 
 // ── Public entry points ────────────────────────────────────────────────────
 
@@ -30,18 +38,187 @@ pub fn to_bytes<T: Serialize>(value: &T) -> Result<Vec<u8>> {
 
 /// Serialize `value` as XDR bytes, writing directly into `writer`.
 ///
+/// Requires the `std` feature.
+///
 /// Unlike [`to_bytes`], this never allocates an intermediate buffer. Useful
 /// when writing to a `TcpStream`, `File`, or any other `Write` sink.
+#[cfg(feature = "std")]
 pub fn to_writer<W: Write, T: Serialize>(mut writer: W, value: &T) -> Result<()> {
     let mut ser = Serializer::new(&mut writer);
     value.serialize(&mut ser)
 }
 
+/// Serialize `value` as XDR bytes into a [`futures::io::AsyncWrite`](AsyncWrite).
+///
+/// Requires the `futures` feature.
+///
+/// The async counterpart of [`to_writer`]. The value is encoded into a buffer
+/// first and then written with a single `write_all`; like [`to_writer`], the
+/// writer is not flushed.
+#[cfg(feature = "futures")]
+pub async fn to_async_writer<W, T>(mut writer: W, value: &T) -> Result<()>
+where
+    W: AsyncWrite + Unpin,
+    T: Serialize,
+{
+    let bytes = to_bytes(value)?;
+    writer.write_all(&bytes).await?;
+    Ok(())
+}
+
+/// Serialize `value` as XDR bytes into a [`tokio::io::AsyncWrite`](TokioAsyncWrite).
+///
+/// Requires the `tokio` feature. Behaves as [`to_async_writer`].
+#[cfg(feature = "tokio")]
+pub async fn to_tokio_writer<W, T>(mut writer: W, value: &T) -> Result<()>
+where
+    W: TokioAsyncWrite + Unpin,
+    T: Serialize,
+{
+    let bytes = to_bytes(value)?;
+    writer.write_all(&bytes).await?;
+    Ok(())
+}
+
+/// Serialize `value` into the start of `buf`, returning the number of bytes
+/// written.
+///
+/// Nothing is allocated. If `value` does not fit, [`Error::BufferTooSmall`] is
+/// returned and the contents of `buf` are unspecified; use
+/// [`serialized_size`] to pick a buffer that is large enough.
+///
+/// ```rust
+/// use xdr_serde::{Error, to_slice};
+///
+/// let mut buf = [0u8; 8];
+/// assert_eq!(to_slice(&(1u32, 2u32), &mut buf), Ok(8));
+/// assert_eq!(buf, [0, 0, 0, 1, 0, 0, 0, 2]);
+/// assert_eq!(to_slice(&"too long", &mut buf), Err(Error::BufferTooSmall));
+/// ```
+pub fn to_slice<T: Serialize>(value: &T, buf: &mut [u8]) -> Result<usize> {
+    let mut ser = Serializer::new(SliceWriter::new(buf));
+    value.serialize(&mut ser)?;
+    Ok(ser.into_writer().position())
+}
+
+/// The number of bytes [`to_bytes`] would produce for `value`, computed
+/// without allocating.
+///
+/// `value` is run through the same [`Serializer`] as [`to_bytes`], writing
+/// into a sink that only counts, so every encoding rule applies, including
+/// padding, fixed opaque data and `<N>` bounds. An error is returned exactly
+/// when [`to_bytes`] would fail.
+///
+/// ```rust
+/// use xdr_serde::{serialized_size, to_bytes};
+///
+/// let value = (7u8, "hello".to_string(), vec![1u16, 2]);
+/// assert_eq!(serialized_size(&value).unwrap(), 4 + (4 + 8) + (4 + 8));
+/// assert_eq!(serialized_size(&value).unwrap(), to_bytes(&value).unwrap().len());
+/// ```
+pub fn serialized_size<T: Serialize>(value: &T) -> Result<usize> {
+    let mut ser = Serializer::new(SizeCounter(0));
+    value.serialize(&mut ser)?;
+    Ok(ser.into_writer().0)
+}
+
+// ── Outputs ────────────────────────────────────────────────────────────────
+
+/// A destination for XDR bytes.
+///
+/// Implemented for every [`std::io::Write`] with the `std` feature, and for
+/// `Vec<u8>` and `&mut O` without it; and for [`SliceWriter`].
+pub trait Output {
+    /// Write all of `bytes`, or fail.
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<()>;
+}
+
+#[cfg(feature = "std")]
+impl<W: Write + ?Sized> Output for W {
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<()> {
+        self.write_all(bytes).map_err(Error::from)
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl Output for Vec<u8> {
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<()> {
+        self.extend_from_slice(bytes);
+        Ok(())
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl<O: Output + ?Sized> Output for &mut O {
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<()> {
+        (**self).write_bytes(bytes)
+    }
+}
+
+/// An [`Output`] over a fixed buffer, used by [`to_slice`].
+///
+/// Writing past the end of the buffer fails with [`Error::BufferTooSmall`].
+///
+/// ```rust
+/// use serde::Serialize;
+/// use xdr_serde::ser::{Serializer, SliceWriter};
+///
+/// let mut buf = [0u8; 16];
+/// let mut ser = Serializer::new(SliceWriter::new(&mut buf));
+/// 1u32.serialize(&mut ser).unwrap();
+/// 2u64.serialize(&mut ser).unwrap();
+/// assert_eq!(ser.into_writer().position(), 12);
+/// ```
+pub struct SliceWriter<'a> {
+    buf: &'a mut [u8],
+    pos: usize,
+}
+
+impl<'a> SliceWriter<'a> {
+    /// Create a writer that fills `buf` from the start.
+    pub fn new(buf: &'a mut [u8]) -> Self {
+        SliceWriter { buf, pos: 0 }
+    }
+
+    /// The number of bytes written so far.
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// The bytes written so far.
+    pub fn written(&self) -> &[u8] {
+        &self.buf[..self.pos]
+    }
+}
+
+impl Output for SliceWriter<'_> {
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<()> {
+        let end = self.pos + bytes.len();
+        let dest = self
+            .buf
+            .get_mut(self.pos..end)
+            .ok_or(Error::BufferTooSmall)?;
+        dest.copy_from_slice(bytes);
+        self.pos = end;
+        Ok(())
+    }
+}
+
+/// An [`Output`] that discards its input and counts the bytes.
+struct SizeCounter(usize);
+
+impl Output for SizeCounter {
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<()> {
+        self.0 += bytes.len();
+        Ok(())
+    }
+}
+
 // ── Serializer ─────────────────────────────────────────────────────────────
 
-/// The XDR serializer. Generic over any `W: Write`.
+/// The XDR serializer. Generic over any [`Output`] `W`.
 ///
-/// Obtain one via [`to_bytes`] / [`to_writer`], or construct directly for
+/// Obtain one via [`to_bytes`] / [`to_writer`] / [`to_slice`], or construct directly for
 /// advanced use cases:
 ///
 /// ```rust
@@ -53,14 +230,20 @@ pub fn to_writer<W: Write, T: Serialize>(mut writer: W, value: &T) -> Result<()>
 /// 42u32.serialize(&mut ser).unwrap();
 /// assert_eq!(buf, [0, 0, 0, 42]);
 /// ```
-pub struct Serializer<W: Write> {
+pub struct Serializer<W: Output> {
     writer: W,
+    /// Length bound announced by a `BOUNDED_TOKEN` tuple struct, applied to
+    /// the next length prefix written.
+    bound: Option<u32>,
 }
 
-impl<W: Write> Serializer<W> {
+impl<W: Output> Serializer<W> {
     /// Create a new serializer that writes into `writer`.
     pub fn new(writer: W) -> Self {
-        Serializer { writer }
+        Serializer {
+            writer,
+            bound: None,
+        }
     }
 
     /// Consume the serializer and return the inner writer.
@@ -71,9 +254,7 @@ impl<W: Write> Serializer<W> {
     // ── Internal helpers ───────────────────────────────────────────────────
 
     fn write_all(&mut self, bytes: &[u8]) -> Result<()> {
-        self.writer
-            .write_all(bytes)
-            .map_err(|e| Error::Io(e.to_string()))
+        self.writer.write_bytes(bytes)
     }
 
     fn write_u32(&mut self, v: u32) -> Result<()> {
@@ -103,22 +284,28 @@ impl<W: Write> Serializer<W> {
         Ok(())
     }
 
-    /// XDR variable-length opaque: 4-byte length + padded data.
-    fn write_opaque_variable(&mut self, bytes: &[u8]) -> Result<()> {
-        if bytes.len() > u32::MAX as usize {
+    /// Write a length or count prefix, enforcing any pending `<N>` bound.
+    fn write_len(&mut self, len: usize) -> Result<()> {
+        let max = self.bound.take().unwrap_or(u32::MAX);
+        if len > max as usize {
             return Err(Error::LengthOverflow {
-                max: u32::MAX,
-                got: u32::MAX,
+                max,
+                got: u32::try_from(len).unwrap_or(u32::MAX),
             });
         }
-        self.write_u32(bytes.len() as u32)?;
+        self.write_u32(len as u32)
+    }
+
+    /// XDR variable-length opaque: 4-byte length + padded data.
+    fn write_opaque_variable(&mut self, bytes: &[u8]) -> Result<()> {
+        self.write_len(bytes.len())?;
         self.write_padded_bytes(bytes)
     }
 }
 
 // ── serde::Serializer impl ─────────────────────────────────────────────────
 
-impl<'a, W: Write> ser::Serializer for &'a mut Serializer<W> {
+impl<W: Output> ser::Serializer for &mut Serializer<W> {
     type Ok = ();
     type Error = Error;
 
@@ -250,7 +437,7 @@ impl<'a, W: Write> ser::Serializer for &'a mut Serializer<W> {
     /// XDR variable-length array → 4-byte element count + elements
     fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq> {
         let l = len.ok_or(Error::LengthRequired)?;
-        self.write_u32(l as u32)?;
+        self.write_len(l)?;
         Ok(self)
     }
 
@@ -260,9 +447,14 @@ impl<'a, W: Write> ser::Serializer for &'a mut Serializer<W> {
     }
     fn serialize_tuple_struct(
         self,
-        _name: &'static str,
-        _len: usize,
+        name: &'static str,
+        len: usize,
     ) -> Result<Self::SerializeTupleStruct> {
+        if name == crate::BOUNDED_TOKEN {
+            // `len` carries the bound `N`; the single field that follows is
+            // the string/opaque/array whose length prefix it constrains.
+            self.bound = Some(u32::try_from(len).unwrap_or(u32::MAX));
+        }
         Ok(self)
     }
 
@@ -281,7 +473,7 @@ impl<'a, W: Write> ser::Serializer for &'a mut Serializer<W> {
     /// Map → 4-byte pair count + alternating key/value pairs
     fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap> {
         let l = len.ok_or(Error::LengthRequired)?;
-        self.write_u32(l as u32)?;
+        self.write_len(l)?;
         Ok(self)
     }
 
@@ -307,7 +499,7 @@ impl<'a, W: Write> ser::Serializer for &'a mut Serializer<W> {
 
 macro_rules! forward_serialize_element {
     ($t:ty) => {
-        impl<'a, W: Write> $t for &'a mut Serializer<W> {
+        impl<'a, W: Output> $t for &'a mut Serializer<W> {
             type Ok = ();
             type Error = Error;
             fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
@@ -322,7 +514,7 @@ macro_rules! forward_serialize_element {
 
 macro_rules! forward_serialize_field {
     ($t:ty) => {
-        impl<'a, W: Write> $t for &'a mut Serializer<W> {
+        impl<'a, W: Output> $t for &'a mut Serializer<W> {
             type Ok = ();
             type Error = Error;
             fn serialize_field<T: Serialize + ?Sized>(
@@ -342,18 +534,19 @@ macro_rules! forward_serialize_field {
 forward_serialize_element!(ser::SerializeSeq);
 forward_serialize_element!(ser::SerializeTuple);
 
-impl<'a, W: Write> ser::SerializeTupleStruct for &'a mut Serializer<W> {
+impl<W: Output> ser::SerializeTupleStruct for &mut Serializer<W> {
     type Ok = ();
     type Error = Error;
     fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
         value.serialize(&mut **self)
     }
     fn end(self) -> Result<()> {
+        self.bound = None;
         Ok(())
     }
 }
 
-impl<'a, W: Write> ser::SerializeTupleVariant for &'a mut Serializer<W> {
+impl<W: Output> ser::SerializeTupleVariant for &mut Serializer<W> {
     type Ok = ();
     type Error = Error;
     fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
@@ -364,7 +557,7 @@ impl<'a, W: Write> ser::SerializeTupleVariant for &'a mut Serializer<W> {
     }
 }
 
-impl<'a, W: Write> ser::SerializeMap for &'a mut Serializer<W> {
+impl<W: Output> ser::SerializeMap for &mut Serializer<W> {
     type Ok = ();
     type Error = Error;
     fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<()> {
@@ -387,9 +580,9 @@ forward_serialize_field!(ser::SerializeStructVariant);
 // call that `FixedOpaqueHelper` makes, and route it through `write_padded_bytes`
 // (no length prefix) instead of the normal `write_opaque_variable` (with prefix).
 
-struct FixedOpaqueSerializer<'a, W: Write>(&'a mut Serializer<W>);
+struct FixedOpaqueSerializer<'a, W: Output>(&'a mut Serializer<W>);
 
-impl<'a, W: Write> ser::Serializer for FixedOpaqueSerializer<'a, W> {
+impl<'a, W: Output> ser::Serializer for FixedOpaqueSerializer<'a, W> {
     type Ok = ();
     type Error = Error;
 
@@ -0,0 +1,106 @@
+//! Encoding shared by the result unions of the NFS-family protocols, which
+//! switch on a status and carry the results only when it is `OK`:
+//!
+//! ```text
+//! union XXXres switch (XXXstat status) {
+//! case XXX_OK:
+//!     XXXresok resok;
+//! default:
+//!     XXXresfail resfail;    /* often void */
+//! };
+//! ```
+//!
+//! Such a union is an enum with a variant per arm. Its `Serialize` impl
+//! hands the status and the arm's body to [`serialize`] or
+//! [`serialize_void`]; its `Deserialize` impl implements [`StatusUnion`] to
+//! pick the arm and calls [`deserialize`].
+
+use core::fmt;
+use core::marker::PhantomData;
+use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeTuple, Serializer};
+
+/// Encode `status` followed by `body`.
+pub(crate) fn serialize<S, St, B>(serializer: S, status: &St, body: &B) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    St: Serialize,
+    B: Serialize + ?Sized,
+{
+    let mut tuple = serializer.serialize_tuple(2)?;
+    tuple.serialize_element(status)?;
+    tuple.serialize_element(body)?;
+    tuple.end()
+}
+
+/// Encode `status` for an arm that is `void`.
+#[cfg(any(feature = "mount3", feature = "nfs4"))]
+pub(crate) fn serialize_void<S, St>(serializer: S, status: &St) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    St: Serialize,
+{
+    let mut tuple = serializer.serialize_tuple(1)?;
+    tuple.serialize_element(status)?;
+    tuple.end()
+}
+
+/// A union switched on a status, decoded by [`deserialize`].
+pub(crate) trait StatusUnion<'de>: Sized {
+    /// The discriminant.
+    type Status: Deserialize<'de>;
+
+    /// What the union is, for error messages.
+    const EXPECTING: &'static str;
+
+    /// Decode the arm `status` selects, reading its body, if any, from
+    /// `body`.
+    fn arm<A: SeqAccess<'de>>(status: Self::Status, body: ArmBody<A>) -> Result<Self, A::Error>;
+}
+
+/// What follows the status of a union being decoded.
+pub(crate) struct ArmBody<A> {
+    seq: A,
+    expecting: &'static str,
+}
+
+impl<'de, A: SeqAccess<'de>> ArmBody<A> {
+    /// Read the body of the selected arm.
+    pub(crate) fn read<T: Deserialize<'de>>(mut self) -> Result<T, A::Error> {
+        self.seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(1, &self.expecting))
+    }
+}
+
+/// Decode a [`StatusUnion`]: its status, then the arm that selects.
+pub(crate) fn deserialize<'de, D, U>(deserializer: D) -> Result<U, D::Error>
+where
+    D: Deserializer<'de>,
+    U: StatusUnion<'de>,
+{
+    deserializer.deserialize_tuple(2, UnionVisitor(PhantomData))
+}
+
+struct UnionVisitor<U>(PhantomData<U>);
+
+impl<'de, U: StatusUnion<'de>> Visitor<'de> for UnionVisitor<U> {
+    type Value = U;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(U::EXPECTING)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<U, A::Error> {
+        let status = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        U::arm(
+            status,
+            ArmBody {
+                seq,
+                expecting: U::EXPECTING,
+            },
+        )
+    }
+}
@@ -0,0 +1,136 @@
+use futures::executor::block_on;
+use serde::{Deserialize, Serialize};
+use xdr_serde::{
+    Error, from_async_reader, from_bytes, from_reader, from_tokio_reader, to_async_writer,
+    to_bytes, to_tokio_writer, to_writer,
+};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct StateId {
+    sequence_id: u32,
+    #[serde(with = "xdr_serde::fixed_opaque")]
+    other: [u8; 12],
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Entry {
+    fileid: u64,
+    name: String,
+    cookie: u64,
+}
+
+fn entries(n: u64) -> Vec<Entry> {
+    (0..n).map(|i| Entry { fileid: i, name: format!("file-{}", i), cookie: i + 1 }).collect()
+}
+
+// ══════════════════════════════════════════════════════════════════════════
+// futures::io
+// ══════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_from_async_reader_matches_from_reader() {
+    let value = entries(100);
+    let bytes = to_bytes(&value).unwrap();
+    // A tiny buffer forces the value to be decoded across many refills.
+    for capacity in [1, 7, 64, 8192] {
+        let reader = futures::io::BufReader::with_capacity(capacity, &bytes[..]);
+        let decoded: Vec<Entry> = block_on(from_async_reader(reader)).unwrap();
+        assert_eq!(decoded, value);
+    }
+    assert_eq!(from_reader::<_, Vec<Entry>>(&bytes[..]).unwrap(), value);
+}
+
+#[test]
+fn test_from_async_reader_consumes_only_the_value() {
+    let mut bytes = to_bytes(&"first".to_string()).unwrap();
+    bytes.extend(to_bytes(&0xDEAD_BEEFu32).unwrap());
+    let mut reader = futures::io::BufReader::with_capacity(16, &bytes[..]);
+    let first: String = block_on(from_async_reader(&mut reader)).unwrap();
+    let second: u32 = block_on(from_async_reader(&mut reader)).unwrap();
+    assert_eq!((first.as_str(), second), ("first", 0xDEAD_BEEF));
+}
+
+#[test]
+fn test_from_async_reader_large_array_then_trailer() {
+    // The count prefix lets the decoder wait for the whole array; the value
+    // after it must still be left in the reader.
+    let value: Vec<u32> = (0..100_000).collect();
+    let mut bytes = to_bytes(&value).unwrap();
+    bytes.extend(to_bytes(&0xDEAD_BEEFu32).unwrap());
+    let mut reader = futures::io::BufReader::with_capacity(64, &bytes[..]);
+    let decoded: Vec<u32> = block_on(from_async_reader(&mut reader)).unwrap();
+    let trailer: u32 = block_on(from_async_reader(&mut reader)).unwrap();
+    assert_eq!((decoded, trailer), (value, 0xDEAD_BEEF));
+
+    let value = vec![entries(3), entries(0), entries(5)];
+    let mut bytes = to_bytes(&value).unwrap();
+    bytes.extend(to_bytes(&7u32).unwrap());
+    for capacity in [1, 5, 64] {
+        let mut reader = futures::io::BufReader::with_capacity(capacity, &bytes[..]);
+        let decoded: Vec<Vec<Entry>> = block_on(from_async_reader(&mut reader)).unwrap();
+        let trailer: u32 = block_on(from_async_reader(&mut reader)).unwrap();
+        assert_eq!((&decoded, trailer), (&value, 7));
+    }
+}
+
+#[test]
+fn test_from_async_reader_eof() {
+    let bytes = [0u8, 0, 0];
+    assert_eq!(block_on(from_async_reader::<_, u32>(&bytes[..])), Err(Error::UnexpectedEof));
+    assert_eq!(from_reader::<_, u32>(&bytes[..]), Err(Error::UnexpectedEof));
+
+    // A length prefix promising more than the stream holds.
+    let bytes = [0u8, 0, 0, 8, b'a', b'b', 0, 0];
+    let reader = futures::io::BufReader::with_capacity(2, &bytes[..]);
+    assert_eq!(block_on(from_async_reader::<_, String>(reader)), Err(Error::UnexpectedEof));
+}
+
+#[test]
+fn test_from_async_reader_errors_match_sync() {
+    let bytes = [0u8, 0, 0, 2];
+    assert_eq!(block_on(from_async_reader::<_, bool>(&bytes[..])), Err(Error::InvalidBool(2)));
+    assert_eq!(from_reader::<_, bool>(&bytes[..]), Err(Error::InvalidBool(2)));
+}
+
+#[test]
+fn test_async_fixed_opaque() {
+    let id = StateId { sequence_id: 1, other: [0xAA; 12] };
+    let mut bytes = Vec::new();
+    block_on(to_async_writer(&mut bytes, &id)).unwrap();
+    assert_eq!(bytes.len(), 16);
+    let mut sync_bytes = Vec::new();
+    to_writer(&mut sync_bytes, &id).unwrap();
+    assert_eq!(bytes, sync_bytes);
+
+    let reader = futures::io::BufReader::with_capacity(3, &bytes[..]);
+    assert_eq!(block_on(from_async_reader::<_, StateId>(reader)).unwrap(), id);
+}
+
+// ══════════════════════════════════════════════════════════════════════════
+// tokio::io
+// ══════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_tokio_roundtrip() {
+    let value = entries(50);
+    let mut bytes = Vec::new();
+    block_on(to_tokio_writer(&mut bytes, &value)).unwrap();
+    assert_eq!(from_bytes::<Vec<Entry>>(&bytes).unwrap(), value);
+
+    let reader = tokio::io::BufReader::with_capacity(5, &bytes[..]);
+    let decoded: Vec<Entry> = block_on(from_tokio_reader(reader)).unwrap();
+    assert_eq!(decoded, value);
+}
+
+#[test]
+fn test_tokio_reader_eof_and_fixed_opaque() {
+    assert_eq!(block_on(from_tokio_reader::<_, u64>(&[0u8; 7][..])), Err(Error::UnexpectedEof));
+
+    let id = StateId { sequence_id: 9, other: *b"stateid-0001" };
+    let mut bytes = Vec::new();
+    block_on(to_tokio_writer(&mut bytes, &id)).unwrap();
+    bytes.extend([0, 0, 0, 1]);
+    let mut reader = tokio::io::BufReader::with_capacity(4, &bytes[..]);
+    assert_eq!(block_on(from_tokio_reader::<_, StateId>(&mut reader)).unwrap(), id);
+    assert!(block_on(from_tokio_reader::<_, bool>(&mut reader)).unwrap());
+}
@@ -0,0 +1,249 @@
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpListener, UdpSocket};
+use std::time::Duration;
+use xdr_serde::rpc::client::{CallError, Client};
+use xdr_serde::rpc::server::Server;
+use xdr_serde::rpc::*;
+use xdr_serde::{Error, from_bytes_partial, to_bytes};
+
+const PROG: u32 = 0x2000_0099;
+
+fn server() -> Server {
+    Server::new()
+        .with_procedure(PROG, 1, 0, |_, ()| ())
+        .with_procedure(PROG, 1, 1, |_, name: String| name.len() as u32)
+        .with_procedure(PROG, 1, 2, |call: &CallBody, ()| {
+            AuthSysParms::from_opaque_auth(&call.cred).map(|parms| parms.uid).ok()
+        })
+        .with_procedure(PROG, 2, 0, |_, ()| ())
+}
+
+fn spawn_tcp() -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    std::thread::spawn(move || server().serve_tcp(&listener));
+    addr
+}
+
+fn spawn_udp() -> SocketAddr {
+    let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let addr = socket.local_addr().unwrap();
+    std::thread::spawn(move || server().serve_udp(&socket));
+    addr
+}
+
+/// A UDP server that answers each datagram with `respond`, which may send any
+/// number of replies.
+fn spawn_udp_with<F>(mut respond: F) -> SocketAddr
+where
+    F: FnMut(&UdpSocket, SocketAddr, &[u8]) + Send + 'static,
+{
+    let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let addr = socket.local_addr().unwrap();
+    std::thread::spawn(move || {
+        let mut buf = [0; 65536];
+        loop {
+            let (n, peer) = socket.recv_from(&mut buf).unwrap();
+            respond(&socket, peer, &buf[..n]);
+        }
+    });
+    addr
+}
+
+fn xid_of(request: &[u8]) -> u32 {
+    from_bytes_partial::<RpcMsg>(request).unwrap().0.xid
+}
+
+// ══════════════════════════════════════════════════════════════════════════
+// Calls
+// ══════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_tcp_calls() {
+    let mut client = Client::tcp(spawn_tcp(), PROG, 1).unwrap();
+    client.call::<_, ()>(0, &()).unwrap();
+    for name in ["a", "loopback", ""] {
+        let len: u32 = client.call(1, &name).unwrap();
+        assert_eq!(len as usize, name.len());
+    }
+}
+
+#[test]
+fn test_udp_calls() {
+    let mut client = Client::udp(spawn_udp(), PROG, 1).unwrap();
+    let len: u32 = client.call(1, &"datagram").unwrap();
+    assert_eq!(len, 8);
+}
+
+#[test]
+fn test_credential_is_sent() {
+    let cred = AuthSysParms::new(1, "client", 501, 20, &[]).unwrap().to_opaque_auth().unwrap();
+    let mut client = Client::udp(spawn_udp(), PROG, 1).unwrap().with_credential(cred);
+    assert_eq!(client.call::<_, Option<u32>>(2, &()).unwrap(), Some(501));
+}
+
+// ══════════════════════════════════════════════════════════════════════════
+// Errors
+// ══════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_accepted_errors() {
+    let addr = spawn_tcp();
+    let mut client = Client::tcp(addr, PROG, 1).unwrap();
+    assert_eq!(client.call::<_, ()>(9, &()), Err(CallError::ProcUnavail));
+    assert_eq!(client.call::<_, u32>(1, &5u32), Err(CallError::GarbageArgs));
+
+    let mut client = Client::tcp(addr, PROG, 5).unwrap();
+    assert_eq!(
+        client.call::<_, ()>(0, &()),
+        Err(CallError::ProgMismatch(MismatchInfo { low: 1, high: 2 }))
+    );
+    let mut client = Client::tcp(addr, PROG + 1, 1).unwrap();
+    assert_eq!(client.call::<_, ()>(0, &()), Err(CallError::ProgUnavail));
+}
+
+#[test]
+fn test_results_that_do_not_decode() {
+    let mut client = Client::udp(spawn_udp(), PROG, 1).unwrap();
+    assert_eq!(client.call::<_, u64>(1, &"abc"), Err(CallError::Xdr(Error::UnexpectedEof)));
+}
+
+#[test]
+fn test_rejected_errors() {
+    let addr = spawn_udp_with(|socket, peer, request| {
+        let reply = RpcMsg {
+            xid: xid_of(request),
+            body: MsgBody::Reply(ReplyBody::Denied(RejectedReply::AuthError(AuthStat::TooWeak))),
+        };
+        socket.send_to(&to_bytes(&reply).unwrap(), peer).unwrap();
+    });
+    let mut client = Client::udp(addr, PROG, 1).unwrap();
+    assert_eq!(client.call::<_, ()>(0, &()), Err(CallError::AuthError(AuthStat::TooWeak)));
+}
+
+#[test]
+fn test_undecodable_reply_header() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut buf = [0; 1024];
+        let _ = stream.read(&mut buf).unwrap();
+        // The request's XID, then msg_type 7.
+        let mut reply = vec![0x80, 0, 0, 8];
+        reply.extend(&buf[4..8]);
+        reply.extend([0, 0, 0, 7]);
+        stream.write_all(&reply).unwrap();
+        let _ = stream.read(&mut buf);
+    });
+    let mut client = Client::tcp(addr, PROG, 1).unwrap();
+    assert_eq!(client.call::<_, ()>(0, &()), Err(CallError::Xdr(Error::InvalidDiscriminant(7))));
+}
+
+#[test]
+fn test_udp_skips_undecodable_datagrams() {
+    let server = server();
+    let addr = spawn_udp_with(move |socket, peer, request| {
+        // A truncated datagram and one with msg_type 7, then the real reply.
+        socket.send_to(&request[..2], peer).unwrap();
+        let mut junk = request[..4].to_vec();
+        junk.extend([0, 0, 0, 7]);
+        socket.send_to(&junk, peer).unwrap();
+        socket.send_to(&server.dispatch(request).unwrap(), peer).unwrap();
+    });
+    let mut client = Client::udp(addr, PROG, 1).unwrap();
+    assert_eq!(client.call::<_, u32>(1, &"junk").unwrap(), 4);
+}
+
+#[test]
+fn test_tcp_timeout_closes_connection() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut buf = [0; 1024];
+        let _ = stream.read(&mut buf).unwrap();
+        // Half of a record header, then nothing until the client hangs up.
+        stream.write_all(&[0x80, 0]).unwrap();
+        let _ = stream.read(&mut buf);
+    });
+    let mut client = Client::tcp(addr, PROG, 1).unwrap().with_timeout(Duration::from_millis(50));
+    assert_eq!(client.call::<_, ()>(0, &()), Err(CallError::TimedOut));
+    assert!(matches!(client.call::<_, ()>(0, &()), Err(CallError::Xdr(Error::Io(_)))));
+}
+
+// ══════════════════════════════════════════════════════════════════════════
+// XIDs and retransmission
+// ══════════════════════════════════════════════════════════════════════════
+
+#[test]
+#[should_panic(expected = "timeout must be greater than zero")]
+fn test_zero_timeout_rejected() {
+    let _ = Client::udp(spawn_udp(), PROG, 1).unwrap().with_timeout(Duration::ZERO);
+}
+
+#[test]
+fn test_udp_retransmits_after_timeout() {
+    let server = server();
+    let mut seen = Vec::new();
+    let addr = spawn_udp_with(move |socket, peer, request| {
+        // Drop the first two transmissions.
+        seen.push(xid_of(request));
+        if seen.len() > 2 {
+            assert!(seen.iter().all(|&xid| xid == seen[0]), "retransmissions keep the XID");
+            socket.send_to(&server.dispatch(request).unwrap(), peer).unwrap();
+        }
+    });
+    let mut client = Client::udp(addr, PROG, 1)
+        .unwrap()
+        .with_timeout(Duration::from_millis(50))
+        .with_retries(3);
+    assert_eq!(client.call::<_, u32>(1, &"retry").unwrap(), 5);
+}
+
+#[test]
+fn test_udp_times_out() {
+    let addr = spawn_udp_with(|_, _, _| {});
+    let mut client = Client::udp(addr, PROG, 1)
+        .unwrap()
+        .with_timeout(Duration::from_millis(20))
+        .with_retries(1);
+    assert_eq!(client.call::<_, ()>(0, &()), Err(CallError::TimedOut));
+}
+
+#[test]
+fn test_unknown_accept_stat() {
+    let addr = spawn_udp_with(|socket, peer, request| {
+        let reply = RpcMsg {
+            xid: xid_of(request),
+            body: MsgBody::Reply(ReplyBody::Accepted(AcceptedReply {
+                verf: OpaqueAuth::none(),
+                reply_data: ReplyData::Other(99),
+            })),
+        };
+        socket.send_to(&to_bytes(&reply).unwrap(), peer).unwrap();
+    });
+    let mut client = Client::udp(addr, PROG, 1).unwrap();
+    assert_eq!(client.call::<_, ()>(0, &()), Err(CallError::UnknownAcceptStat(99)));
+}
+
+#[test]
+fn test_replies_matched_by_xid() {
+    let server = server();
+    let addr = spawn_udp_with(move |socket, peer, request| {
+        // A stale reply to another XID, a call, then the real reply.
+        let stale = RpcMsg {
+            xid: xid_of(request).wrapping_sub(1),
+            body: MsgBody::Reply(ReplyBody::Accepted(AcceptedReply {
+                verf: OpaqueAuth::none(),
+                reply_data: ReplyData::SystemErr,
+            })),
+        };
+        socket.send_to(&to_bytes(&stale).unwrap(), peer).unwrap();
+        socket.send_to(request, peer).unwrap();
+        socket.send_to(&server.dispatch(request).unwrap(), peer).unwrap();
+    });
+    let mut client = Client::udp(addr, PROG, 1).unwrap();
+    assert_eq!(client.call::<_, u32>(1, &"xid").unwrap(), 3);
+    assert_eq!(client.call::<_, u32>(1, &"again").unwrap(), 5);
+}
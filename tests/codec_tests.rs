@@ -0,0 +1,100 @@
+use bytes::BytesMut;
+use serde::{Deserialize, Serialize};
+use tokio_util::codec::{Decoder, Encoder};
+use xdr_serde::rpc::codec::RecordCodec;
+use xdr_serde::rpc::record::RecordWriter;
+use xdr_serde::{DecodeLimits, Error};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Lookup {
+    dir: u64,
+    name: String,
+}
+
+fn lookup(name: &str) -> Lookup {
+    Lookup { dir: 0x0102_0304_0506_0708, name: name.to_string() }
+}
+
+// ══════════════════════════════════════════════════════════════════════════
+// Decoder
+// ══════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_decode_fragmented_records() {
+    // Two records written by the blocking writer in 8-byte fragments.
+    let mut writer = RecordWriter::new(Vec::new()).with_max_fragment_size(8);
+    writer.write_record(&lookup("a.txt")).unwrap();
+    writer.write_record(&lookup("")).unwrap();
+    let stream = writer.into_inner();
+
+    let mut codec = RecordCodec::<Lookup>::new();
+    let mut buf = BytesMut::new();
+    let mut decoded = Vec::new();
+    // Feed one byte at a time, as a slow socket might.
+    for &b in &stream {
+        buf.extend_from_slice(&[b]);
+        while let Some(value) = codec.decode(&mut buf).unwrap() {
+            decoded.push(value);
+        }
+    }
+    assert_eq!(decoded, [lookup("a.txt"), lookup("")]);
+    assert_eq!(codec.decode_eof(&mut buf), Ok(None));
+}
+
+#[test]
+fn test_decode_eof_mid_record() {
+    let mut codec = RecordCodec::<u32>::new();
+    let mut buf = BytesMut::from(&[0x00, 0, 0, 2, 0, 0, 0x80, 0][..]);
+    assert_eq!(codec.decode(&mut buf), Ok(None));
+    assert_eq!(codec.decode_eof(&mut buf), Err(Error::UnexpectedEof));
+}
+
+#[test]
+fn test_decode_max_record_size() {
+    let mut codec = RecordCodec::<Vec<u32>>::new().with_max_record_size(16);
+    // Rejected from the header alone, before the data arrives.
+    let mut buf = BytesMut::from(&[0x80, 0, 0, 20][..]);
+    assert_eq!(codec.decode(&mut buf), Err(Error::LengthOverflow { max: 16, got: 20 }));
+
+    // The limit covers the record, not each fragment.
+    let mut codec = RecordCodec::<Vec<u32>>::new().with_max_record_size(16);
+    let mut buf = BytesMut::from(&[0x00, 0, 0, 12, 0, 0, 0, 2, 0, 0, 0, 1, 0, 0, 0, 2][..]);
+    buf.extend_from_slice(&[0x80, 0, 0, 8]);
+    assert_eq!(codec.decode(&mut buf), Err(Error::LengthOverflow { max: 16, got: 20 }));
+}
+
+#[test]
+fn test_decode_limits() {
+    let limits = DecodeLimits { max_string_len: 4, ..DecodeLimits::default() };
+    let mut codec = RecordCodec::<Lookup>::new().with_limits(limits);
+    let mut buf = BytesMut::new();
+    codec.encode(&lookup("a.txt"), &mut buf).unwrap();
+    assert_eq!(codec.decode(&mut buf), Err(Error::LengthOverflow { max: 4, got: 5 }));
+}
+
+// ══════════════════════════════════════════════════════════════════════════
+// Encoder
+// ══════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_encode_fragments() {
+    let mut codec = RecordCodec::<()>::new().with_max_fragment_size(8);
+    let mut buf = BytesMut::new();
+    codec.encode((1u64, 2u32), &mut buf).unwrap();
+    codec.encode((), &mut buf).unwrap();
+    #[rustfmt::skip]
+    assert_eq!(buf[..], [
+        0x00, 0, 0, 8, 0, 0, 0, 0, 0, 0, 0, 1,
+        0x80, 0, 0, 4, 0, 0, 0, 2,
+        0x80, 0, 0, 0, // an empty record
+    ]);
+}
+
+#[test]
+fn test_encode_decode_roundtrip() {
+    let mut codec = RecordCodec::<Lookup>::new().with_max_fragment_size(4);
+    let mut buf = BytesMut::new();
+    codec.encode(&lookup("hello, world"), &mut buf).unwrap();
+    assert_eq!(codec.decode(&mut buf), Ok(Some(lookup("hello, world"))));
+    assert!(buf.is_empty());
+}
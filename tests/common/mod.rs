@@ -0,0 +1,25 @@
+//! Helpers shared by the protocol test files.
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::fmt::Debug;
+use xdr_serde::{Error, from_bytes, from_reader, to_bytes};
+
+/// Check that each value encodes as its discriminant and decodes back through
+/// both deserializers, and that each of `unknown` is rejected.
+pub fn assert_discriminants<T>(cases: &[(T, i32)], unknown: &[i32])
+where
+    T: Serialize + DeserializeOwned + PartialEq + Debug,
+{
+    for (value, discriminant) in cases {
+        let bytes = to_bytes(value).unwrap();
+        assert_eq!(bytes, discriminant.to_be_bytes(), "{:?}", value);
+        assert_eq!(&from_bytes::<T>(&bytes).unwrap(), value);
+        assert_eq!(&from_reader::<_, T>(&bytes[..]).unwrap(), value);
+    }
+    for &d in unknown {
+        let bytes = d.to_be_bytes();
+        assert_eq!(from_bytes::<T>(&bytes), Err(Error::InvalidDiscriminant(d)));
+        assert_eq!(from_reader::<_, T>(&bytes[..]), Err(Error::InvalidDiscriminant(d)));
+    }
+}
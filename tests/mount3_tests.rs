@@ -0,0 +1,152 @@
+mod common;
+
+use xdr_serde::mount3::*;
+use xdr_serde::rpc::{MsgBody, ReplyBody, ReplyData, RpcMsg};
+use xdr_serde::{Error, from_bytes, from_bytes_partial, from_reader, to_bytes};
+
+// ══════════════════════════════════════════════════════════════════════════
+// MNT
+// ══════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_mountstat3_values() {
+    let cases = [
+        (Mountstat3::Ok, 0), (Mountstat3::NoEnt, 2), (Mountstat3::Acces, 13),
+        (Mountstat3::NotDir, 20), (Mountstat3::NotSupp, 10004), (Mountstat3::ServerFault, 10006),
+    ];
+    common::assert_discriminants(&cases, &[3]);
+}
+
+// A synthetic reply to MNT("/export/home"), written by hand after RFC 1813
+// appendix I rather than captured, without its record mark.
+#[rustfmt::skip]
+const MNT_REPLY: [u8; 72] = [
+    0x5e, 0x11, 0x07, 0x42,                         // xid
+    0x00, 0x00, 0x00, 0x01,                         // REPLY
+    0x00, 0x00, 0x00, 0x00,                         // MSG_ACCEPTED
+    0x00, 0x00, 0x00, 0x00,  0x00, 0x00, 0x00, 0x00, // verf: AUTH_NONE
+    0x00, 0x00, 0x00, 0x00,                         // SUCCESS
+    0x00, 0x00, 0x00, 0x00,                         // MNT3_OK
+    0x00, 0x00, 0x00, 0x1c,                         // fhandle: 28 bytes
+    0x01, 0x00, 0x07, 0x00, 0x02, 0x00, 0xfd, 0x00, 0x00, 0x00, 0x00, 0x00, 0x8a, 0x3f,
+    0x5d, 0x1e, 0x9c, 0x40, 0x4b, 0x61, 0xa2, 0x20, 0x93, 0x11, 0x6c, 0x7e, 0x02, 0x00,
+    0x00, 0x00, 0x00, 0x02,                         // auth_flavors
+    0x00, 0x00, 0x00, 0x01,                         //   AUTH_SYS
+    0x00, 0x05, 0xf3, 0x73,                         //   RPCSEC_GSS krb5
+];
+
+#[test]
+fn test_mnt_reply_roundtrip() {
+    let (msg, rest) = from_bytes_partial::<RpcMsg>(&MNT_REPLY).unwrap();
+    let MsgBody::Reply(ReplyBody::Accepted(reply)) = &msg.body else { panic!("{:?}", msg.body) };
+    assert_eq!(reply.reply_data, ReplyData::Success);
+
+    let res: Mountres3 = from_bytes(rest).unwrap();
+    assert_eq!(res.status(), Mountstat3::Ok);
+    let mountinfo = res.clone().into_result().unwrap();
+    assert_eq!(mountinfo.fhandle.0.len(), 28);
+    assert_eq!(&mountinfo.fhandle.0[..4], [0x01, 0x00, 0x07, 0x00]);
+    assert_eq!(mountinfo.auth_flavors, [1, 390003]);
+
+    let mut encoded = to_bytes(&msg).unwrap();
+    encoded.extend(to_bytes(&res).unwrap());
+    assert_eq!(encoded, MNT_REPLY);
+}
+
+#[test]
+fn test_mountres3_err() {
+    let res = Mountres3::Err(Mountstat3::Acces);
+    assert_eq!(to_bytes(&res).unwrap(), [0, 0, 0, 13]);
+    assert_eq!(from_bytes::<Mountres3>(&[0, 0, 0, 13]).unwrap(), res);
+    assert_eq!(res.into_result(), Err(Mountstat3::Acces));
+
+    assert!(matches!(to_bytes(&Mountres3::Err(Mountstat3::Ok)), Err(Error::Message(_))));
+}
+
+#[test]
+fn test_fhandle3_bound() {
+    assert!(Fhandle3::new(vec![0; 64]).is_ok());
+    assert!(matches!(Fhandle3::new(vec![0; 65]), Err(Error::LengthOverflow { max: 64, got: 65 })));
+}
+
+// ══════════════════════════════════════════════════════════════════════════
+// EXPORT and DUMP
+// ══════════════════════════════════════════════════════════════════════════
+
+// Synthetic results of EXPORT, written by hand: /export/home to everyone,
+// /srv/nfs to a network and a host.
+#[rustfmt::skip]
+const EXPORT_RESULT: [u8; 96] = [
+    0x00, 0x00, 0x00, 0x01,                         // exports: more
+    0x00, 0x00, 0x00, 0x0c,  b'/', b'e', b'x', b'p', b'o', b'r', b't', b'/', b'h', b'o', b'm', b'e',
+    0x00, 0x00, 0x00, 0x01,                         //   groups: more
+    0x00, 0x00, 0x00, 0x01,  b'*', 0, 0, 0,
+    0x00, 0x00, 0x00, 0x00,                         //   groups: end
+    0x00, 0x00, 0x00, 0x01,                         // exports: more
+    0x00, 0x00, 0x00, 0x08,  b'/', b's', b'r', b'v', b'/', b'n', b'f', b's',
+    0x00, 0x00, 0x00, 0x01,                         //   groups: more
+    0x00, 0x00, 0x00, 0x0e,  b'1', b'9', b'2', b'.', b'1', b'6', b'8', b'.', b'1', b'.', b'0', b'/',
+    b'2', b'4', 0, 0,
+    0x00, 0x00, 0x00, 0x01,                         //   groups: more
+    0x00, 0x00, 0x00, 0x04,  b'h', b'o', b's', b't',
+    0x00, 0x00, 0x00, 0x00,                         //   groups: end
+    0x00, 0x00, 0x00, 0x00,                         // exports: end
+];
+
+#[test]
+fn test_exports_roundtrip() {
+    let exports: Exports = from_bytes(&EXPORT_RESULT).unwrap();
+    assert_eq!(exports.dirs().collect::<Vec<_>>(), ["/export/home", "/srv/nfs"]);
+    assert_eq!(exports.get("/export/home").unwrap().groups().collect::<Vec<_>>(), ["*"]);
+    assert_eq!(exports.get("/srv/nfs").unwrap().groups().collect::<Vec<_>>(), ["192.168.1.0/24", "host"]);
+    assert!(exports.get("/srv").is_none());
+
+    let built = Exports(vec![
+        ExportNode::new("/export/home", &["*"]).unwrap(),
+        ExportNode::new("/srv/nfs", &["192.168.1.0/24", "host"]).unwrap(),
+    ]);
+    assert_eq!(exports, built);
+    assert_eq!(to_bytes(&built).unwrap(), EXPORT_RESULT);
+    assert_eq!(from_reader::<_, Exports>(&EXPORT_RESULT[..]).unwrap(), built);
+}
+
+#[test]
+fn test_exports_empty_groups() {
+    let exports = Exports(vec![ExportNode::new("/", &[]).unwrap()]);
+    let bytes = to_bytes(&exports).unwrap();
+    assert_eq!(bytes, [0, 0, 0, 1, 0, 0, 0, 1, b'/', 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(from_bytes::<Exports>(&bytes).unwrap(), exports);
+    assert_eq!(to_bytes(&Exports::default()).unwrap(), [0; 4]);
+}
+
+#[test]
+fn test_group_name_bound() {
+    let long = "g".repeat(256);
+    assert!(matches!(ExportNode::new("/srv", &[&long]), Err(Error::LengthOverflow { max: 255, got: 256 })));
+
+    let mut bytes = vec![0, 0, 0, 1, 0, 0, 0, 1, b'/', 0, 0, 0, 0, 0, 0, 1, 0, 0, 1, 0];
+    bytes.extend([b'g'; 256]);
+    assert!(matches!(from_bytes::<Exports>(&bytes), Err(Error::LengthOverflow { max: 255, .. })));
+}
+
+#[test]
+fn test_mountlist_roundtrip() {
+    // Synthetic results of DUMP, written by hand.
+    #[rustfmt::skip]
+    let dump = [
+        0, 0, 0, 1,                                   // more
+        0, 0, 0, 6,  b'c', b'l', b'i', b'e', b'n', b't', 0, 0,
+        0, 0, 0, 12,  b'/', b'e', b'x', b'p', b'o', b'r', b't', b'/', b'h', b'o', b'm', b'e',
+        0, 0, 0, 1,                                   // more
+        0, 0, 0, 6,  b'c', b'l', b'i', b'e', b'n', b't', 0, 0,
+        0, 0, 0, 8,  b'/', b's', b'r', b'v', b'/', b'n', b'f', b's',
+        0, 0, 0, 0,                                   // end
+    ];
+    let list: MountList = from_bytes(&dump).unwrap();
+    assert_eq!(list.0.len(), 2);
+    assert_eq!(list.directories("client").collect::<Vec<_>>(), ["/export/home", "/srv/nfs"]);
+    assert_eq!(list.directories("other").count(), 0);
+    assert_eq!(to_bytes(&list).unwrap(), dump);
+
+    assert_eq!(from_bytes::<MountList>(&[0; 4]).unwrap(), MountList::default());
+}
@@ -0,0 +1,224 @@
+mod common;
+
+use xdr_serde::nfs3::*;
+use xdr_serde::{ByteBuf, Error, from_bytes, from_reader, to_bytes};
+
+fn fh(byte: u8) -> NfsFh3 {
+    NfsFh3::new(vec![byte; 8]).unwrap()
+}
+
+fn fattr(r#type: Ftype3) -> Fattr3 {
+    Fattr3 {
+        r#type, mode: 0o644, nlink: 1, uid: 1000, gid: 1000,
+        size: 12345, used: 16384,
+        rdev: Specdata3 { specdata1: 0, specdata2: 0 },
+        fsid: 0xABCD_EF01_2345_6789, fileid: 1,
+        atime: Nfstime3 { seconds: 1700000000, nseconds: 0 },
+        mtime: Nfstime3 { seconds: 1700000001, nseconds: 500000000 },
+        ctime: Nfstime3 { seconds: 1700000001, nseconds: 500000000 },
+    }
+}
+
+// ══════════════════════════════════════════════════════════════════════════
+// Basic types
+// ══════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_nfsstat3_values() {
+    let cases = [
+        (Nfsstat3::Ok, 0), (Nfsstat3::Perm, 1), (Nfsstat3::NoEnt, 2), (Nfsstat3::Acces, 13),
+        (Nfsstat3::NotEmpty, 66), (Nfsstat3::Stale, 70), (Nfsstat3::BadHandle, 10001),
+        (Nfsstat3::Jukebox, 10008),
+    ];
+    // 3 and 4 are not NFSv3 errors.
+    common::assert_discriminants(&cases, &[3, 4]);
+}
+
+#[test]
+fn test_ftype3_starts_at_one() {
+    assert_eq!(to_bytes(&Ftype3::Reg).unwrap(), [0, 0, 0, 1]);
+    assert_eq!(to_bytes(&Ftype3::Fifo).unwrap(), [0, 0, 0, 7]);
+    assert!(from_bytes::<Ftype3>(&[0, 0, 0, 0]).is_err());
+}
+
+#[test]
+fn test_fattr3_layout() {
+    let attr = fattr(Ftype3::Dir);
+    let bytes = to_bytes(&attr).unwrap();
+    assert_eq!(bytes.len(), 84);
+    assert_eq!(&bytes[..4], [0, 0, 0, 2]);
+    assert_eq!(&bytes[4..8], 0o644u32.to_be_bytes());
+    assert_eq!(&bytes[20..28], 12345u64.to_be_bytes());
+    assert_eq!(&bytes[44..52], 0xABCD_EF01_2345_6789u64.to_be_bytes());
+    assert_eq!(&bytes[80..84], 500000000u32.to_be_bytes());
+    assert_eq!(from_bytes::<Fattr3>(&bytes).unwrap(), attr);
+    assert_eq!(from_reader::<_, Fattr3>(&bytes[..]).unwrap(), attr);
+}
+
+#[test]
+fn test_nfs_fh3_bound() {
+    assert!(NfsFh3::new(vec![0; 64]).is_ok());
+    assert!(matches!(NfsFh3::new(vec![0; 65]), Err(Error::LengthOverflow { max: 64, got: 65 })));
+
+    let mut bytes = vec![0, 0, 0, 65];
+    bytes.extend([0; 68]);
+    assert!(matches!(from_bytes::<NfsFh3>(&bytes), Err(Error::LengthOverflow { .. })));
+}
+
+#[test]
+fn test_sattr3_encoding() {
+    // Nothing to set: four FALSEs and two DONT_CHANGEs.
+    assert_eq!(to_bytes(&Sattr3::default()).unwrap(), [0; 24]);
+
+    let sattr = Sattr3 {
+        mode: Some(0o755),
+        size: Some(0),
+        atime: SetAtime::SetToServerTime,
+        mtime: SetMtime::SetToClientTime(Nfstime3 { seconds: 7, nseconds: 8 }),
+        ..Sattr3::default()
+    };
+    #[rustfmt::skip]
+    let expected = [
+        0, 0, 0, 1,  0, 0, 0x01, 0xED,   // set_mode3
+        0, 0, 0, 0,                      // set_uid3
+        0, 0, 0, 0,                      // set_gid3
+        0, 0, 0, 1,  0, 0, 0, 0, 0, 0, 0, 0, // set_size3
+        0, 0, 0, 1,                      // set_atime: SET_TO_SERVER_TIME
+        0, 0, 0, 2,  0, 0, 0, 7, 0, 0, 0, 8, // set_mtime: SET_TO_CLIENT_TIME
+    ];
+    assert_eq!(to_bytes(&sattr).unwrap(), expected);
+    assert_eq!(from_bytes::<Sattr3>(&expected).unwrap(), sattr);
+}
+
+#[test]
+fn test_wcc_data_roundtrip() {
+    let wcc = WccData {
+        before: Some(WccAttr { size: 10, mtime: Nfstime3::default(), ctime: Nfstime3::default() }),
+        after: Some(fattr(Ftype3::Reg)),
+    };
+    let bytes = to_bytes(&wcc).unwrap();
+    assert_eq!(bytes.len(), 4 + 24 + 4 + 84);
+    assert_eq!(from_bytes::<WccData>(&bytes).unwrap(), wcc);
+    assert_eq!(to_bytes(&WccData::default()).unwrap(), [0; 8]);
+}
+
+// ══════════════════════════════════════════════════════════════════════════
+// Procedures
+// ══════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_result_ok_and_err() {
+    let ok: Getattr3Res = Nfs3Res::Ok(Getattr3Resok { obj_attributes: fattr(Ftype3::Reg) });
+    let bytes = to_bytes(&ok).unwrap();
+    assert_eq!(&bytes[..4], [0, 0, 0, 0]);
+    assert_eq!(bytes.len(), 4 + 84);
+    assert_eq!(from_bytes::<Getattr3Res>(&bytes).unwrap(), ok);
+
+    // GETATTR3res has a void resfail.
+    let err: Getattr3Res = Nfs3Res::Err(Nfsstat3::Stale, ());
+    assert_eq!(to_bytes(&err).unwrap(), [0, 0, 0, 70]);
+    assert_eq!(from_bytes::<Getattr3Res>(&[0, 0, 0, 70]).unwrap(), err);
+    assert_eq!(err.status(), Nfsstat3::Stale);
+    assert_eq!(err.into_result(), Err((Nfsstat3::Stale, ())));
+
+    let err: Access3Res = Nfs3Res::Err(Nfsstat3::Acces, Access3Resfail { obj_attributes: None });
+    assert_eq!(to_bytes(&err).unwrap(), [0, 0, 0, 13, 0, 0, 0, 0]);
+}
+
+#[test]
+fn test_result_err_with_ok_status_rejected() {
+    let bogus: Getattr3Res = Nfs3Res::Err(Nfsstat3::Ok, ());
+    assert!(matches!(to_bytes(&bogus), Err(Error::Message(_))));
+}
+
+#[test]
+fn test_readdir3res_entries() {
+    let res: Readdir3Res = Nfs3Res::Ok(Readdir3Resok {
+        dir_attributes: None,
+        cookieverf: Cookieverf3([0xAB; 8]),
+        reply: Dirlist3 {
+            entries: vec![
+                Entry3 { fileid: 2, name: ".".into(), cookie: 1 },
+                Entry3 { fileid: 1, name: "..".into(), cookie: 2 },
+            ],
+            eof: true,
+        },
+    });
+    #[rustfmt::skip]
+    let expected = [
+        0, 0, 0, 0,                         // NFS3_OK
+        0, 0, 0, 0,                         // dir_attributes: none
+        0xAB, 0xAB, 0xAB, 0xAB, 0xAB, 0xAB, 0xAB, 0xAB, // cookieverf
+        0, 0, 0, 1,                         // entries
+        0, 0, 0, 0, 0, 0, 0, 2,             // fileid
+        0, 0, 0, 1,  b'.', 0, 0, 0,         // name
+        0, 0, 0, 0, 0, 0, 0, 1,             // cookie
+        0, 0, 0, 1,                         // nextentry
+        0, 0, 0, 0, 0, 0, 0, 1,
+        0, 0, 0, 2,  b'.', b'.', 0, 0,
+        0, 0, 0, 0, 0, 0, 0, 2,
+        0, 0, 0, 0,                         // nextentry: null
+        0, 0, 0, 1,                         // eof
+    ];
+    assert_eq!(to_bytes(&res).unwrap(), expected);
+    assert_eq!(from_bytes::<Readdir3Res>(&expected).unwrap(), res);
+    assert_eq!(from_reader::<_, Readdir3Res>(&expected[..]).unwrap(), res);
+}
+
+#[test]
+fn test_readdirplus3_roundtrip() {
+    let res: Readdirplus3Res = Nfs3Res::Ok(Readdirplus3Resok {
+        dir_attributes: Some(fattr(Ftype3::Dir)),
+        cookieverf: Cookieverf3::default(),
+        reply: Dirlistplus3 {
+            entries: vec![Entryplus3 {
+                fileid: 3, name: "file".into(), cookie: 3,
+                name_attributes: Some(fattr(Ftype3::Reg)), name_handle: Some(fh(3)),
+            }],
+            eof: false,
+        },
+    });
+    let bytes = to_bytes(&res).unwrap();
+    assert_eq!(from_bytes::<Readdirplus3Res>(&bytes).unwrap(), res);
+}
+
+#[test]
+fn test_createhow3_and_mknoddata3() {
+    let args = Create3Args {
+        r#where: Diropargs3 { dir: fh(1), name: "new".into() },
+        how: Createhow3::Exclusive(Createverf3(*b"verifier")),
+    };
+    let bytes = to_bytes(&args).unwrap();
+    assert_eq!(&bytes[bytes.len() - 12..bytes.len() - 8], [0, 0, 0, 2]);
+    assert_eq!(&bytes[bytes.len() - 8..], b"verifier");
+    assert_eq!(from_bytes::<Create3Args>(&bytes).unwrap(), args);
+
+    let spec = Specdata3 { specdata1: 8, specdata2: 1 };
+    let arms = [
+        (Mknoddata3::Reg, 4),
+        (Mknoddata3::Chr(Devicedata3 { dev_attributes: Sattr3::default(), spec }), 4 + 24 + 8),
+        (Mknoddata3::Fifo(Sattr3::default()), 4 + 24),
+    ];
+    for (what, len) in arms {
+        let bytes = to_bytes(&what).unwrap();
+        assert_eq!(bytes.len(), len, "{:?}", what);
+        assert_eq!(from_bytes::<Mknoddata3>(&bytes).unwrap(), what);
+    }
+}
+
+#[test]
+fn test_write3args_data_padding() {
+    let args = Write3Args {
+        file: fh(9),
+        offset: 4096,
+        count: 5,
+        stable: StableHow::FileSync,
+        data: ByteBuf::from(b"hello".to_vec()),
+    };
+    let bytes = to_bytes(&args).unwrap();
+    // fh, offset, count, stable, then length-prefixed data padded to 8.
+    assert_eq!(bytes.len(), 12 + 8 + 4 + 4 + 4 + 8);
+    assert_eq!(&bytes[24..28], [0, 0, 0, 2]);
+    assert_eq!(&bytes[32..], [b'h', b'e', b'l', b'l', b'o', 0, 0, 0]);
+    assert_eq!(from_bytes::<Write3Args>(&bytes).unwrap(), args);
+}
@@ -0,0 +1,371 @@
+mod common;
+
+use xdr_serde::nfs4::attr::*;
+use xdr_serde::nfs4::*;
+use xdr_serde::rpc::{AuthFlavor, AuthSysParms, MsgBody, ReplyBody, ReplyData, RpcMsg};
+use xdr_serde::{ByteBuf, Error, from_bytes, from_bytes_partial, from_reader, to_bytes};
+
+const SESSIONID: [u8; 16] = [0x65, 0xf0, 0x9a, 0x3c, 0x01, 0, 0, 0, 0x02, 0, 0, 0, 0, 0, 0, 0];
+
+fn stateid(seqid: u32) -> Stateid4 {
+    Stateid4 { seqid, other: [0x11; 12] }
+}
+
+fn owner(name: &[u8]) -> StateOwner4 {
+    StateOwner4 { clientid: 0x65f0_9a3c_0000_0001, owner: name.try_into().unwrap() }
+}
+
+/// Strip the record mark from a single-fragment record.
+fn record(bytes: &[u8]) -> &[u8] {
+    let mark = u32::from_be_bytes(bytes[..4].try_into().unwrap());
+    assert_eq!(mark & 0x8000_0000, 0x8000_0000);
+    assert_eq!((mark & 0x7fff_ffff) as usize, bytes.len() - 4);
+    &bytes[4..]
+}
+
+// ══════════════════════════════════════════════════════════════════════════
+// Basic types
+// ══════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_nfsstat4_values() {
+    let cases = [
+        (Nfsstat4::Ok, 0), (Nfsstat4::Noent, 2), (Nfsstat4::Stale, 70),
+        (Nfsstat4::Badhandle, 10001), (Nfsstat4::Denied, 10010), (Nfsstat4::OpIllegal, 10044),
+        (Nfsstat4::Badsession, 10052), (Nfsstat4::ClientidBusy, 10074),
+        (Nfsstat4::DelegRevoked, 10087),
+    ];
+    // 19 is NFS3ERR_NODEV, 10073 is unassigned.
+    common::assert_discriminants(&cases, &[19, 10073]);
+}
+
+#[test]
+fn test_stateid4_and_fh_encoding() {
+    let bytes = to_bytes(&stateid(3)).unwrap();
+    assert_eq!(bytes.len(), 16);
+    assert_eq!(&bytes[..4], [0, 0, 0, 3]);
+    assert_eq!(from_bytes::<Stateid4>(&bytes).unwrap(), stateid(3));
+
+    assert!(NfsFh4::new(vec![0; 128]).is_ok());
+    assert!(matches!(NfsFh4::new(vec![0; 129]), Err(Error::LengthOverflow { max: 128, got: 129 })));
+    assert_eq!(to_bytes(&Verifier4(*b"verifier")).unwrap(), b"verifier");
+}
+
+#[test]
+fn test_fattr4_layout() {
+    let attrs = Fattr4 { attrmask: vec![0x12], attr_vals: ByteBuf::from(vec![0; 12]) };
+    let bytes = to_bytes(&attrs).unwrap();
+    assert_eq!(&bytes[..12], [0, 0, 0, 1, 0, 0, 0, 0x12, 0, 0, 0, 12]);
+    assert_eq!(bytes.len(), 24);
+    assert_eq!(from_bytes::<Fattr4>(&bytes).unwrap(), attrs);
+}
+
+// ══════════════════════════════════════════════════════════════════════════
+// Operations
+// ══════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_result_ok_and_err() {
+    let ok: Getfh4Res = Nfs4Res::Ok(Getfh4Resok { object: NfsFh4::new(vec![7; 8]).unwrap() });
+    let bytes = to_bytes(&ok).unwrap();
+    assert_eq!(bytes.len(), 4 + 4 + 8);
+    assert_eq!(from_bytes::<Getfh4Res>(&bytes).unwrap(), ok);
+
+    let err: Getfh4Res = Nfs4Res::Err(Nfsstat4::Nofilehandle);
+    assert_eq!(to_bytes(&err).unwrap(), 10020u32.to_be_bytes());
+    assert_eq!(from_bytes::<Getfh4Res>(&10020u32.to_be_bytes()).unwrap(), err);
+    assert_eq!(err.into_result(), Err(Nfsstat4::Nofilehandle));
+
+    let bogus: Putfh4Res = Nfs4Res::Err(Nfsstat4::Ok);
+    assert!(matches!(to_bytes(&bogus), Err(Error::Message(_))));
+}
+
+#[test]
+fn test_lock4res_denied() {
+    let denied = Lock4Res::Denied(Lock4Denied {
+        offset: 0, length: u64::MAX, locktype: NfsLockType4::WriteLt, owner: owner(b"lock"),
+    });
+    let bytes = to_bytes(&denied).unwrap();
+    assert_eq!(&bytes[..4], 10010u32.to_be_bytes());
+    assert_eq!(from_bytes::<Lock4Res>(&bytes).unwrap(), denied);
+    assert_eq!(denied.status(), Nfsstat4::Denied);
+
+    let ok = Lock4Res::Ok(Lock4Resok { lock_stateid: stateid(1) });
+    assert_eq!(from_bytes::<Lock4Res>(&to_bytes(&ok).unwrap()).unwrap(), ok);
+    let err = Lock4Res::Err(Nfsstat4::Grace);
+    assert_eq!(from_bytes::<Lock4Res>(&to_bytes(&err).unwrap()).unwrap(), err);
+
+    // DENIED has its own arm.
+    assert!(matches!(to_bytes(&Lock4Res::Err(Nfsstat4::Denied)), Err(Error::Message(_))));
+}
+
+#[test]
+fn test_open4_roundtrip() {
+    let args = NfsArgop4::Open(Open4Args {
+        seqid: 0,
+        share_access: OPEN4_SHARE_ACCESS_BOTH | OPEN4_SHARE_ACCESS_WANT_NO_DELEG,
+        share_deny: OPEN4_SHARE_DENY_NONE,
+        owner: owner(b"open id:\x00\x00\x00\x2a"),
+        openhow: Openflag4::Open4Create(Createhow4::Exclusive4_1(Creatverfattr {
+            cva_verf: Verifier4(*b"\x65\xf0\x9a\x3c\x00\x00\x00\x07"),
+            cva_attrs: Fattr4::default(),
+        })),
+        claim: OpenClaim4::ClaimNull("new.txt".into()),
+    });
+    let bytes = to_bytes(&args).unwrap();
+    assert_eq!(&bytes[..4], [0, 0, 0, 18]);
+    assert_eq!(from_bytes::<NfsArgop4>(&bytes).unwrap(), args);
+
+    let res = NfsResop4::Open(Nfs4Res::Ok(Open4Resok {
+        stateid: stateid(1),
+        cinfo: ChangeInfo4 { atomic: true, before: 10, after: 11 },
+        rflags: OPEN4_RESULT_LOCKTYPE_POSIX,
+        attrset: vec![0, 0x2],
+        delegation: OpenDelegation4::OpenDelegateNoneExt(OpenNoneDelegation4::Wnd4NotWanted),
+    }));
+    assert_eq!(from_bytes::<NfsResop4>(&to_bytes(&res).unwrap()).unwrap(), res);
+    assert_eq!(res.status(), Nfsstat4::Ok);
+}
+
+#[test]
+fn test_unknown_opnum_rejected() {
+    // Opnum 2 is unassigned; 59 is an NFSv4.2 operation.
+    assert!(matches!(from_bytes::<NfsArgop4>(&[0, 0, 0, 2]), Err(Error::InvalidDiscriminant(2))));
+    assert!(matches!(from_bytes::<NfsResop4>(&[0, 0, 0, 59]), Err(Error::InvalidDiscriminant(59))));
+    assert_eq!(to_bytes(&NfsArgop4::Illegal).unwrap(), 10044u32.to_be_bytes());
+}
+
+// ══════════════════════════════════════════════════════════════════════════
+// COMPOUND
+// ══════════════════════════════════════════════════════════════════════════
+
+// A synthetic NFSv4.1 SEQUENCE; PUTROOTFH; GETFH; GETATTR(type, size),
+// written by hand after RFC 8881 rather than captured, as a TCP record.
+#[rustfmt::skip]
+const CALL: [u8; 144] = [
+    0x80, 0x00, 0x00, 0x8c,                         // record mark: last, 140 bytes
+    0x3b, 0x2a, 0x1c, 0x0d,                         // xid
+    0x00, 0x00, 0x00, 0x00,                         // CALL
+    0x00, 0x00, 0x00, 0x02,                         // rpcvers
+    0x00, 0x01, 0x86, 0xa3,                         // NFS4_PROGRAM
+    0x00, 0x00, 0x00, 0x04,                         // NFS_V4
+    0x00, 0x00, 0x00, 0x01,                         // NFSPROC4_COMPOUND
+    0x00, 0x00, 0x00, 0x01,  0x00, 0x00, 0x00, 0x20, // cred: AUTH_SYS, 32 bytes
+    0x65, 0xf0, 0x9a, 0x3c,                         //   stamp
+    0x00, 0x00, 0x00, 0x06,  b'c', b'l', b'i', b'e', b'n', b't', 0, 0,
+    0x00, 0x00, 0x03, 0xe8,                         //   uid
+    0x00, 0x00, 0x03, 0xe8,                         //   gid
+    0x00, 0x00, 0x00, 0x01,  0x00, 0x00, 0x03, 0xe8, //  gids
+    0x00, 0x00, 0x00, 0x00,  0x00, 0x00, 0x00, 0x00, // verf: AUTH_NONE
+    0x00, 0x00, 0x00, 0x00,                         // tag
+    0x00, 0x00, 0x00, 0x01,                         // minorversion
+    0x00, 0x00, 0x00, 0x04,                         // argarray
+    0x00, 0x00, 0x00, 0x35,                         // OP_SEQUENCE
+    0x65, 0xf0, 0x9a, 0x3c, 0x01, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x2a,                         //   sa_sequenceid
+    0x00, 0x00, 0x00, 0x00,                         //   sa_slotid
+    0x00, 0x00, 0x00, 0x00,                         //   sa_highest_slotid
+    0x00, 0x00, 0x00, 0x00,                         //   sa_cachethis
+    0x00, 0x00, 0x00, 0x18,                         // OP_PUTROOTFH
+    0x00, 0x00, 0x00, 0x0a,                         // OP_GETFH
+    0x00, 0x00, 0x00, 0x09,                         // OP_GETATTR
+    0x00, 0x00, 0x00, 0x01,  0x00, 0x00, 0x00, 0x12, //   attr_request: type, size
+];
+
+// The reply to CALL, equally synthetic, as a TCP record.
+#[rustfmt::skip]
+const REPLY: [u8; 144] = [
+    0x80, 0x00, 0x00, 0x8c,                         // record mark: last, 140 bytes
+    0x3b, 0x2a, 0x1c, 0x0d,                         // xid
+    0x00, 0x00, 0x00, 0x01,                         // REPLY
+    0x00, 0x00, 0x00, 0x00,                         // MSG_ACCEPTED
+    0x00, 0x00, 0x00, 0x00,  0x00, 0x00, 0x00, 0x00, // verf: AUTH_NONE
+    0x00, 0x00, 0x00, 0x00,                         // SUCCESS
+    0x00, 0x00, 0x00, 0x00,                         // NFS4_OK
+    0x00, 0x00, 0x00, 0x00,                         // tag
+    0x00, 0x00, 0x00, 0x04,                         // resarray
+    0x00, 0x00, 0x00, 0x35,  0x00, 0x00, 0x00, 0x00, // OP_SEQUENCE: NFS4_OK
+    0x65, 0xf0, 0x9a, 0x3c, 0x01, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x2a,                         //   sr_sequenceid
+    0x00, 0x00, 0x00, 0x00,                         //   sr_slotid
+    0x00, 0x00, 0x00, 0x00,                         //   sr_highest_slotid
+    0x00, 0x00, 0x00, 0x1d,                         //   sr_target_highest_slotid
+    0x00, 0x00, 0x00, 0x00,                         //   sr_status_flags
+    0x00, 0x00, 0x00, 0x18,  0x00, 0x00, 0x00, 0x00, // OP_PUTROOTFH: NFS4_OK
+    0x00, 0x00, 0x00, 0x0a,  0x00, 0x00, 0x00, 0x00, // OP_GETFH: NFS4_OK
+    0x00, 0x00, 0x00, 0x08,  0x01, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x09,  0x00, 0x00, 0x00, 0x00, // OP_GETATTR: NFS4_OK
+    0x00, 0x00, 0x00, 0x01,  0x00, 0x00, 0x00, 0x12, //   attrmask: type, size
+    0x00, 0x00, 0x00, 0x0c,                         //   attr_vals
+    0x00, 0x00, 0x00, 0x02,                         //     NF4DIR
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x10, 0x00, //     4096
+];
+
+#[test]
+fn test_compound4args_from_packet() {
+    let (msg, rest) = from_bytes_partial::<RpcMsg>(record(&CALL)).unwrap();
+    assert_eq!(msg.xid, 0x3b2a1c0d);
+    let MsgBody::Call(call) = &msg.body else { panic!("not a call: {:?}", msg.body) };
+    assert_eq!((call.prog, call.vers, call.proc), (NFS4_PROGRAM, NFS_V4, NFSPROC4_COMPOUND));
+    assert_eq!(call.cred.flavor, AuthFlavor::Sys);
+    assert_eq!(AuthSysParms::from_opaque_auth(&call.cred).unwrap().machinename.as_str(), "client");
+
+    let args: Compound4Args = from_bytes(rest).unwrap();
+    let expected = Compound4Args {
+        tag: String::new(),
+        minorversion: 1,
+        argarray: vec![
+            NfsArgop4::Sequence(Sequence4Args {
+                sa_sessionid: Sessionid4(SESSIONID),
+                sa_sequenceid: 42, sa_slotid: 0, sa_highest_slotid: 0, sa_cachethis: false,
+            }),
+            NfsArgop4::Putrootfh,
+            NfsArgop4::Getfh,
+            NfsArgop4::Getattr(Getattr4Args { attr_request: vec![0x12] }),
+        ],
+    };
+    assert_eq!(args, expected);
+
+    let mut encoded = to_bytes(&msg).unwrap();
+    encoded.extend(to_bytes(&args).unwrap());
+    assert_eq!(encoded, record(&CALL));
+}
+
+#[test]
+fn test_compound4res_from_packet() {
+    let (msg, rest) = from_bytes_partial::<RpcMsg>(record(&REPLY)).unwrap();
+    let MsgBody::Reply(ReplyBody::Accepted(reply)) = &msg.body else { panic!("{:?}", msg.body) };
+    assert_eq!(reply.reply_data, ReplyData::Success);
+
+    let res: Compound4Res = from_bytes(rest).unwrap();
+    assert_eq!(res.status, Nfsstat4::Ok);
+    assert_eq!(res.resarray.len(), 4);
+    let NfsResop4::Sequence(Nfs4Res::Ok(seq)) = &res.resarray[0] else { panic!() };
+    assert_eq!(seq.sr_sessionid, Sessionid4(SESSIONID));
+    assert_eq!(seq.sr_target_highest_slotid, 29);
+    assert_eq!(res.resarray[1], NfsResop4::Putrootfh(Nfs4Res::Ok(())));
+    let NfsResop4::Getfh(Nfs4Res::Ok(fh)) = &res.resarray[2] else { panic!() };
+    assert_eq!(fh.object, NfsFh4::new(vec![1, 0, 1, 0, 0, 0, 0, 0]).unwrap());
+    let NfsResop4::Getattr(Nfs4Res::Ok(attr)) = &res.resarray[3] else { panic!() };
+    assert_eq!(attr.obj_attributes.attrmask, [0x12]);
+    let attrs = Attrs4::from_fattr4(&attr.obj_attributes).unwrap();
+    assert_eq!(attrs, Attrs4 { r#type: Some(NfsFtype4::Nf4dir), size: Some(4096), ..Attrs4::default() });
+
+    let mut encoded = to_bytes(&msg).unwrap();
+    encoded.extend(to_bytes(&res).unwrap());
+    assert_eq!(encoded, record(&REPLY));
+    assert_eq!(from_reader::<_, Compound4Res>(rest).unwrap(), res);
+}
+
+#[test]
+fn test_compound4res_stops_at_error() {
+    let res = Compound4Res {
+        status: Nfsstat4::Noent,
+        tag: "lookup".into(),
+        resarray: vec![
+            NfsResop4::Putfh(Nfs4Res::Ok(())),
+            NfsResop4::Lookup(Nfs4Res::Err(Nfsstat4::Noent)),
+        ],
+    };
+    #[rustfmt::skip]
+    let expected = [
+        0, 0, 0, 2,                                   // NFS4ERR_NOENT
+        0, 0, 0, 6,  b'l', b'o', b'o', b'k', b'u', b'p', 0, 0,
+        0, 0, 0, 2,                                   // resarray
+        0, 0, 0, 22,  0, 0, 0, 0,                     // OP_PUTFH: NFS4_OK
+        0, 0, 0, 15,  0, 0, 0, 2,                     // OP_LOOKUP: NFS4ERR_NOENT
+    ];
+    assert_eq!(to_bytes(&res).unwrap(), expected);
+    assert_eq!(from_bytes::<Compound4Res>(&expected).unwrap(), res);
+    assert_eq!(res.resarray.last().unwrap().status(), res.status);
+}
+
+// ══════════════════════════════════════════════════════════════════════════
+// Attributes
+// ══════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_bitmap4() {
+    assert_eq!(bitmap4(&[]), Vec::<u32>::new());
+    assert_eq!(bitmap4(&[FATTR4_TYPE, FATTR4_SIZE]), [0x12]);
+    assert_eq!(bitmap4(&[FATTR4_MODE, FATTR4_TIME_MODIFY_SET]), [0, 0x0040_0002]);
+    assert_eq!(bitmap4(&[FATTR4_SUPPATTR_EXCLCREAT]), [0, 0, 1 << 11]);
+}
+
+#[test]
+fn test_attrs4_packing() {
+    let attrs = Attrs4 {
+        change: Some(0x0102_0304_0506_0708),
+        mode: Some(0o640),
+        owner: Some("alice@example.com".into()),
+        time_modify_set: Some(Settime4::SetToServerTime4),
+        ..Attrs4::default()
+    };
+    let fattr = attrs.to_fattr4().unwrap();
+    assert_eq!(fattr.attrmask, [1 << FATTR4_CHANGE, (1 << 1) | (1 << 4) | (1 << 22)]);
+    #[rustfmt::skip]
+    let expected = [
+        1, 2, 3, 4, 5, 6, 7, 8,                      // change
+        0, 0, 0x01, 0xa0,                            // mode
+        0, 0, 0, 17,  b'a', b'l', b'i', b'c', b'e', b'@', b'e', b'x',
+        b'a', b'm', b'p', b'l', b'e', b'.', b'c', b'o', b'm', 0, 0, 0, // owner
+        0, 0, 0, 0,                                  // time_modify_set: SET_TO_SERVER_TIME4
+    ];
+    assert_eq!(fattr.attr_vals.as_slice(), expected);
+    assert_eq!(Attrs4::from_fattr4(&fattr).unwrap(), attrs);
+
+    // Through SETATTR and back.
+    let args = Setattr4Args { stateid: Stateid4::default(), obj_attributes: fattr };
+    let decoded: Setattr4Args = from_bytes(&to_bytes(&args).unwrap()).unwrap();
+    assert_eq!(Attrs4::from_fattr4(&decoded.obj_attributes).unwrap(), attrs);
+
+    let empty = Attrs4::default().to_fattr4().unwrap();
+    assert_eq!(to_bytes(&empty).unwrap(), [0; 8]);
+    assert_eq!(Attrs4::from_fattr4(&empty).unwrap(), Attrs4::default());
+}
+
+#[test]
+fn test_attrs4_nested_values() {
+    let attrs = Attrs4 {
+        supported_attrs: Some(bitmap4(&[FATTR4_TYPE, FATTR4_FILEHANDLE, FATTR4_DACL])),
+        filehandle: Some(NfsFh4::new(vec![9; 20]).unwrap()),
+        fs_locations: Some(FsLocations4 {
+            fs_root: vec!["export".into(), "home".into()],
+            locations: vec![FsLocation4 { server: vec!["nfs2".into()], rootpath: vec!["home".into()] }],
+        }),
+        dacl: Some(Nfsacl41 {
+            na41_flag: 0,
+            na41_aces: vec![Nfsace4 { r#type: 0, flag: 0, access_mask: 0x1, who: "EVERYONE@".into() }],
+        }),
+        retention_get: Some(RetentionGet4 { rg_duration: 60, rg_begin_time: None }),
+        ..Attrs4::default()
+    };
+    let fattr = attrs.to_fattr4().unwrap();
+    assert_eq!(fattr.attrmask.len(), 3);
+    assert_eq!(Attrs4::from_fattr4(&fattr).unwrap(), attrs);
+}
+
+#[test]
+fn test_attrs4_unknown_bit_rejected() {
+    // Bit 77 is past fs_charset_cap (76), the last NFSv4.1 attribute; its
+    // value could be any size.
+    let fattr = Fattr4 { attrmask: vec![0x10, 0, 1 << 13], attr_vals: ByteBuf::from(vec![0; 12]) };
+    assert!(matches!(Attrs4::from_fattr4(&fattr), Err(Error::InvalidDiscriminant(77))));
+
+    // Trailing zero words are allowed.
+    let fattr = Fattr4 { attrmask: vec![0x10, 0, 0], attr_vals: ByteBuf::from(vec![0; 8]) };
+    assert_eq!(Attrs4::from_fattr4(&fattr).unwrap().size, Some(0));
+}
+
+#[test]
+fn test_attrs4_length_mismatch_rejected() {
+    // size is a uint64_t, so four bytes are too few and twelve too many.
+    let short = Fattr4 { attrmask: vec![0x10], attr_vals: ByteBuf::from(vec![0; 4]) };
+    assert!(matches!(Attrs4::from_fattr4(&short), Err(Error::UnexpectedEof)));
+    let long = Fattr4 { attrmask: vec![0x10], attr_vals: ByteBuf::from(vec![0; 12]) };
+    assert!(matches!(Attrs4::from_fattr4(&long), Err(Error::Message(_))));
+
+    // An enum value is checked like any other.
+    let bad_type = Fattr4 { attrmask: vec![0x2], attr_vals: ByteBuf::from(vec![0; 4]) };
+    assert!(matches!(Attrs4::from_fattr4(&bad_type), Err(Error::InvalidDiscriminant(0))));
+}
@@ -0,0 +1,202 @@
+mod common;
+
+use xdr_serde::nlm4::*;
+use xdr_serde::rpc::{AuthFlavor, MsgBody, RpcMsg};
+use xdr_serde::{Error, from_bytes, from_bytes_partial, from_reader, to_bytes};
+
+fn netobj(bytes: &[u8]) -> Netobj {
+    bytes.try_into().unwrap()
+}
+
+fn alock() -> Nlm4Lock {
+    Nlm4Lock {
+        caller_name: "client".try_into().unwrap(),
+        fh: netobj(&[0x01, 0x00, 0x07, 0x00, 0x02, 0x00, 0xfd, 0x00, 0x8a, 0x3f, 0x5d, 0x1e]),
+        oh: netobj(b"1234@client"),
+        svid: 1234,
+        l_offset: 0x1_0000_0000,
+        l_len: 4096,
+    }
+}
+
+// ══════════════════════════════════════════════════════════════════════════
+// Status
+// ══════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_nlm4_stats_values() {
+    let cases = [
+        (Nlm4Stats::Granted, 0), (Nlm4Stats::Denied, 1), (Nlm4Stats::DeniedNolocks, 2),
+        (Nlm4Stats::Blocked, 3), (Nlm4Stats::DeniedGracePeriod, 4), (Nlm4Stats::Deadlck, 5),
+        (Nlm4Stats::Rofs, 6), (Nlm4Stats::StaleFh, 7), (Nlm4Stats::Fbig, 8), (Nlm4Stats::Failed, 9),
+    ];
+    common::assert_discriminants(&cases, &[10, -1]);
+}
+
+#[test]
+fn test_nlm4_res_roundtrip() {
+    let res = Nlm4Res { cookie: netobj(&[0, 0, 0, 7]), stat: Nlm4Stats::DeniedGracePeriod };
+    let bytes = to_bytes(&res).unwrap();
+    assert_eq!(bytes, [0, 0, 0, 4, 0, 0, 0, 7, 0, 0, 0, 4]);
+    assert_eq!(from_bytes::<Nlm4Res>(&bytes).unwrap(), res);
+    assert_eq!(from_reader::<_, Nlm4Res>(&bytes[..]).unwrap(), res);
+
+    // An empty cookie is allowed.
+    let bytes = [0, 0, 0, 0, 0, 0, 0, 0];
+    assert_eq!(from_bytes::<Nlm4Res>(&bytes).unwrap(), Nlm4Res { cookie: Netobj::default(), stat: Nlm4Stats::Granted });
+}
+
+// ══════════════════════════════════════════════════════════════════════════
+// LOCK and TEST
+// ══════════════════════════════════════════════════════════════════════════
+
+// An NLMPROC4_LOCK call for 4096 bytes at offset 4 GiB, without its record
+// mark.
+#[rustfmt::skip]
+const LOCK_CALL: [u8; 128] = [
+    0x6a, 0x01, 0x22, 0x5c,                         // xid
+    0x00, 0x00, 0x00, 0x00,                         // CALL
+    0x00, 0x00, 0x00, 0x02,                         // rpcvers
+    0x00, 0x01, 0x86, 0xb5,                         // NLM_PROG
+    0x00, 0x00, 0x00, 0x04,                         // NLM4_VERS
+    0x00, 0x00, 0x00, 0x02,                         // NLMPROC4_LOCK
+    0x00, 0x00, 0x00, 0x00,  0x00, 0x00, 0x00, 0x00, // cred: AUTH_NONE
+    0x00, 0x00, 0x00, 0x00,  0x00, 0x00, 0x00, 0x00, // verf: AUTH_NONE
+    0x00, 0x00, 0x00, 0x04,  0x00, 0x00, 0x00, 0x07, // cookie
+    0x00, 0x00, 0x00, 0x01,                         // block
+    0x00, 0x00, 0x00, 0x01,                         // exclusive
+    0x00, 0x00, 0x00, 0x06,  b'c', b'l', b'i', b'e', b'n', b't', 0, 0,
+    0x00, 0x00, 0x00, 0x0c,                         // fh
+    0x01, 0x00, 0x07, 0x00, 0x02, 0x00, 0xfd, 0x00, 0x8a, 0x3f, 0x5d, 0x1e,
+    0x00, 0x00, 0x00, 0x0b,  b'1', b'2', b'3', b'4', b'@', b'c', b'l', b'i', b'e', b'n', b't', 0,
+    0x00, 0x00, 0x04, 0xd2,                         // svid
+    0x00, 0x00, 0x00, 0x01,  0x00, 0x00, 0x00, 0x00, // l_offset
+    0x00, 0x00, 0x00, 0x00,  0x00, 0x00, 0x10, 0x00, // l_len
+    0x00, 0x00, 0x00, 0x00,                         // reclaim
+    0x00, 0x00, 0x00, 0x03,                         // state
+];
+
+#[test]
+fn test_lockargs_from_packet() {
+    let (msg, rest) = from_bytes_partial::<RpcMsg>(&LOCK_CALL).unwrap();
+    let MsgBody::Call(call) = &msg.body else { panic!("not a call: {:?}", msg.body) };
+    assert_eq!((call.prog, call.vers, call.proc), (NLM_PROG, NLM4_VERS, NLMPROC4_LOCK));
+    assert_eq!(call.cred.flavor, AuthFlavor::None);
+
+    let expected = Nlm4Lockargs {
+        cookie: netobj(&[0, 0, 0, 7]),
+        block: true,
+        exclusive: true,
+        alock: alock(),
+        reclaim: false,
+        state: 3,
+    };
+    assert_eq!(from_bytes::<Nlm4Lockargs>(rest).unwrap(), expected);
+    assert_eq!(from_reader::<_, Nlm4Lockargs>(rest).unwrap(), expected);
+
+    let mut encoded = to_bytes(&msg).unwrap();
+    encoded.extend(to_bytes(&expected).unwrap());
+    assert_eq!(encoded, LOCK_CALL);
+}
+
+#[test]
+fn test_testres_denied() {
+    let holder = Nlm4Holder { exclusive: true, svid: 99, oh: netobj(b"99@other"), l_offset: 0, l_len: 0 };
+    let res = Nlm4Testres { cookie: netobj(&[1]), stat: Nlm4Testrply::Denied(holder.clone()) };
+    #[rustfmt::skip]
+    let expected = [
+        0, 0, 0, 1,  1, 0, 0, 0,                      // cookie
+        0, 0, 0, 1,                                   // NLM4_DENIED
+        0, 0, 0, 1,                                   // exclusive
+        0, 0, 0, 99,                                  // svid
+        0, 0, 0, 8,  b'9', b'9', b'@', b'o', b't', b'h', b'e', b'r',
+        0, 0, 0, 0, 0, 0, 0, 0,                       // l_offset
+        0, 0, 0, 0, 0, 0, 0, 0,                       // l_len
+    ];
+    assert_eq!(to_bytes(&res).unwrap(), expected);
+    assert_eq!(from_bytes::<Nlm4Testres>(&expected).unwrap(), res);
+    assert_eq!(from_reader::<_, Nlm4Testres>(&expected[..]).unwrap(), res);
+    assert_eq!(res.stat.stat(), Nlm4Stats::Denied);
+
+    // Every other status has no body.
+    let granted = Nlm4Testres { cookie: netobj(&[1]), stat: Nlm4Testrply::Granted };
+    let bytes = to_bytes(&granted).unwrap();
+    assert_eq!(bytes, [0, 0, 0, 1, 1, 0, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(from_reader::<_, Nlm4Testres>(&bytes[..]).unwrap(), granted);
+    let grace: Nlm4Testres = from_bytes(&[0, 0, 0, 0, 0, 0, 0, 4]).unwrap();
+    assert_eq!(grace.stat.stat(), Nlm4Stats::DeniedGracePeriod);
+}
+
+#[test]
+fn test_unlock_and_cancel_roundtrip() {
+    let unlock = Nlm4Unlockargs { cookie: netobj(&[2]), alock: alock() };
+    let bytes = to_bytes(&unlock).unwrap();
+    assert_eq!(from_bytes::<Nlm4Unlockargs>(&bytes).unwrap(), unlock);
+    assert_eq!(from_reader::<_, Nlm4Unlockargs>(&bytes[..]).unwrap(), unlock);
+
+    let cancel = Nlm4Cancargs { cookie: netobj(&[3]), block: true, exclusive: false, alock: alock() };
+    let bytes = to_bytes(&cancel).unwrap();
+    // cookie, block and exclusive come before the same lock as UNLOCK's.
+    assert_eq!(&bytes[16..], &to_bytes(&unlock).unwrap()[8..]);
+    assert_eq!(from_bytes::<Nlm4Cancargs>(&bytes).unwrap(), cancel);
+    assert_eq!(from_reader::<_, Nlm4Cancargs>(&bytes[..]).unwrap(), cancel);
+}
+
+#[test]
+fn test_caller_name_bound() {
+    let mut lock = alock();
+    lock.caller_name = "h".repeat(1024).try_into().unwrap();
+    let bytes = to_bytes(&lock).unwrap();
+    assert_eq!(from_bytes::<Nlm4Lock>(&bytes).unwrap(), lock);
+
+    let mut long = vec![0, 0, 0x04, 0x01];
+    long.extend([b'h'; 1028]);
+    assert!(matches!(from_bytes::<Nlm4Lock>(&long), Err(Error::LengthOverflow { max: 1024, got: 1025 })));
+    assert!(matches!(from_reader::<_, Nlm4Lock>(&long[..]), Err(Error::LengthOverflow { max: 1024, .. })));
+}
+
+// ══════════════════════════════════════════════════════════════════════════
+// SHARE and FREE_ALL
+// ══════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_share_roundtrip() {
+    let args = Nlm4Shareargs {
+        cookie: netobj(&[4]),
+        share: Nlm4Share {
+            caller_name: "client".try_into().unwrap(),
+            fh: netobj(&[0xfe; 8]),
+            oh: netobj(b"owner"),
+            mode: Fsh4Mode::Dw,
+            access: Fsh4Access::Rw,
+        },
+        reclaim: false,
+    };
+    let bytes = to_bytes(&args).unwrap();
+    // mode and access follow the oh; reclaim ends the arguments.
+    assert_eq!(&bytes[bytes.len() - 12..], [0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0, 0]);
+    assert_eq!(from_bytes::<Nlm4Shareargs>(&bytes).unwrap(), args);
+    assert_eq!(from_reader::<_, Nlm4Shareargs>(&bytes[..]).unwrap(), args);
+
+    let res = Nlm4Shareres { cookie: netobj(&[4]), stat: Nlm4Stats::Granted, sequence: 0 };
+    let bytes = to_bytes(&res).unwrap();
+    assert_eq!(bytes, [0, 0, 0, 1, 4, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(from_reader::<_, Nlm4Shareres>(&bytes[..]).unwrap(), res);
+
+    assert!(matches!(from_bytes::<Fsh4Mode>(&[0, 0, 0, 4]), Err(Error::InvalidDiscriminant(4))));
+}
+
+#[test]
+fn test_notify_roundtrip() {
+    let notify = Nlm4Notify { name: "client".try_into().unwrap(), state: 5 };
+    let bytes = to_bytes(&notify).unwrap();
+    assert_eq!(bytes, [0, 0, 0, 6, b'c', b'l', b'i', b'e', b'n', b't', 0, 0, 0, 0, 0, 5]);
+    assert_eq!(from_bytes::<Nlm4Notify>(&bytes).unwrap(), notify);
+    assert_eq!(from_reader::<_, Nlm4Notify>(&bytes[..]).unwrap(), notify);
+
+    // LM_MAXNAMELEN is one more than LM_MAXSTRLEN.
+    let mut long = vec![0, 0, 0x04, 0x01];
+    long.extend([b'h'; 1028]);
+    long.extend([0, 0, 0, 5]);
+    assert_eq!(from_bytes::<Nlm4Notify>(&long).unwrap().name.len(), LM_MAXNAMELEN as usize);
+}
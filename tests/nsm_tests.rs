@@ -0,0 +1,125 @@
+use xdr_serde::nsm::*;
+use xdr_serde::rpc::{MsgBody, RpcMsg};
+use xdr_serde::{Error, from_bytes, from_bytes_partial, from_reader, to_bytes};
+
+fn my_id() -> MyId {
+    MyId { my_name: "client".try_into().unwrap(), my_prog: 100021, my_vers: 4, my_proc: 16 }
+}
+
+// ══════════════════════════════════════════════════════════════════════════
+// SM_MON
+// ══════════════════════════════════════════════════════════════════════════
+
+// An SM_MON call from the local lock manager, without its record mark.
+#[rustfmt::skip]
+const MON_CALL: [u8; 92] = [
+    0x21, 0x9e, 0x40, 0x03,                         // xid
+    0x00, 0x00, 0x00, 0x00,                         // CALL
+    0x00, 0x00, 0x00, 0x02,                         // rpcvers
+    0x00, 0x01, 0x86, 0xb8,                         // SM_PROG
+    0x00, 0x00, 0x00, 0x01,                         // SM_VERS
+    0x00, 0x00, 0x00, 0x02,                         // SM_MON
+    0x00, 0x00, 0x00, 0x00,  0x00, 0x00, 0x00, 0x00, // cred: AUTH_NONE
+    0x00, 0x00, 0x00, 0x00,  0x00, 0x00, 0x00, 0x00, // verf: AUTH_NONE
+    0x00, 0x00, 0x00, 0x06,  b's', b'e', b'r', b'v', b'e', b'r', 0, 0,
+    0x00, 0x00, 0x00, 0x06,  b'c', b'l', b'i', b'e', b'n', b't', 0, 0,
+    0x00, 0x01, 0x86, 0xb5,                         // my_prog: NLM_PROG
+    0x00, 0x00, 0x00, 0x04,                         // my_vers
+    0x00, 0x00, 0x00, 0x10,                         // my_proc
+    0x00, 0x00, 0x00, 0x2a, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+];
+
+#[test]
+fn test_mon_from_packet() {
+    let (msg, rest) = from_bytes_partial::<RpcMsg>(&MON_CALL).unwrap();
+    let MsgBody::Call(call) = &msg.body else { panic!("not a call: {:?}", msg.body) };
+    assert_eq!((call.prog, call.vers, call.proc), (SM_PROG, SM_VERS, SM_MON));
+
+    let mut r#priv = [0; SM_PRIV_SIZE];
+    r#priv[3] = 42;
+    let expected = Mon { mon_id: MonId { mon_name: "server".try_into().unwrap(), my_id: my_id() }, r#priv };
+    assert_eq!(from_bytes::<Mon>(rest).unwrap(), expected);
+    assert_eq!(from_reader::<_, Mon>(rest).unwrap(), expected);
+
+    let mut encoded = to_bytes(&msg).unwrap();
+    encoded.extend(to_bytes(&expected).unwrap());
+    assert_eq!(encoded, MON_CALL);
+}
+
+#[test]
+fn test_sm_stat_res_roundtrip() {
+    let ok = SmStatRes { res_stat: Res::StatSucc, state: 7 };
+    let bytes = to_bytes(&ok).unwrap();
+    assert_eq!(bytes, [0, 0, 0, 0, 0, 0, 0, 7]);
+    assert_eq!(from_bytes::<SmStatRes>(&bytes).unwrap(), ok);
+    assert_eq!(from_reader::<_, SmStatRes>(&bytes[..]).unwrap(), ok);
+
+    let fail = SmStatRes { res_stat: Res::StatFail, state: -1 };
+    let bytes = to_bytes(&fail).unwrap();
+    assert_eq!(bytes, [0, 0, 0, 1, 0xff, 0xff, 0xff, 0xff]);
+    assert_eq!(from_reader::<_, SmStatRes>(&bytes[..]).unwrap(), fail);
+
+    assert_eq!((Res::StatSucc.discriminant(), Res::StatFail.discriminant()), (0, 1));
+    assert!(matches!(from_bytes::<SmStatRes>(&[0, 0, 0, 2, 0, 0, 0, 7]), Err(Error::InvalidDiscriminant(2))));
+    assert!(matches!(from_reader::<_, SmStatRes>(&[0, 0, 0, 0, 0, 0][..]), Err(Error::UnexpectedEof)));
+}
+
+#[test]
+fn test_priv_is_fixed_length() {
+    let mon = Mon { mon_id: MonId::default(), r#priv: [0xaa; SM_PRIV_SIZE] };
+    let bytes = to_bytes(&mon).unwrap();
+    // Two empty strings and three ints, then sixteen bytes with no length.
+    assert_eq!(bytes.len(), 4 + 4 + 12 + 16);
+    assert_eq!(&bytes[20..], [0xaa; 16]);
+    assert!(matches!(from_bytes::<Mon>(&bytes[..35]), Err(Error::UnexpectedEof)));
+    assert!(matches!(from_reader::<_, Mon>(&bytes[..35]), Err(Error::UnexpectedEof)));
+}
+
+// ══════════════════════════════════════════════════════════════════════════
+// Other procedures
+// ══════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_unmon_and_stat_roundtrip() {
+    let name = SmName { mon_name: "server".try_into().unwrap() };
+    let bytes = to_bytes(&name).unwrap();
+    assert_eq!(bytes, [0, 0, 0, 6, b's', b'e', b'r', b'v', b'e', b'r', 0, 0]);
+    assert_eq!(from_reader::<_, SmName>(&bytes[..]).unwrap(), name);
+
+    let id = MonId { mon_name: "server".try_into().unwrap(), my_id: my_id() };
+    let bytes = to_bytes(&id).unwrap();
+    assert_eq!(from_bytes::<MonId>(&bytes).unwrap(), id);
+    assert_eq!(from_reader::<_, MonId>(&bytes[..]).unwrap(), id);
+    assert_eq!(from_bytes::<MyId>(&bytes[12..]).unwrap(), my_id());
+
+    let stat = SmStat { state: 9 };
+    assert_eq!(to_bytes(&stat).unwrap(), [0, 0, 0, 9]);
+    assert_eq!(from_reader::<_, SmStat>(&[0, 0, 0, 9][..]).unwrap(), stat);
+}
+
+#[test]
+fn test_notify_and_callback_roundtrip() {
+    let notify = StatChge { mon_name: "server".try_into().unwrap(), state: 11 };
+    let bytes = to_bytes(&notify).unwrap();
+    assert_eq!(bytes, [0, 0, 0, 6, b's', b'e', b'r', b'v', b'e', b'r', 0, 0, 0, 0, 0, 11]);
+    assert_eq!(from_bytes::<StatChge>(&bytes).unwrap(), notify);
+    assert_eq!(from_reader::<_, StatChge>(&bytes[..]).unwrap(), notify);
+
+    // The callback to my_proc carries the new state and the caller's priv.
+    let status = Status { mon_name: notify.mon_name.clone(), state: notify.state, r#priv: [7; SM_PRIV_SIZE] };
+    let encoded = to_bytes(&status).unwrap();
+    assert_eq!(&encoded[..16], bytes);
+    assert_eq!(from_bytes::<Status>(&encoded).unwrap(), status);
+    assert_eq!(from_reader::<_, Status>(&encoded[..]).unwrap(), status);
+}
+
+#[test]
+fn test_mon_name_bound() {
+    assert!(SmString::try_from("h".repeat(1024).as_str()).is_ok());
+    assert!(matches!(SmString::try_from("h".repeat(1025).as_str()), Err(Error::LengthOverflow { max: 1024, got: 1025 })));
+
+    let mut long = vec![0, 0, 0x04, 0x01];
+    long.extend([b'h'; 1028]);
+    assert!(matches!(from_bytes::<SmName>(&long), Err(Error::LengthOverflow { max: 1024, got: 1025 })));
+    assert!(matches!(from_reader::<_, SmName>(&long[..]), Err(Error::LengthOverflow { max: 1024, .. })));
+}
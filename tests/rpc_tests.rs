@@ -0,0 +1,355 @@
+use std::io::{Read, Write};
+use xdr_serde::bounded::BoundedBytes;
+use xdr_serde::rpc::record::{RecordReader, RecordWriter};
+use xdr_serde::rpc::*;
+use xdr_serde::{Error, from_bytes, from_bytes_partial, from_reader, to_bytes};
+
+fn reply(xid: u32, body: ReplyBody) -> RpcMsg {
+    RpcMsg { xid, body: MsgBody::Reply(body) }
+}
+
+fn accepted(reply_data: ReplyData) -> ReplyBody {
+    ReplyBody::Accepted(AcceptedReply { verf: OpaqueAuth::none(), reply_data })
+}
+
+fn roundtrip(msg: &RpcMsg) -> Vec<u8> {
+    let bytes = to_bytes(msg).unwrap();
+    assert_eq!(*msg, from_bytes(&bytes).unwrap());
+    assert_eq!(*msg, from_reader(std::io::Cursor::new(&bytes)).unwrap());
+    bytes
+}
+
+// ══════════════════════════════════════════════════════════════════════════
+// Calls
+// ══════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_call_wire_format() {
+    // A portmapper GETPORT call header as sent by rpcinfo, with AUTH_SYS.
+    let cred = OpaqueAuth::new(AuthFlavor::Sys, vec![0, 0, 0, 1, 0, 0, 0, 0]).unwrap();
+    let msg = RpcMsg {
+        xid: 0x5F3A_0001,
+        body: MsgBody::Call(CallBody { cred, ..CallBody::new(100000, 2, 3) }),
+    };
+    let bytes = roundtrip(&msg);
+    #[rustfmt::skip]
+    assert_eq!(bytes, [
+        0x5F, 0x3A, 0x00, 0x01, // xid
+        0, 0, 0, 0,             // CALL
+        0, 0, 0, 2,             // rpcvers
+        0, 0x01, 0x86, 0xA0,    // prog 100000
+        0, 0, 0, 2,             // vers
+        0, 0, 0, 3,             // proc GETPORT
+        0, 0, 0, 1,             // cred: AUTH_SYS
+        0, 0, 0, 8, 0, 0, 0, 1, 0, 0, 0, 0,
+        0, 0, 0, 0,             // verf: AUTH_NONE
+        0, 0, 0, 0,
+    ]);
+    assert_eq!(msg.body.msg_type(), MsgType::Call);
+}
+
+#[test]
+fn test_call_arguments_follow_header() {
+    let msg = RpcMsg { xid: 1, body: MsgBody::Call(CallBody::new(100003, 3, 0)) };
+    let mut bytes = to_bytes(&msg).unwrap();
+    bytes.extend(to_bytes(&"/export".to_string()).unwrap());
+    let (header, args) = from_bytes_partial::<RpcMsg>(&bytes).unwrap();
+    assert_eq!(header, msg);
+    assert_eq!(from_bytes::<String>(args).unwrap(), "/export");
+}
+
+// ══════════════════════════════════════════════════════════════════════════
+// Replies
+// ══════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_accepted_replies() {
+    let ok = reply(7, accepted(ReplyData::Success));
+    assert_eq!(roundtrip(&ok), [0, 0, 0, 7, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+
+    let mismatch = reply(8, accepted(ReplyData::ProgMismatch(MismatchInfo { low: 2, high: 4 })));
+    let bytes = roundtrip(&mismatch);
+    assert_eq!(&bytes[20..], [0, 0, 0, 2, 0, 0, 0, 2, 0, 0, 0, 4]);
+
+    for (data, stat) in [
+        (ReplyData::ProgUnavail, AcceptStat::ProgUnavail),
+        (ReplyData::ProcUnavail, AcceptStat::ProcUnavail),
+        (ReplyData::GarbageArgs, AcceptStat::GarbageArgs),
+        (ReplyData::SystemErr, AcceptStat::SystemErr),
+        (ReplyData::Other(6), AcceptStat::Other(6)),
+    ] {
+        let bytes = roundtrip(&reply(9, accepted(data)));
+        assert_eq!(bytes[20..], stat.discriminant().to_be_bytes());
+        assert_eq!(data.stat(), stat);
+        assert_eq!(data.discriminant(), stat.discriminant());
+    }
+}
+
+#[test]
+fn test_rejected_replies() {
+    let rpc = reply(1, ReplyBody::Denied(RejectedReply::RpcMismatch(MismatchInfo { low: 2, high: 2 })));
+    assert_eq!(
+        roundtrip(&rpc),
+        [0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0, 2]
+    );
+
+    let auth = ReplyBody::Denied(RejectedReply::AuthError(AuthStat::TooWeak));
+    assert_eq!(auth.stat(), ReplyStat::MsgDenied);
+    let bytes = roundtrip(&reply(2, auth));
+    assert_eq!(&bytes[8..], [0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0, 5]);
+
+    let gss = RejectedReply::AuthError(AuthStat::RpcsecGssCtxProblem);
+    assert_eq!(to_bytes(&gss).unwrap(), [0, 0, 0, 1, 0, 0, 0, 14]);
+    assert_eq!(gss.stat(), RejectStat::AuthError);
+}
+
+#[test]
+fn test_invalid_discriminants() {
+    // mtype 2
+    assert_eq!(from_bytes::<RpcMsg>(&[0, 0, 0, 1, 0, 0, 0, 2]), Err(Error::InvalidDiscriminant(2)));
+    // accept_stat 6 takes the default arm of reply_data.
+    let mut bytes = to_bytes(&reply(1, accepted(ReplyData::Success))).unwrap();
+    bytes[23] = 6;
+    assert_eq!(from_bytes::<RpcMsg>(&bytes), Ok(reply(1, accepted(ReplyData::Other(6)))));
+    // auth_stat 15 takes the default arm of auth_stat.
+    assert_eq!(from_bytes::<AuthStat>(&[0, 0, 0, 15]), Ok(AuthStat::Other(15)));
+}
+
+#[test]
+fn test_denied_auth_stat_decodes() {
+    // xid 3, REPLY, MSG_DENIED, AUTH_ERROR, RPCSEC_GSS_CREDPROBLEM
+    let bytes = [0, 0, 0, 3, 0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0, 13];
+    let denied = |stat| reply(3, ReplyBody::Denied(RejectedReply::AuthError(stat)));
+    assert_eq!(from_bytes::<RpcMsg>(&bytes), Ok(denied(AuthStat::RpcsecGssCredProblem)));
+    assert_eq!(from_reader::<_, RpcMsg>(&bytes[..]), Ok(denied(AuthStat::RpcsecGssCredProblem)));
+
+    // A value RFC 5531 does not list is still an AUTH_ERROR.
+    let mut unknown = bytes;
+    unknown[19] = 99;
+    assert_eq!(from_bytes::<RpcMsg>(&unknown), Ok(denied(AuthStat::Other(99))));
+    assert_eq!(to_bytes(&denied(AuthStat::Other(99))).unwrap(), unknown);
+}
+
+// ══════════════════════════════════════════════════════════════════════════
+// opaque_auth
+// ══════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_opaque_auth_body_bounded_at_400() {
+    assert!(OpaqueAuth::new(AuthFlavor::Sys, vec![0; 400]).is_ok());
+    assert_eq!(
+        OpaqueAuth::new(AuthFlavor::Sys, vec![0; 401]),
+        Err(Error::LengthOverflow { max: 400, got: 401 })
+    );
+
+    // A 401-byte body on the wire is rejected at the length prefix.
+    let mut bytes = vec![0, 0, 0, 1, 0, 0, 0x01, 0x91];
+    bytes.extend([0; 404]);
+    let overflow = Err(Error::LengthOverflow { max: 400, got: 401 });
+    assert_eq!(from_bytes::<OpaqueAuth>(&bytes), overflow);
+    assert_eq!(from_reader::<_, OpaqueAuth>(std::io::Cursor::new(&bytes)), overflow);
+}
+
+#[test]
+fn test_opaque_auth_unknown_flavor() {
+    let auth = OpaqueAuth {
+        flavor: AuthFlavor::Other(390003),
+        body: BoundedBytes::new(vec![1, 2, 3]).unwrap(),
+    };
+    let bytes = to_bytes(&auth).unwrap();
+    assert_eq!(bytes, [0, 5, 0xF3, 0x73, 0, 0, 0, 3, 1, 2, 3, 0]);
+    assert_eq!(auth, from_bytes(&bytes).unwrap());
+    assert_eq!(from_bytes::<AuthFlavor>(&[0, 0, 0, 6]).unwrap(), AuthFlavor::RpcsecGss);
+    assert_eq!(OpaqueAuth::default(), OpaqueAuth::none());
+}
+
+// ══════════════════════════════════════════════════════════════════════════
+// AUTH_SYS
+// ══════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_auth_sys_wire_format() {
+    // The credential body Linux sends for uid 0 on host "nfs1".
+    let parms = AuthSysParms::new(0x6574_1A2B, "nfs1", 0, 0, &[0, 1]).unwrap();
+    let cred = parms.to_opaque_auth().unwrap();
+    assert_eq!(cred.flavor, AuthFlavor::Sys);
+    #[rustfmt::skip]
+    assert_eq!(cred.body.as_bytes(), [
+        0x65, 0x74, 0x1A, 0x2B,     // stamp
+        0, 0, 0, 4, b'n', b'f', b's', b'1',
+        0, 0, 0, 0,                 // uid
+        0, 0, 0, 0,                 // gid
+        0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0, 1,
+    ]);
+    assert_eq!(AuthSysParms::from_opaque_auth(&cred).unwrap(), parms);
+
+    // Carried in a call, the credential round-trips with the header.
+    let msg = RpcMsg {
+        xid: 3,
+        body: MsgBody::Call(CallBody { cred, ..CallBody::new(100003, 3, 4) }),
+    };
+    let MsgBody::Call(call) = from_bytes::<RpcMsg>(&roundtrip(&msg)).unwrap().body else {
+        panic!("expected a call");
+    };
+    assert_eq!(AuthSysParms::from_opaque_auth(&call.cred).unwrap().uid, 0);
+}
+
+#[test]
+fn test_auth_sys_bounds() {
+    let name = "h".repeat(256);
+    assert_eq!(
+        AuthSysParms::new(0, &name, 0, 0, &[]),
+        Err(Error::LengthOverflow { max: 255, got: 256 })
+    );
+    assert_eq!(
+        AuthSysParms::new(0, "host", 0, 0, &[7; 17]),
+        Err(Error::LengthOverflow { max: 16, got: 17 })
+    );
+
+    // 17 groups on the wire are rejected at the count prefix.
+    let mut body = vec![0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 17];
+    body.extend([0; 17 * 4]);
+    let cred = OpaqueAuth::new(AuthFlavor::Sys, body).unwrap();
+    assert_eq!(
+        AuthSysParms::from_opaque_auth(&cred),
+        Err(Error::LengthOverflow { max: 16, got: 17 })
+    );
+}
+
+#[test]
+fn test_auth_sys_rejects_other_flavors() {
+    assert_eq!(
+        AuthSysParms::from_opaque_auth(&OpaqueAuth::none()),
+        Err(Error::InvalidDiscriminant(0))
+    );
+    let truncated = OpaqueAuth::new(AuthFlavor::Sys, vec![0, 0, 0, 1, 0, 0, 0, 9]).unwrap();
+    assert_eq!(AuthSysParms::from_opaque_auth(&truncated), Err(Error::UnexpectedEof));
+}
+
+// ══════════════════════════════════════════════════════════════════════════
+// Record marking
+// ══════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_record_writer_fragments() {
+    let mut writer = RecordWriter::new(Vec::new()).with_max_fragment_size(8);
+    writer.write_all(&[1; 20]).unwrap();
+    writer.end_record().unwrap();
+    writer.end_record().unwrap(); // an empty record
+    #[rustfmt::skip]
+    assert_eq!(writer.into_inner(), [
+        0x00, 0, 0, 8, 1, 1, 1, 1, 1, 1, 1, 1,
+        0x00, 0, 0, 8, 1, 1, 1, 1, 1, 1, 1, 1,
+        0x80, 0, 0, 4, 1, 1, 1, 1,
+        0x80, 0, 0, 0,
+    ]);
+
+    // Data filling the last fragment exactly is still sent with the last bit.
+    let mut writer = RecordWriter::new(Vec::new()).with_max_fragment_size(4);
+    writer.write_record(&7u32).unwrap();
+    assert_eq!(writer.into_inner(), [0x80, 0, 0, 4, 0, 0, 0, 7]);
+}
+
+#[test]
+fn test_record_writer_error_accepts_nothing() {
+    // Fails its first write, then succeeds.
+    struct FailOnce(Vec<u8>, bool);
+    impl Write for FailOnce {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            if !self.1 {
+                self.1 = true;
+                return Err(std::io::ErrorKind::WouldBlock.into());
+            }
+            self.0.write(buf)
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let data: Vec<u8> = (0..20).collect();
+    let mut writer = RecordWriter::new(FailOnce(Vec::new(), false)).with_max_fragment_size(8);
+    let mut rest = &data[..];
+    let mut errors = 0;
+    while !rest.is_empty() {
+        match writer.write(rest) {
+            Ok(n) => rest = &rest[n..],
+            Err(_) => errors += 1,
+        }
+    }
+    writer.end_record().unwrap();
+    assert_eq!(errors, 1);
+    let mut expected = vec![0x00, 0, 0, 8];
+    expected.extend(0..8);
+    expected.extend([0x00, 0, 0, 8]);
+    expected.extend(8..16);
+    expected.extend([0x80, 0, 0, 4]);
+    expected.extend(16..20);
+    assert_eq!(writer.into_inner().0, expected);
+}
+
+#[test]
+fn test_record_roundtrip_across_fragments() {
+    let call = RpcMsg { xid: 9, body: MsgBody::Call(CallBody::new(100003, 3, 6)) };
+    let mut writer = RecordWriter::new(Vec::new()).with_max_fragment_size(12);
+    writer.write_record(&(&call, "/export/home".to_string())).unwrap();
+    writer.write_record(&reply(9, accepted(ReplyData::Success))).unwrap();
+    let stream = writer.into_inner();
+
+    let mut reader = RecordReader::new(&stream[..]);
+    assert!(reader.next_record().unwrap());
+    assert_eq!(from_reader::<_, RpcMsg>(&mut reader).unwrap(), call);
+    assert_eq!(from_reader::<_, String>(&mut reader).unwrap(), "/export/home");
+    assert_eq!(reader.read(&mut [0; 4]).unwrap(), 0); // end of record
+
+    let record = reader.read_record().unwrap().unwrap();
+    assert_eq!(from_bytes::<RpcMsg>(&record).unwrap(), reply(9, accepted(ReplyData::Success)));
+    assert_eq!(reader.read_record().unwrap(), None);
+}
+
+#[test]
+fn test_record_reader_skips_unread_data() {
+    // Two records; the first has an empty middle fragment.
+    let stream = [
+        0x00, 0, 0, 4, 0, 0, 0, 1, 0x00, 0, 0, 0, 0x80, 0, 0, 4, 0, 0, 0, 2, //
+        0x80, 0, 0, 4, 0, 0, 0, 3,
+    ];
+    let mut reader = RecordReader::new(&stream[..]);
+    assert!(reader.next_record().unwrap());
+    assert_eq!(from_reader::<_, u32>(&mut reader).unwrap(), 1);
+    assert!(reader.next_record().unwrap());
+    assert_eq!(from_reader::<_, u32>(&mut reader).unwrap(), 3);
+    assert!(!reader.next_record().unwrap());
+}
+
+#[test]
+fn test_record_reader_max_record_size() {
+    // The second fragment takes the record to 12 bytes.
+    let stream = [0x00, 0, 0, 8, 0, 0, 0, 1, 0, 0, 0, 2, 0x80, 0, 0, 4, 0, 0, 0, 3];
+    let mut reader = RecordReader::new(&stream[..]).with_max_record_size(8);
+    assert!(reader.next_record().unwrap());
+    assert_eq!(
+        from_reader::<_, (u32, u32, u32)>(&mut reader),
+        Err(Error::LengthOverflow { max: 8, got: 12 })
+    );
+
+    // A single oversized fragment is rejected before its data is read.
+    let mut reader = RecordReader::new(&[0x80, 0, 0, 16][..]).with_max_record_size(8);
+    assert_eq!(reader.next_record(), Err(Error::LengthOverflow { max: 8, got: 16 }));
+}
+
+#[test]
+fn test_record_reader_truncated() {
+    // Ends in the middle of a header.
+    let mut reader = RecordReader::new(&[0x80, 0][..]);
+    assert_eq!(reader.next_record(), Err(Error::UnexpectedEof));
+
+    // Ends in the middle of a fragment.
+    let mut reader = RecordReader::new(&[0x80, 0, 0, 8, 0, 0, 0, 1][..]);
+    assert_eq!(reader.read_record(), Err(Error::UnexpectedEof));
+
+    // The record ends before the value does.
+    let mut reader = RecordReader::new(&[0x80, 0, 0, 4, 0, 0, 0, 1][..]);
+    assert!(reader.next_record().unwrap());
+    assert_eq!(from_reader::<_, (u32, u32)>(&mut reader), Err(Error::UnexpectedEof));
+}
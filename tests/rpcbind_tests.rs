@@ -0,0 +1,205 @@
+use std::net::{SocketAddr, UdpSocket};
+use xdr_serde::rpc::client::Client;
+use xdr_serde::rpc::rpcbind::*;
+use xdr_serde::{ByteBuf, DecodeLimits, Deserialize, Deserializer, Error, from_bytes, to_bytes};
+
+const PROG: u32 = 0x2000_0077;
+
+fn rpcb(vers: u32, netid: &str, addr: &str) -> Rpcb {
+    Rpcb {
+        r_prog: PROG,
+        r_vers: vers,
+        r_netid: netid.into(),
+        r_addr: addr.into(),
+        r_owner: "test".into(),
+    }
+}
+
+fn spawn(rpcbind: &Rpcbind) -> SocketAddr {
+    let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let addr = socket.local_addr().unwrap();
+    let server = rpcbind.server();
+    std::thread::spawn(move || server.serve_udp(&socket));
+    addr
+}
+
+// ══════════════════════════════════════════════════════════════════════════
+// Wire format
+// ══════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_mapping_encoding() {
+    let mapping = Mapping { prog: 100003, vers: 3, prot: IPPROTO_UDP, port: 2049 };
+    #[rustfmt::skip]
+    let expected = [
+        0x00, 0x01, 0x86, 0xA3,
+        0x00, 0x00, 0x00, 0x03,
+        0x00, 0x00, 0x00, 0x11,
+        0x00, 0x00, 0x08, 0x01,
+    ];
+    assert_eq!(to_bytes(&mapping).unwrap(), expected);
+    assert_eq!(from_bytes::<Mapping>(&expected).unwrap(), mapping);
+}
+
+#[test]
+fn test_pmaplist_encoding() {
+    let mappings = [
+        Mapping { prog: PMAP_PROG, vers: PMAP_VERS, prot: IPPROTO_TCP, port: 111 },
+        Mapping { prog: PMAP_PROG, vers: PMAP_VERS, prot: IPPROTO_UDP, port: 111 },
+    ];
+    let list = PmapList(mappings.to_vec());
+    let bytes = to_bytes(&list).unwrap();
+    assert_eq!(bytes.len(), 2 * (4 + 16) + 4);
+    assert_eq!(&bytes[..4], [0, 0, 0, 1]);
+    assert_eq!(&bytes[20..24], [0, 0, 0, 1]);
+    assert_eq!(&bytes[40..], [0, 0, 0, 0]);
+
+    assert_eq!(from_bytes::<PmapList>(&bytes).unwrap(), list);
+
+    // An empty list is a lone FALSE.
+    assert_eq!(to_bytes(&PmapList::default()).unwrap(), [0, 0, 0, 0]);
+    assert_eq!(from_bytes::<PmapList>(&[0, 0, 0, 0]).unwrap(), PmapList(vec![]));
+}
+
+#[test]
+fn test_rpcb_list_roundtrip() {
+    let entries = vec![rpcb(1, "tcp", "127.0.0.1.4.0"), rpcb(1, "udp6", "::1.4.0")];
+    let bytes = to_bytes(&RpcbList(entries.clone())).unwrap();
+    assert_eq!(from_bytes::<RpcbList>(&bytes).unwrap().0, entries);
+}
+
+#[test]
+fn test_call_args_encoding() {
+    let args = CallArgs { prog: 100003, vers: 3, proc: 0, args: ByteBuf::from(vec![1, 2, 3]) };
+    #[rustfmt::skip]
+    let expected = [
+        0x00, 0x01, 0x86, 0xA3,
+        0x00, 0x00, 0x00, 0x03,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x03, 1, 2, 3, 0,
+    ];
+    assert_eq!(to_bytes(&args).unwrap(), expected);
+    assert_eq!(from_bytes::<CallArgs>(&expected).unwrap(), args);
+
+    // The opaque body has no bound of its own; the decode limits still apply.
+    let limits = DecodeLimits { max_opaque_len: 2, ..DecodeLimits::default() };
+    let mut de = Deserializer::new(&expected).with_limits(limits);
+    assert_eq!(
+        CallArgs::deserialize(&mut de),
+        Err(Error::LengthOverflow { max: 2, got: 3 })
+    );
+}
+
+#[test]
+fn test_uaddr() {
+    let addr: SocketAddr = "192.0.2.1:2049".parse().unwrap();
+    assert_eq!(to_uaddr(addr), "192.0.2.1.8.1");
+    assert_eq!(parse_uaddr("192.0.2.1.8.1"), Some(addr));
+
+    let addr: SocketAddr = "[fe80::1]:111".parse().unwrap();
+    assert_eq!(to_uaddr(addr), "fe80::1.0.111");
+    assert_eq!(parse_uaddr("fe80::1.0.111"), Some(addr));
+
+    for bad in ["", "192.0.2.1", "192.0.2.1.8", "192.0.2.1.256.1", "host.8.1"] {
+        assert_eq!(parse_uaddr(bad), None, "{:?}", bad);
+    }
+}
+
+// ══════════════════════════════════════════════════════════════════════════
+// Registry
+// ══════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_set_and_unset() {
+    let rpcbind = Rpcbind::new();
+    assert!(rpcbind.set(rpcb(1, "tcp", "127.0.0.1.4.0")));
+    assert!(!rpcbind.set(rpcb(1, "tcp", "127.0.0.1.4.1")), "already registered");
+    assert!(rpcbind.set(rpcb(1, "udp", "127.0.0.1.4.0")));
+    assert!(rpcbind.set(rpcb(2, "tcp", "127.0.0.1.4.2")));
+
+    assert!(rpcbind.unset(&rpcb(1, "udp", "")));
+    assert!(!rpcbind.unset(&rpcb(1, "udp", "")));
+    assert_eq!(rpcbind.dump().len(), 2);
+    // An empty netid removes the version on every transport.
+    assert!(rpcbind.unset(&rpcb(2, "", "")));
+    assert_eq!(rpcbind.dump(), [rpcb(1, "tcp", "127.0.0.1.4.0")]);
+}
+
+#[test]
+fn test_getaddr_version_fallback() {
+    let rpcbind = Rpcbind::new();
+    rpcbind.set(rpcb(1, "tcp", "127.0.0.1.4.1"));
+    rpcbind.set(rpcb(2, "tcp", "127.0.0.1.4.2"));
+    assert_eq!(rpcbind.getaddr(&rpcb(2, "tcp", "")), "127.0.0.1.4.2");
+    assert_eq!(rpcbind.getaddr(&rpcb(2, "", "")), "127.0.0.1.4.2");
+    assert_eq!(rpcbind.getaddr(&rpcb(3, "tcp", "")), "127.0.0.1.4.1");
+    assert_eq!(rpcbind.getaddr(&rpcb(2, "udp", "")), "");
+    assert_eq!(rpcbind.getversaddr(&rpcb(3, "tcp", "")), "");
+    assert_eq!(rpcbind.getversaddr(&rpcb(1, "tcp", "")), "127.0.0.1.4.1");
+}
+
+#[test]
+fn test_portmapper_view() {
+    let rpcbind = Rpcbind::new();
+    let mapping = Mapping { prog: PROG, vers: 1, prot: IPPROTO_UDP, port: 1024 };
+    assert!(rpcbind.pmap_set(mapping));
+    assert!(!rpcbind.pmap_set(Mapping { port: 1025, ..mapping }));
+    assert!(!rpcbind.pmap_set(Mapping { prot: 99, ..mapping }));
+    assert!(!rpcbind.pmap_set(Mapping { prot: IPPROTO_TCP, port: 70000, ..mapping }));
+    assert_eq!(rpcbind.dump()[0].r_addr, "0.0.0.0.4.0");
+
+    // rpcbind registrations appear through the portmapper, IPv6 ones do not.
+    rpcbind.set(rpcb(1, "tcp", "127.0.0.1.4.1"));
+    rpcbind.set(rpcb(1, "tcp6", "::1.4.1"));
+    let query = Mapping { port: 0, ..mapping };
+    assert_eq!(rpcbind.pmap_getport(&query), 1024);
+    assert_eq!(rpcbind.pmap_getport(&Mapping { prot: IPPROTO_TCP, ..query }), 1025);
+    assert_eq!(rpcbind.pmap_getport(&Mapping { prog: PROG + 1, ..query }), 0);
+    assert_eq!(rpcbind.pmap_dump().len(), 2);
+
+    // Unsetting removes the version on every transport, IPv6 included.
+    assert!(rpcbind.pmap_unset(&query));
+    assert!(rpcbind.dump().is_empty());
+}
+
+// ══════════════════════════════════════════════════════════════════════════
+// Server
+// ══════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_portmapper_over_udp() {
+    let rpcbind = Rpcbind::new();
+    let mut client = Client::udp(spawn(&rpcbind), PMAP_PROG, PMAP_VERS).unwrap();
+    client.call::<_, ()>(PMAPPROC_NULL, &()).unwrap();
+    let mapping = Mapping { prog: PROG, vers: 1, prot: IPPROTO_TCP, port: 4000 };
+    assert!(client.call::<_, bool>(PMAPPROC_SET, &mapping).unwrap());
+    assert_eq!(client.call::<_, u32>(PMAPPROC_GETPORT, &mapping).unwrap(), 4000);
+
+    let list: PmapList = client.call(PMAPPROC_DUMP, &()).unwrap();
+    assert_eq!(list.0, [mapping]);
+    // The registration is visible to the embedding process.
+    assert_eq!(rpcbind.pmap_dump(), [mapping]);
+
+    assert!(client.call::<_, bool>(PMAPPROC_UNSET, &mapping).unwrap());
+    assert_eq!(client.call::<_, PmapList>(PMAPPROC_DUMP, &()).unwrap(), PmapList(vec![]));
+}
+
+#[test]
+fn test_rpcbind_over_udp() {
+    let rpcbind = Rpcbind::new();
+    let addr = spawn(&rpcbind);
+    rpcbind.set(rpcb(1, "udp", "127.0.0.1.4.1"));
+
+    let mut v3 = Client::udp(addr, RPCBPROG, RPCBVERS).unwrap();
+    assert!(v3.call::<_, bool>(RPCBPROC_SET, &rpcb(2, "udp", "127.0.0.1.4.2")).unwrap());
+    assert_eq!(v3.call::<_, String>(RPCBPROC_GETADDR, &rpcb(2, "udp", "")).unwrap(), "127.0.0.1.4.2");
+    assert!(v3.call::<_, u32>(RPCBPROC_GETTIME, &()).unwrap() > 0);
+    let list: RpcbList = v3.call(RPCBPROC_DUMP, &()).unwrap();
+    assert_eq!(list.0.len(), 2);
+
+    let mut v4 = Client::udp(addr, RPCBPROG, RPCBVERS4).unwrap();
+    assert_eq!(v4.call::<_, String>(RPCBPROC_GETVERSADDR, &rpcb(3, "udp", "")).unwrap(), "");
+    assert_eq!(v4.call::<_, String>(RPCBPROC_GETADDR, &rpcb(3, "udp", "")).unwrap(), "127.0.0.1.4.1");
+    assert!(v4.call::<_, bool>(RPCBPROC_UNSET, &rpcb(1, "", "")).unwrap());
+    assert_eq!(rpcbind.dump(), [rpcb(2, "udp", "127.0.0.1.4.2")]);
+}
@@ -0,0 +1,201 @@
+use std::io::BufReader;
+use std::net::{TcpListener, TcpStream, UdpSocket};
+use xdr_serde::rpc::record::{RecordReader, RecordWriter};
+use xdr_serde::rpc::server::Server;
+use xdr_serde::rpc::*;
+use xdr_serde::{DecodeLimits, from_bytes, from_bytes_partial, from_reader, to_bytes};
+
+const PROG: u32 = 0x2000_0042;
+
+fn server() -> Server {
+    Server::new()
+        .with_procedure(PROG, 2, 0, |_, ()| ())
+        .with_procedure(PROG, 2, 1, |_, (a, b): (u32, u32)| a.wrapping_add(b))
+        .with_procedure(PROG, 3, 1, |_, name: String| name.to_uppercase())
+        .with_procedure(PROG, 3, 2, |call: &CallBody, ()| {
+            AuthSysParms::from_opaque_auth(&call.cred).map(|parms| parms.uid).ok()
+        })
+}
+
+fn call(xid: u32, vers: u32, proc: u32, args: &[u8]) -> Vec<u8> {
+    let msg = RpcMsg { xid, body: MsgBody::Call(CallBody::new(PROG, vers, proc)) };
+    let mut bytes = to_bytes(&msg).unwrap();
+    bytes.extend_from_slice(args);
+    bytes
+}
+
+/// Split a reply into its header and results.
+fn parse(reply: &[u8]) -> (RpcMsg, Vec<u8>) {
+    let (header, results) = from_bytes_partial::<RpcMsg>(reply).unwrap();
+    (header, results.to_vec())
+}
+
+fn reply_data(reply: &[u8]) -> ReplyData {
+    match parse(reply).0.body {
+        MsgBody::Reply(ReplyBody::Accepted(accepted)) => accepted.reply_data,
+        body => panic!("expected an accepted reply, got {:?}", body),
+    }
+}
+
+// ══════════════════════════════════════════════════════════════════════════
+// Dispatch
+// ══════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_dispatch_success() {
+    let server = server();
+    let reply = server.dispatch(&call(11, 2, 1, &to_bytes(&(40u32, 2u32)).unwrap())).unwrap();
+    let (header, results) = parse(&reply);
+    assert_eq!(header.xid, 11);
+    assert_eq!(reply_data(&reply), ReplyData::Success);
+    assert_eq!(from_bytes::<u32>(&results).unwrap(), 42);
+
+    // A void procedure has empty results.
+    let (_, results) = parse(&server.dispatch(&call(12, 2, 0, &[])).unwrap());
+    assert!(results.is_empty());
+}
+
+#[test]
+fn test_dispatch_unavailable() {
+    let server = server();
+    let mut other = call(1, 2, 0, &[]);
+    other[15] = 0x43; // program 0x20000043
+    assert_eq!(reply_data(&server.dispatch(&other).unwrap()), ReplyData::ProgUnavail);
+
+    let reply = server.dispatch(&call(2, 4, 0, &[])).unwrap();
+    assert_eq!(reply_data(&reply), ReplyData::ProgMismatch(MismatchInfo { low: 2, high: 3 }));
+
+    let reply = server.dispatch(&call(3, 3, 0, &[])).unwrap();
+    assert_eq!(reply_data(&reply), ReplyData::ProcUnavail);
+}
+
+#[test]
+fn test_dispatch_garbage_args() {
+    let server = server();
+    // Only one of the two arguments.
+    let reply = server.dispatch(&call(4, 2, 1, &[0, 0, 0, 1])).unwrap();
+    assert_eq!(reply_data(&reply), ReplyData::GarbageArgs);
+    // A string that is not UTF-8.
+    let reply = server.dispatch(&call(5, 3, 1, &[0, 0, 0, 1, 0xFF, 0, 0, 0])).unwrap();
+    assert_eq!(reply_data(&reply), ReplyData::GarbageArgs);
+}
+
+#[test]
+fn test_dispatch_enforces_limits() {
+    let limits = DecodeLimits { max_string_len: 4, ..DecodeLimits::default() };
+    let server = server().with_limits(limits);
+    let reply = server.dispatch(&call(7, 3, 1, &to_bytes(&"abcd").unwrap())).unwrap();
+    assert_eq!(from_bytes::<String>(&parse(&reply).1).unwrap(), "ABCD");
+    let reply = server.dispatch(&call(8, 3, 1, &to_bytes(&"abcde").unwrap())).unwrap();
+    assert_eq!(reply_data(&reply), ReplyData::GarbageArgs);
+}
+
+#[test]
+fn test_dispatch_rpc_mismatch() {
+    let mut request = call(6, 2, 0, &[]);
+    request[11] = 3; // rpcvers
+    let (header, _) = parse(&server().dispatch(&request).unwrap());
+    let MsgBody::Reply(ReplyBody::Denied(rejected)) = header.body else {
+        panic!("expected a denied reply");
+    };
+    assert_eq!(rejected, RejectedReply::RpcMismatch(MismatchInfo { low: 2, high: 2 }));
+}
+
+#[test]
+fn test_dispatch_ignores_unanswerable_messages() {
+    let server = server();
+    assert_eq!(server.dispatch(&[0, 0, 0, 1]), None);
+    let reply = server.dispatch(&call(7, 2, 0, &[])).unwrap();
+    assert_eq!(server.dispatch(&reply), None);
+}
+
+#[test]
+fn test_dispatch_passes_credential() {
+    let cred = AuthSysParms::new(0, "client", 1000, 100, &[]).unwrap().to_opaque_auth().unwrap();
+    let msg = RpcMsg {
+        xid: 8,
+        body: MsgBody::Call(CallBody { cred, ..CallBody::new(PROG, 3, 2) }),
+    };
+    let (_, results) = parse(&server().dispatch(&to_bytes(&msg).unwrap()).unwrap());
+    assert_eq!(from_bytes::<Option<u32>>(&results).unwrap(), Some(1000));
+
+    let (_, results) = parse(&server().dispatch(&call(9, 3, 2, &[])).unwrap());
+    assert_eq!(from_bytes::<Option<u32>>(&results).unwrap(), None);
+}
+
+// ══════════════════════════════════════════════════════════════════════════
+// Transports
+// ══════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_serve_tcp_loopback() {
+    let server = server();
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    std::thread::spawn(move || server.serve_tcp(&listener));
+
+    let stream = TcpStream::connect(addr).unwrap();
+    let mut writer = RecordWriter::new(stream.try_clone().unwrap()).with_max_fragment_size(16);
+    let mut reader = RecordReader::new(BufReader::new(stream));
+    // Two calls pipelined on one connection; replies arrive in order.
+    let first = RpcMsg { xid: 21, body: MsgBody::Call(CallBody::new(PROG, 3, 1)) };
+    writer.write_record(&(first, "loopback".to_string())).unwrap();
+    let second = RpcMsg { xid: 22, body: MsgBody::Call(CallBody::new(PROG, 9, 1)) };
+    writer.write_record(&second).unwrap();
+
+    assert!(reader.next_record().unwrap());
+    assert_eq!(from_reader::<_, RpcMsg>(&mut reader).unwrap().xid, 21);
+    assert_eq!(from_reader::<_, String>(&mut reader).unwrap(), "LOOPBACK");
+    let second = reader.read_record().unwrap().unwrap();
+    assert_eq!(parse(&second).0.xid, 22);
+    assert_eq!(reply_data(&second), ReplyData::ProgMismatch(MismatchInfo { low: 2, high: 3 }));
+}
+
+#[test]
+fn test_serve_non_blocking_returns() {
+    // Nothing is pending, so the first accept or receive would block.
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    listener.set_nonblocking(true).unwrap();
+    assert!(matches!(server().serve_tcp(&listener), Err(xdr_serde::Error::Io(_))));
+
+    let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+    socket.set_nonblocking(true).unwrap();
+    assert!(matches!(server().serve_udp(&socket), Err(xdr_serde::Error::Io(_))));
+}
+
+#[test]
+fn test_serve_udp_loopback() {
+    let server = server();
+    let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let addr = socket.local_addr().unwrap();
+    std::thread::spawn(move || server.serve_udp(&socket));
+
+    let client = UdpSocket::bind("127.0.0.1:0").unwrap();
+    client.set_read_timeout(Some(std::time::Duration::from_secs(5))).unwrap();
+    client.send_to(&call(31, 2, 1, &to_bytes(&(1u32, 2u32)).unwrap()), addr).unwrap();
+    let mut buf = [0; 1024];
+    let (n, from) = client.recv_from(&mut buf).unwrap();
+    assert_eq!(from, addr);
+    let (header, results) = parse(&buf[..n]);
+    assert_eq!(header.xid, 31);
+    assert_eq!(from_bytes::<u32>(&results).unwrap(), 3);
+}
+
+#[test]
+fn test_serve_udp_survives_unsendable_reply() {
+    // A reply too large for one datagram fails to send.
+    let server = server().with_procedure(PROG, 4, 0, |_, ()| vec![0u32; 20_000]);
+    let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let addr = socket.local_addr().unwrap();
+    std::thread::spawn(move || server.serve_udp(&socket));
+
+    let client = UdpSocket::bind("127.0.0.1:0").unwrap();
+    client.set_read_timeout(Some(std::time::Duration::from_secs(5))).unwrap();
+    client.send_to(&call(41, 4, 0, &[]), addr).unwrap();
+    client.send_to(&call(42, 2, 1, &to_bytes(&(2u32, 3u32)).unwrap()), addr).unwrap();
+    let mut buf = [0; 1024];
+    let (n, _) = client.recv_from(&mut buf).unwrap();
+    let (header, results) = parse(&buf[..n]);
+    assert_eq!(header.xid, 42);
+    assert_eq!(from_bytes::<u32>(&results).unwrap(), 5);
+}
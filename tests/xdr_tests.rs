@@ -1,5 +1,9 @@
 use serde::{Deserialize, Serialize};
-use xdr_serde::{from_bytes, from_bytes_partial, from_reader, to_bytes, to_writer};
+use xdr_serde::bounded::{BoundedBytes, BoundedString, BoundedVec};
+use xdr_serde::{
+    DecodeLimits, Deserializer, ReaderDeserializer, from_bytes, from_bytes_partial, from_reader,
+    serialized_size, to_bytes, to_slice, to_writer,
+};
 
 
 #[test]
@@ -382,6 +386,7 @@ fn test_fixed_opaque_4_bytes_no_padding() {
 }
 
 #[test]
+#[allow(clippy::manual_div_ceil)]
 fn test_fixed_opaque_all_sizes_1_through_16() {
     // Verify that every array size from 1 to 16 encodes/decodes correctly
     // and the total wire size is always a multiple of 4.
@@ -474,4 +479,633 @@ fn test_nfs_stateid_xdr_wire_layout() {
     assert_eq!(&bytes[4..16], [0xAA; 12]);     // other (raw, no length prefix)
     assert_eq!(state, from_bytes(&bytes).unwrap());
     assert_eq!(state, from_reader(std::io::Cursor::new(&bytes[..])).unwrap());
-}
\ No newline at end of file
+}
+// ══════════════════════════════════════════════════════════════════════════
+// Strict padding validation
+// ══════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_lenient_padding_by_default() {
+    // "A" followed by non-zero padding is accepted unless strict mode is on.
+    let bytes = [0, 0, 0, 1, b'A', 0xFF, 0, 0];
+    assert_eq!(from_bytes::<String>(&bytes).unwrap(), "A");
+    assert_eq!(from_reader::<_, String>(std::io::Cursor::new(bytes)).unwrap(), "A");
+}
+
+#[test]
+fn test_strict_padding_rejects_non_zero() {
+    let bytes = [0, 0, 0, 1, b'A', 0, 0, 0x01];
+    let mut de = Deserializer::new(&bytes).with_strict_padding(true);
+    assert_eq!(String::deserialize(&mut de), Err(xdr_serde::Error::InvalidPadding));
+
+    let mut de = ReaderDeserializer::new(std::io::Cursor::new(bytes)).with_strict_padding(true);
+    assert_eq!(String::deserialize(&mut de), Err(xdr_serde::Error::InvalidPadding));
+}
+
+#[test]
+fn test_strict_padding_accepts_zero() {
+    let bytes = to_bytes(&serde_bytes::ByteBuf::from(vec![1, 2, 3, 4, 5])).unwrap();
+    let mut de = Deserializer::new(&bytes).with_strict_padding(true);
+    let decoded = serde_bytes::ByteBuf::deserialize(&mut de).unwrap();
+    assert_eq!(decoded.as_slice(), [1, 2, 3, 4, 5]);
+    assert!(de.remaining().is_empty());
+}
+
+#[test]
+fn test_strict_padding_fixed_opaque() {
+    // handle: [u8; 5] is followed by 3 padding bytes; corrupt the last one.
+    let fid = Nfs4FileId { verifier: [0; 8], generation: 1, handle: [1, 2, 3, 4, 5], flags: 0 };
+    let mut bytes = to_bytes(&fid).unwrap();
+    bytes[19] = 0x80;
+
+    assert_eq!(from_bytes::<Nfs4FileId>(&bytes).unwrap(), fid);
+
+    let mut de = Deserializer::new(&bytes).with_strict_padding(true);
+    assert_eq!(Nfs4FileId::deserialize(&mut de), Err(xdr_serde::Error::InvalidPadding));
+
+    let mut de = ReaderDeserializer::new(std::io::Cursor::new(&bytes)).with_strict_padding(true);
+    assert_eq!(Nfs4FileId::deserialize(&mut de), Err(xdr_serde::Error::InvalidPadding));
+}
+
+// ══════════════════════════════════════════════════════════════════════════
+// Decode limits
+// ══════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_limits_string_length() {
+    let limits = DecodeLimits { max_string_len: 4, ..DecodeLimits::default() };
+    let bytes = to_bytes(&"hello".to_string()).unwrap();
+    let overflow = xdr_serde::Error::LengthOverflow { max: 4, got: 5 };
+
+    let mut de = Deserializer::new(&bytes).with_limits(limits);
+    assert_eq!(String::deserialize(&mut de), Err(overflow.clone()));
+
+    let mut de = ReaderDeserializer::new(std::io::Cursor::new(&bytes)).with_limits(limits);
+    assert_eq!(String::deserialize(&mut de), Err(overflow));
+
+    // Opaque data is governed by its own limit.
+    let mut de = Deserializer::new(&bytes).with_limits(limits);
+    assert!(serde_bytes::ByteBuf::deserialize(&mut de).is_ok());
+}
+
+#[test]
+fn test_limits_opaque_length_before_allocation() {
+    // Claims 0xFFFFFFFF bytes of opaque data but carries none.
+    let bytes = [0xFF, 0xFF, 0xFF, 0xFF];
+    let limits = DecodeLimits { max_opaque_len: 1024, ..DecodeLimits::default() };
+    let overflow = xdr_serde::Error::LengthOverflow { max: 1024, got: u32::MAX };
+
+    let mut de = ReaderDeserializer::new(std::io::Cursor::new(bytes)).with_limits(limits);
+    assert_eq!(serde_bytes::ByteBuf::deserialize(&mut de), Err(overflow.clone()));
+
+    let mut de = Deserializer::new(&bytes).with_limits(limits);
+    assert_eq!(serde_bytes::ByteBuf::deserialize(&mut de), Err(overflow));
+}
+
+#[test]
+fn test_reader_forged_length_without_limits_is_eof() {
+    // Without limits the reader must still fail cleanly rather than allocate 4 GiB.
+    let bytes = [0xFF, 0xFF, 0xFF, 0xFF, 1, 2, 3, 4];
+    let result = from_reader::<_, serde_bytes::ByteBuf>(std::io::Cursor::new(bytes));
+    assert_eq!(result, Err(xdr_serde::Error::UnexpectedEof));
+}
+
+#[test]
+fn test_limits_element_count() {
+    let limits = DecodeLimits { max_elements: 3, ..DecodeLimits::default() };
+    let bytes = to_bytes(&vec![1u32, 2, 3, 4]).unwrap();
+    let overflow = xdr_serde::Error::LengthOverflow { max: 3, got: 4 };
+
+    let mut de = Deserializer::new(&bytes).with_limits(limits);
+    assert_eq!(Vec::<u32>::deserialize(&mut de), Err(overflow.clone()));
+
+    let mut de = ReaderDeserializer::new(std::io::Cursor::new(&bytes)).with_limits(limits);
+    assert_eq!(Vec::<u32>::deserialize(&mut de), Err(overflow.clone()));
+
+    let map: std::collections::BTreeMap<u32, u32> = (0..4).map(|i| (i, i)).collect();
+    let bytes = to_bytes(&map).unwrap();
+    let mut de = Deserializer::new(&bytes).with_limits(limits);
+    assert_eq!(std::collections::BTreeMap::<u32, u32>::deserialize(&mut de), Err(overflow));
+}
+
+#[test]
+fn test_limits_nesting_depth() {
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Node { value: u32, next: Option<Box<Node>> }
+
+    let mut list = None;
+    for value in 0..10 {
+        list = Some(Box::new(Node { value, next: list }));
+    }
+    let bytes = to_bytes(&list).unwrap();
+
+    // Each list element costs two levels: the optional-data arm and the struct.
+    let limits = DecodeLimits { max_depth: 8, ..DecodeLimits::default() };
+    let overflow = xdr_serde::Error::LengthOverflow { max: 8, got: 9 };
+
+    let mut de = Deserializer::new(&bytes).with_limits(limits);
+    assert_eq!(Option::<Box<Node>>::deserialize(&mut de), Err(overflow.clone()));
+
+    let mut de = ReaderDeserializer::new(std::io::Cursor::new(&bytes)).with_limits(limits);
+    assert_eq!(Option::<Box<Node>>::deserialize(&mut de), Err(overflow));
+
+    let limits = DecodeLimits { max_depth: 20, ..DecodeLimits::default() };
+    let mut de = Deserializer::new(&bytes).with_limits(limits);
+    assert_eq!(Option::<Box<Node>>::deserialize(&mut de).unwrap(), list);
+}
+
+#[test]
+fn test_limits_total_bytes() {
+    let v: Vec<u32> = vec![1, 2, 3];
+    let bytes = to_bytes(&v).unwrap(); // 16 bytes
+    let limits = DecodeLimits { max_bytes: 12, ..DecodeLimits::default() };
+    let overflow = xdr_serde::Error::LengthOverflow { max: 12, got: 16 };
+
+    let mut de = Deserializer::new(&bytes).with_limits(limits);
+    assert_eq!(Vec::<u32>::deserialize(&mut de), Err(overflow.clone()));
+
+    let mut de = ReaderDeserializer::new(std::io::Cursor::new(&bytes)).with_limits(limits);
+    assert_eq!(Vec::<u32>::deserialize(&mut de), Err(overflow));
+
+    let limits = DecodeLimits { max_bytes: 16, ..DecodeLimits::default() };
+    let mut de = ReaderDeserializer::new(std::io::Cursor::new(&bytes)).with_limits(limits);
+    assert_eq!(Vec::<u32>::deserialize(&mut de).unwrap(), v);
+}
+
+// ══════════════════════════════════════════════════════════════════════════
+// Bounded string<N> / opaque<N> / T<N>
+// ══════════════════════════════════════════════════════════════════════════
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct BoundedRecord {
+    name: BoundedString<8>,
+    data: BoundedBytes<5>,
+    gids: BoundedVec<u32, 2>,
+}
+
+#[test]
+fn test_bounded_roundtrip_same_wire_as_unbounded() {
+    let rec = BoundedRecord {
+        name: BoundedString::new("nfs".to_string()).unwrap(),
+        data: BoundedBytes::new(vec![1, 2, 3, 4, 5]).unwrap(),
+        gids: BoundedVec::new(vec![10, 20]).unwrap(),
+    };
+    let bytes = to_bytes(&rec).unwrap();
+
+    let mut plain = to_bytes(&"nfs".to_string()).unwrap();
+    plain.extend(to_bytes(&serde_bytes::ByteBuf::from(vec![1, 2, 3, 4, 5])).unwrap());
+    plain.extend(to_bytes(&vec![10u32, 20]).unwrap());
+    assert_eq!(bytes, plain);
+
+    assert_eq!(rec, from_bytes(&bytes).unwrap());
+    assert_eq!(rec, from_reader(std::io::Cursor::new(&bytes)).unwrap());
+}
+
+#[test]
+fn test_bounded_constructor_rejects_oversized() {
+    let overflow = xdr_serde::Error::LengthOverflow { max: 8, got: 9 };
+    assert_eq!(BoundedString::<8>::try_from("123456789"), Err(overflow));
+    assert!(BoundedBytes::<5>::new(vec![0; 6]).is_err());
+    assert!(BoundedVec::<u32, 2>::new(vec![0; 3]).is_err());
+    assert_eq!(BoundedString::<8>::MAX, 8);
+}
+
+#[test]
+fn test_bounded_decode_rejects_oversized() {
+    // A record whose name is 9 bytes long, decoded as string<8>.
+    let bytes = to_bytes(&("123456789".to_string(), serde_bytes::ByteBuf::new(), Vec::<u32>::new()))
+        .unwrap();
+    let overflow = xdr_serde::Error::LengthOverflow { max: 8, got: 9 };
+    assert_eq!(from_bytes::<BoundedRecord>(&bytes), Err(overflow.clone()));
+    assert_eq!(from_reader::<_, BoundedRecord>(std::io::Cursor::new(&bytes)), Err(overflow));
+
+    let bytes = to_bytes(&(String::new(), serde_bytes::ByteBuf::new(), vec![1u32, 2, 3])).unwrap();
+    let overflow = xdr_serde::Error::LengthOverflow { max: 2, got: 3 };
+    assert_eq!(from_bytes::<BoundedRecord>(&bytes), Err(overflow));
+}
+
+#[test]
+fn test_bounded_decode_rejects_before_allocating() {
+    // opaque<5> claiming 0xFFFFFFFF bytes: must fail on the prefix, not on EOF.
+    let mut bytes = to_bytes(&String::new()).unwrap();
+    bytes.extend([0xFF, 0xFF, 0xFF, 0xFF]);
+    let overflow = xdr_serde::Error::LengthOverflow { max: 5, got: u32::MAX };
+    assert_eq!(from_reader::<_, BoundedRecord>(std::io::Cursor::new(&bytes)), Err(overflow));
+}
+
+#[test]
+fn test_bounded_tighter_than_decode_limits() {
+    let limits = DecodeLimits { max_string_len: 4, ..DecodeLimits::default() };
+    let bytes = to_bytes(&"12345".to_string()).unwrap();
+    let mut de = Deserializer::new(&bytes).with_limits(limits);
+    assert_eq!(
+        BoundedString::<8>::deserialize(&mut de),
+        Err(xdr_serde::Error::LengthOverflow { max: 4, got: 5 })
+    );
+}
+
+#[test]
+fn test_bounded_serialize_with_plain_fields() {
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Entry {
+        #[serde(
+            serialize_with = "xdr_serde::bounded::serialize::<4, _, _>",
+            deserialize_with = "xdr_serde::bounded::deserialize::<4, _, _>"
+        )]
+        name: String,
+        #[serde(
+            serialize_with = "xdr_serde::bounded::serialize::<2, _, _>",
+            deserialize_with = "xdr_serde::bounded::deserialize::<2, _, _>"
+        )]
+        ids: Vec<u32>,
+    }
+
+    let ok = Entry { name: "abcd".into(), ids: vec![1, 2] };
+    let bytes = to_bytes(&ok).unwrap();
+    assert_eq!(ok, from_bytes(&bytes).unwrap());
+
+    // The serializer refuses to write an oversized length prefix.
+    let too_long = Entry { name: "abcde".into(), ids: vec![] };
+    assert_eq!(to_bytes(&too_long), Err(xdr_serde::Error::LengthOverflow { max: 4, got: 5 }));
+    let too_many = Entry { name: String::new(), ids: vec![1, 2, 3] };
+    let mut sink = Vec::new();
+    assert_eq!(
+        to_writer(&mut sink, &too_many),
+        Err(xdr_serde::Error::LengthOverflow { max: 2, got: 3 })
+    );
+}
+
+#[test]
+fn test_bounded_applies_only_to_own_field() {
+    // The bound on `ids` must not leak into the unbounded string that follows.
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Mixed { ids: BoundedVec<u32, 1>, comment: String }
+
+    let v = Mixed { ids: BoundedVec::default(), comment: "longer than one".into() };
+    assert_eq!(v, from_bytes(&to_bytes(&v).unwrap()).unwrap());
+}
+
+// ══════════════════════════════════════════════════════════════════════════
+// Explicit discriminants
+// ══════════════════════════════════════════════════════════════════════════
+
+const NFS3ERR_NOTDIR: i32 = 20;
+
+xdr_serde::xdr_enum! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Nfsstat3 {
+        Ok = 0,
+        Perm = 1,
+        NoEnt = 2,
+        Io = 5,
+        NotDir = NFS3ERR_NOTDIR,
+        /// Negative discriminants are legal XDR.
+        Jukebox = -10008,
+    }
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct LookupOk { object: Vec<u8>, attr_follows: bool }
+
+xdr_serde::xdr_enum! {
+    #[derive(Debug, PartialEq)]
+    enum LookupRes {
+        Ok(LookupOk) = 0,
+        NoEnt = 2,
+        Io(u32) = 5,
+    }
+}
+
+#[test]
+fn test_discriminant_enum_wire_values() {
+    for (status, wire) in [
+        (Nfsstat3::Ok, [0, 0, 0, 0]),
+        (Nfsstat3::NoEnt, [0, 0, 0, 2]),
+        (Nfsstat3::Io, [0, 0, 0, 5]),
+        (Nfsstat3::NotDir, [0, 0, 0, 20]),
+        (Nfsstat3::Jukebox, (-10008i32).to_be_bytes()),
+    ] {
+        let bytes = to_bytes(&status).unwrap();
+        assert_eq!(bytes, wire, "{:?}", status);
+        assert_eq!(status, from_bytes::<Nfsstat3>(&bytes).unwrap());
+        assert_eq!(status, from_reader::<_, Nfsstat3>(std::io::Cursor::new(&bytes)).unwrap());
+        assert_eq!(i32::from_be_bytes(wire), status.discriminant());
+    }
+}
+
+#[test]
+fn test_discriminant_union_arms() {
+    let ok = LookupRes::Ok(LookupOk { object: vec![1, 2, 3], attr_follows: false });
+    let bytes = to_bytes(&ok).unwrap();
+    assert_eq!(&bytes[..4], [0, 0, 0, 0]);
+    assert_eq!(ok, from_bytes(&bytes).unwrap());
+
+    let io = LookupRes::Io(77);
+    let bytes = to_bytes(&io).unwrap();
+    assert_eq!(bytes, [0, 0, 0, 5, 0, 0, 0, 77]);
+    assert_eq!(io, from_bytes(&bytes).unwrap());
+    assert_eq!(io, from_reader(std::io::Cursor::new(&bytes)).unwrap());
+
+    assert_eq!(to_bytes(&LookupRes::NoEnt).unwrap(), [0, 0, 0, 2]);
+    assert_eq!(LookupRes::NoEnt, from_bytes(&[0, 0, 0, 2]).unwrap());
+}
+
+#[test]
+fn test_discriminant_unknown_value() {
+    let bytes = [0, 0, 0, 3];
+    assert_eq!(from_bytes::<Nfsstat3>(&bytes), Err(xdr_serde::Error::InvalidDiscriminant(3)));
+    assert_eq!(
+        from_reader::<_, LookupRes>(std::io::Cursor::new(bytes)),
+        Err(xdr_serde::Error::InvalidDiscriminant(3))
+    );
+    let bytes = (-1i32).to_be_bytes();
+    assert_eq!(from_bytes::<LookupRes>(&bytes), Err(xdr_serde::Error::InvalidDiscriminant(-1)));
+}
+
+#[test]
+fn test_derived_enum_out_of_range_index() {
+    // Plain serde enums use variant indices; an unknown index is reported the same way.
+    assert_eq!(from_bytes::<FileType>(&[0, 0, 0, 9]), Err(xdr_serde::Error::InvalidDiscriminant(9)));
+    assert_eq!(
+        from_reader::<_, NfsFileType>(std::io::Cursor::new([0, 0, 0, 7])),
+        Err(xdr_serde::Error::InvalidDiscriminant(7))
+    );
+}
+
+#[test]
+fn test_discriminant_enum_inside_containers() {
+    let v = vec![Some(Nfsstat3::Io), None, Some(Nfsstat3::Perm)];
+    let bytes = to_bytes(&v).unwrap();
+    assert_eq!(v, from_bytes::<Vec<Option<Nfsstat3>>>(&bytes).unwrap());
+}
+
+// ══════════════════════════════════════════════════════════════════════════
+// Union default arms
+// ══════════════════════════════════════════════════════════════════════════
+
+xdr_serde::xdr_enum! {
+    #[derive(Debug, PartialEq)]
+    enum AcceptStat {
+        Success(u32) = 0,
+        ProgUnavail = 1,
+        _ =>
+            /// Any other status; no body.
+            Other(i32),
+    }
+}
+
+xdr_serde::xdr_enum! {
+    #[derive(Debug, PartialEq)]
+    enum ResOp {
+        Access(u32) = 3,
+        Close(NfsStateId) = 4,
+        _ => Unknown(i32, String),
+    }
+}
+
+#[test]
+fn test_default_arm_void() {
+    let bytes = [0xFF, 0xFF, 0xFF, 0xFE];
+    let v: AcceptStat = from_bytes(&bytes).unwrap();
+    assert_eq!(v, AcceptStat::Other(-2));
+    assert_eq!(v.discriminant(), -2);
+    assert_eq!(to_bytes(&v).unwrap(), bytes);
+
+    // Listed discriminants still take their own arm.
+    assert_eq!(from_bytes::<AcceptStat>(&[0, 0, 0, 1]).unwrap(), AcceptStat::ProgUnavail);
+    let ok = AcceptStat::Success(9);
+    assert_eq!(ok, from_bytes(&to_bytes(&ok).unwrap()).unwrap());
+}
+
+#[test]
+fn test_default_arm_with_body_byte_identical() {
+    let mut bytes = vec![0, 0, 0, 42];
+    bytes.extend(to_bytes(&"opaque-ish body".to_string()).unwrap());
+
+    let v: ResOp = from_bytes(&bytes).unwrap();
+    assert_eq!(v, ResOp::Unknown(42, "opaque-ish body".into()));
+    assert_eq!(to_bytes(&v).unwrap(), bytes);
+
+    let v: ResOp = from_reader(std::io::Cursor::new(&bytes)).unwrap();
+    assert_eq!(v.discriminant(), 42);
+
+    let close = ResOp::Close(NfsStateId { seqid: 1, other: [7; 12] });
+    let bytes = to_bytes(&close).unwrap();
+    assert_eq!(&bytes[..4], [0, 0, 0, 4]);
+    assert_eq!(close, from_bytes(&bytes).unwrap());
+}
+
+#[test]
+fn test_default_arm_body_still_validated() {
+    // The default arm's body must still decode as its declared type.
+    assert_eq!(from_bytes::<ResOp>(&[0, 0, 0, 42, 0, 0]), Err(xdr_serde::Error::UnexpectedEof));
+}
+
+// ══════════════════════════════════════════════════════════════════════════
+// serialized_size
+// ══════════════════════════════════════════════════════════════════════════
+
+#[derive(Serialize)]
+struct SizedEverything {
+    small: (u8, i16, char, bool),
+    wide: (i64, u64, f32, f64),
+    name: String,
+    #[serde(with = "serde_bytes")]
+    blob: Vec<u8>,
+    list: Vec<u16>,
+    maybe: Option<Box<SizedEverything>>,
+    stateid: NfsStateId,
+    arm: ResOp,
+    unit: (),
+}
+
+#[test]
+fn test_serialized_size_matches_to_bytes() {
+    let inner = SizedEverything {
+        small: (1, -2, 'x', true),
+        wide: (-3, 4, 5.0, 6.0),
+        name: "abcde".into(),
+        blob: vec![1, 2, 3],
+        list: vec![],
+        maybe: None,
+        stateid: NfsStateId { seqid: 1, other: [0; 12] },
+        arm: ResOp::Unknown(42, "xy".into()),
+        unit: (),
+    };
+    let value = SizedEverything {
+        small: (0, 0, '\0', false),
+        wide: (0, 0, 0.0, 0.0),
+        name: String::new(),
+        blob: vec![9; 8],
+        list: vec![1, 2, 3],
+        maybe: Some(Box::new(inner)),
+        stateid: NfsStateId { seqid: 0, other: [0xAB; 12] },
+        arm: ResOp::Access(7),
+        unit: (),
+    };
+    assert_eq!(serialized_size(&value).unwrap(), to_bytes(&value).unwrap().len());
+
+    // u8/u16 widen to 4 bytes; fixed opaque has no prefix; strings pad.
+    assert_eq!(serialized_size(&7u8).unwrap(), 4);
+    assert_eq!(serialized_size(&NfsStateId { seqid: 0, other: [0; 12] }).unwrap(), 16);
+    for (s, size) in [("", 4), ("a", 8), ("abcd", 8), ("abcde", 12)] {
+        assert_eq!(serialized_size(&s).unwrap(), size);
+    }
+}
+
+#[derive(Serialize)]
+struct TooLong {
+    #[serde(serialize_with = "xdr_serde::bounded::serialize::<3, _, _>")]
+    name: String,
+}
+
+#[test]
+fn test_serialized_size_errors_like_to_bytes() {
+    let long = TooLong { name: "toolong".into() };
+    let overflow = xdr_serde::Error::LengthOverflow { max: 3, got: 7 };
+    assert_eq!(serialized_size(&long), Err(overflow.clone()));
+    assert_eq!(to_bytes(&long), Err(overflow));
+    assert_eq!(serialized_size(&vec![0u8; 5]).unwrap(), 4 + 5 * 4);
+}
+
+// ══════════════════════════════════════════════════════════════════════════
+// to_slice
+// ══════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_to_slice_matches_to_bytes() {
+    let value = (
+        NfsStateId { seqid: 3, other: [5; 12] },
+        "abc".to_string(),
+        Some(vec![1u16, 2]),
+        ResOp::Unknown(9, "z".into()),
+    );
+    let expected = to_bytes(&value).unwrap();
+    let size = serialized_size(&value).unwrap();
+
+    let mut buf = vec![0xEE; size + 5];
+    assert_eq!(to_slice(&value, &mut buf).unwrap(), size);
+    assert_eq!(&buf[..size], expected);
+    // Bytes past the encoding are left alone.
+    assert_eq!(&buf[size..], [0xEE; 5]);
+}
+
+#[test]
+fn test_to_slice_buffer_too_small() {
+    let mut exact = [0u8; 8];
+    assert_eq!(to_slice(&"abc", &mut exact), Ok(8));
+    assert_eq!(exact, [0, 0, 0, 3, b'a', b'b', b'c', 0]);
+
+    // One byte short: the padding no longer fits.
+    let mut short = [0u8; 7];
+    assert_eq!(to_slice(&"abc", &mut short), Err(xdr_serde::Error::BufferTooSmall));
+    assert_eq!(to_slice(&1u64, &mut [0u8; 4]), Err(xdr_serde::Error::BufferTooSmall));
+    assert_eq!(to_slice(&(), &mut []), Ok(0));
+}
+
+#[test]
+fn test_slice_writer_into_serializer() {
+    use xdr_serde::ser::{Serializer, SliceWriter};
+
+    let mut buf = [0u8; 12];
+    let mut ser = Serializer::new(SliceWriter::new(&mut buf));
+    7u32.serialize(&mut ser).unwrap();
+    "hi".serialize(&mut ser).unwrap();
+    assert_eq!(ser.into_writer().written(), [0, 0, 0, 7, 0, 0, 0, 2, b'h', b'i', 0, 0]);
+}
+
+// ══════════════════════════════════════════════════════════════════════════
+// Linked lists
+// ══════════════════════════════════════════════════════════════════════════
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Entry3 { fileid: u64, name: String, cookie: u64 }
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct EntryNode { entry: Entry3, next: Option<Box<EntryNode>> }
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct DirList3 {
+    #[serde(with = "xdr_serde::linked_list")]
+    entries: Vec<Entry3>,
+    eof: bool,
+}
+
+fn dir_entries(n: u64) -> Vec<Entry3> {
+    (0..n).map(|i| Entry3 { fileid: i + 100, name: format!("f{}", i), cookie: i + 1 }).collect()
+}
+
+#[test]
+fn test_linked_list_matches_nested_options() {
+    let mut nested = None;
+    for entry in dir_entries(3).into_iter().rev() {
+        nested = Some(Box::new(EntryNode { entry, next: nested }));
+    }
+    let mut expected = to_bytes(&nested).unwrap();
+    expected.extend(to_bytes(&true).unwrap());
+
+    let list = DirList3 { entries: dir_entries(3), eof: true };
+    let bytes = to_bytes(&list).unwrap();
+    assert_eq!(bytes, expected);
+    assert_eq!(serialized_size(&list).unwrap(), bytes.len());
+    assert_eq!(from_bytes::<DirList3>(&bytes).unwrap(), list);
+    assert_eq!(from_reader::<_, DirList3>(std::io::Cursor::new(&bytes)).unwrap(), list);
+}
+
+#[test]
+fn test_linked_list_empty() {
+    let list = DirList3 { entries: vec![], eof: false };
+    let bytes = to_bytes(&list).unwrap();
+    assert_eq!(bytes, [0, 0, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(from_bytes::<DirList3>(&bytes).unwrap(), list);
+}
+
+#[test]
+fn test_linked_list_long_list_is_iterative() {
+    // Deep enough to overflow the stack if each link recursed.
+    let list = DirList3 { entries: dir_entries(200_000), eof: true };
+    let bytes = to_bytes(&list).unwrap();
+    assert_eq!(from_bytes::<DirList3>(&bytes).unwrap(), list);
+    assert_eq!(from_reader::<_, DirList3>(std::io::Cursor::new(&bytes)).unwrap(), list);
+
+    // The struct, the list and an entry: three levels however long the list.
+    let limits = DecodeLimits { max_depth: 3, ..DecodeLimits::default() };
+    let mut de = Deserializer::new(&bytes).with_limits(limits);
+    assert_eq!(DirList3::deserialize(&mut de).unwrap(), list);
+}
+
+#[test]
+fn test_linked_list_counts_nodes_against_max_elements() {
+    let bytes = to_bytes(&DirList3 { entries: dir_entries(3), eof: true }).unwrap();
+    let limits = |max_elements| DecodeLimits { max_elements, ..DecodeLimits::default() };
+
+    let mut de = Deserializer::new(&bytes).with_limits(limits(3));
+    assert_eq!(DirList3::deserialize(&mut de).unwrap().entries, dir_entries(3));
+
+    let overflow = Err(xdr_serde::Error::LengthOverflow { max: 2, got: 3 });
+    let mut de = Deserializer::new(&bytes).with_limits(limits(2));
+    assert_eq!(DirList3::deserialize(&mut de), overflow);
+    let mut de = ReaderDeserializer::new(std::io::Cursor::new(&bytes)).with_limits(limits(2));
+    assert_eq!(DirList3::deserialize(&mut de), overflow);
+
+    // The empty list has no nodes to count.
+    let mut de = Deserializer::new(&[0, 0, 0, 0, 0, 0, 0, 1]).with_limits(limits(0));
+    assert_eq!(DirList3::deserialize(&mut de).unwrap().entries, []);
+}
+
+#[test]
+fn test_linked_list_malformed() {
+    let mut bytes = to_bytes(&DirList3 { entries: dir_entries(2), eof: true }).unwrap();
+    // The second pointer discriminant is neither FALSE nor TRUE.
+    let second = 4 + serialized_size(&dir_entries(1)[0]).unwrap();
+    bytes[second + 3] = 2;
+    assert_eq!(from_bytes::<DirList3>(&bytes), Err(xdr_serde::Error::InvalidBool(2)));
+    assert_eq!(
+        from_reader::<_, DirList3>(std::io::Cursor::new(&bytes)),
+        Err(xdr_serde::Error::InvalidBool(2))
+    );
+
+    // A list whose terminating FALSE is missing.
+    let bytes = to_bytes(&(true, &dir_entries(1)[0])).unwrap();
+    assert_eq!(from_bytes::<DirList3>(&bytes), Err(xdr_serde::Error::UnexpectedEof));
+}
@@ -0,0 +1,163 @@
+//! # xdr-serde-build
+//!
+//! Generate [`xdr-serde`] types from XDR `.x` files (RFC 4506 §6) in a
+//! `build.rs`, so the `.x` file stays the source of truth. In the build
+//! script's `main`:
+//!
+//! ```rust,no_run
+//! xdr_serde_build::compile(&["proto/nfs4.x"]).unwrap();
+//! ```
+//!
+//! Each `proto/NAME.x` becomes `$OUT_DIR/NAME.rs`, to be included from the
+//! crate:
+//!
+//! ```rust,ignore
+//! pub mod nfs4 {
+//!     include!(concat!(env!("OUT_DIR"), "/nfs4.rs"));
+//! }
+//! ```
+//!
+//! The generated code derives `XdrSerialize` / `XdrDeserialize`, so the crate
+//! needs `xdr-serde` with its `derive` feature. See [`xdr-serde-codegen`] for
+//! how XDR maps to Rust.
+//!
+//! Use a [`Builder`] to change the derives, rename types (or map them to
+//! existing ones), or borrow strings from the input:
+//!
+//! ```rust,no_run
+//! xdr_serde_build::Builder::new()
+//!     .with_derives(["Debug", "Clone"])
+//!     .with_type_name("nfs_fh4", "FileHandle")
+//!     .with_type_name("nfstime4", "crate::time::NfsTime")
+//!     .with_borrowed_strings(true)
+//!     .compile(&["proto/nfs4.x"])
+//!     .unwrap();
+//! ```
+//!
+//! [`xdr-serde`]: https://docs.rs/xdr-serde
+//! [`xdr-serde-codegen`]: https://docs.rs/xdr-serde-codegen
+
+use std::io;
+use std::path::{Path, PathBuf};
+use xdr_serde_codegen::{Options, compile_str};
+
+/// Compile `files` into `$OUT_DIR` with the default options.
+///
+/// Shorthand for `Builder::new().compile(files)`.
+pub fn compile<P: AsRef<Path>>(files: &[P]) -> io::Result<()> {
+    Builder::new().compile(files)
+}
+
+/// Configures and runs code generation.
+#[derive(Debug, Clone, Default)]
+pub struct Builder {
+    options: Options,
+    out_dir: Option<PathBuf>,
+}
+
+impl Builder {
+    pub fn new() -> Self {
+        Builder::default()
+    }
+
+    /// Derives for generated structs, unions and typedef newtypes, in
+    /// addition to `XdrSerialize` and `XdrDeserialize`.
+    ///
+    /// Defaults to `Debug, Clone, PartialEq`.
+    pub fn with_derives<I, S>(mut self, derives: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.options.derives = derives.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Derives for generated enums, in addition to `XdrSerialize` and
+    /// `XdrDeserialize`.
+    ///
+    /// Defaults to `Debug, Clone, Copy, PartialEq, Eq, Hash`.
+    pub fn with_enum_derives<I, S>(mut self, derives: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.options.enum_derives = derives.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Name the XDR type `xdr` as `rust` instead of its `UpperCamelCase` form.
+    ///
+    /// A path such as `crate::types::FileHandle` refers to an existing type:
+    /// `xdr`'s definition is not generated and every use of it refers to the
+    /// path instead.
+    pub fn with_type_name(mut self, xdr: impl Into<String>, rust: impl Into<String>) -> Self {
+        self.options.type_names.insert(xdr.into(), rust.into());
+        self
+    }
+
+    /// Generate `&'a str` instead of `String` for strings.
+    ///
+    /// Types containing strings then take a lifetime parameter and borrow
+    /// from the input when decoded with `xdr_serde::from_bytes_partial`.
+    pub fn with_borrowed_strings(mut self, borrowed: bool) -> Self {
+        self.options.borrowed_strings = borrowed;
+        self
+    }
+
+    /// Write generated files to `dir` instead of `$OUT_DIR`.
+    pub fn with_out_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.out_dir = Some(dir.into());
+        self
+    }
+
+    /// Generate `NAME.rs` in the output directory for each `NAME.x` in
+    /// `files`, and tell Cargo to rerun the build script when they change.
+    ///
+    /// A file is only rewritten when its contents change, so unchanged
+    /// output does not trigger a rebuild. Parse and generation errors are
+    /// returned as [`io::ErrorKind::InvalidData`], prefixed with the path.
+    pub fn compile<P: AsRef<Path>>(&self, files: &[P]) -> io::Result<()> {
+        let out_dir = match &self.out_dir {
+            Some(dir) => dir.clone(),
+            None => std::env::var_os("OUT_DIR")
+                .map(PathBuf::from)
+                .ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::NotFound,
+                        "OUT_DIR is not set; call compile from build.rs or use with_out_dir",
+                    )
+                })?,
+        };
+        for file in files {
+            let file = file.as_ref();
+            println!("cargo:rerun-if-changed={}", file.display());
+
+            let src = std::fs::read_to_string(file).map_err(|e| with_path(file, e))?;
+            let rust = compile_str(&src, &self.options).map_err(|e| {
+                with_path(
+                    file,
+                    io::Error::new(io::ErrorKind::InvalidData, e.to_string()),
+                )
+            })?;
+
+            let stem = file.file_stem().ok_or_else(|| {
+                with_path(
+                    file,
+                    io::Error::new(io::ErrorKind::InvalidInput, "not a file"),
+                )
+            })?;
+            let mut name = stem.to_os_string();
+            name.push(".rs");
+            let out = out_dir.join(name);
+            if std::fs::read_to_string(&out).ok().as_deref() != Some(rust.as_str()) {
+                std::fs::write(&out, rust).map_err(|e| with_path(&out, e))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn with_path(path: &Path, e: io::Error) -> io::Error {
+    io::Error::new(e.kind(), format!("{}: {}", path.display(), e))
+}
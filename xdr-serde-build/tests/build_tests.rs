@@ -0,0 +1,101 @@
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use xdr_serde_build::{Builder, compile};
+
+const DEMO: &str = "../xdr-serde-codegen/tests/fixtures/demo.x";
+
+/// A fresh directory under the system temp dir, unique to this test.
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("xdr-serde-build-{}-{}", std::process::id(), name));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+// ══════════════════════════════════════════════════════════════════════════
+// Builder
+// ══════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_compile_writes_stem_rs() {
+    let dir = temp_dir("stem");
+    Builder::new().with_out_dir(&dir).compile(&[DEMO]).unwrap();
+    let generated = std::fs::read_to_string(dir.join("demo.rs")).unwrap();
+    assert_eq!(
+        generated,
+        include_str!("../../xdr-serde-codegen/tests/fixtures/demo.rs")
+    );
+}
+
+#[test]
+fn test_compile_options() {
+    let dir = temp_dir("options");
+    let x = dir.join("names.x");
+    std::fs::write(
+        &x,
+        "typedef opaque fh<8>; struct entry { string name<>; fh handle; };",
+    )
+    .unwrap();
+    Builder::new()
+        .with_derives(["Debug"])
+        .with_type_name("fh", "crate::FileHandle")
+        .with_type_name("entry", "DirEntry")
+        .with_borrowed_strings(true)
+        .with_out_dir(&dir)
+        .compile(&[&x])
+        .unwrap();
+    let generated = std::fs::read_to_string(dir.join("names.rs")).unwrap();
+    assert!(!generated.contains("pub struct Fh"));
+    assert!(generated.contains(
+        "#[derive(Debug, ::xdr_serde::XdrSerialize, ::xdr_serde::XdrDeserialize)]\n\
+         pub struct DirEntry<'a> {\n    pub name: &'a str,\n    pub handle: crate::FileHandle,\n}"
+    ));
+}
+
+#[test]
+fn test_compile_leaves_unchanged_output() {
+    let dir = temp_dir("unchanged");
+    let builder = Builder::new().with_out_dir(&dir);
+    builder.compile(&[DEMO]).unwrap();
+    let out = dir.join("demo.rs");
+    let before = std::fs::metadata(&out).unwrap().modified().unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(20));
+    builder.compile(&[DEMO]).unwrap();
+    assert_eq!(std::fs::metadata(&out).unwrap().modified().unwrap(), before);
+}
+
+// ══════════════════════════════════════════════════════════════════════════
+// Errors
+// ══════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_compile_errors_name_the_file() {
+    let dir = temp_dir("errors");
+    let x = dir.join("bad.x");
+    std::fs::write(&x, "struct s {\n  int a\n};").unwrap();
+    let err = Builder::new()
+        .with_out_dir(&dir)
+        .compile(&[&x])
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+    assert_eq!(
+        err.to_string(),
+        format!("{}: line 3: expected `;`, found `}}`", x.display())
+    );
+    assert!(!dir.join("bad.rs").exists());
+
+    let missing = dir.join("missing.x");
+    let err = Builder::new()
+        .with_out_dir(&dir)
+        .compile(&[&missing])
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::NotFound);
+    assert!(err.to_string().starts_with(&missing.display().to_string()));
+}
+
+#[test]
+fn test_compile_requires_out_dir() {
+    // Integration tests of a crate without a build script have no OUT_DIR.
+    let err = compile(&[DEMO]).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::NotFound);
+}
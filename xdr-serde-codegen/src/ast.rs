@@ -0,0 +1,125 @@
+//! Syntax tree for the XDR language (RFC 4506 §6) and the ONC RPC program
+//! definitions of RFC 5531 §12.
+
+/// A parsed `.x` file: its definitions in source order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Specification {
+    pub definitions: Vec<Definition>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Definition {
+    /// `const NAME = 42;`
+    Const { name: String, value: i64 },
+    /// `typedef declaration;`
+    Typedef(Declaration),
+    /// `enum name { ... };`
+    Enum { name: String, body: EnumBody },
+    /// `struct name { ... };`
+    Struct { name: String, body: StructBody },
+    /// `union name switch (...) { ... };`
+    Union { name: String, body: UnionBody },
+    /// `program NAME { version ... } = 100003;`
+    Program(Program),
+}
+
+/// A constant or a reference to a named constant or enum value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    Constant(i64),
+    Name(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TypeSpec {
+    Int,
+    UnsignedInt,
+    Hyper,
+    UnsignedHyper,
+    Float,
+    Double,
+    Quadruple,
+    Bool,
+    /// `opaque`; only valid in a fixed- or variable-length declaration.
+    Opaque,
+    /// `string`; only valid in a variable-length declaration.
+    String,
+    Enum(EnumBody),
+    Struct(StructBody),
+    Union(Box<UnionBody>),
+    /// A type defined elsewhere, by name.
+    Named(String),
+}
+
+/// One `identifier` together with its type, or `void`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Declaration {
+    Void,
+    Named {
+        name: String,
+        ty: TypeSpec,
+        kind: DeclKind,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeclKind {
+    /// `type name`
+    Scalar,
+    /// `type name[N]`
+    FixedArray(Value),
+    /// `type name<N>`, or `type name<>` without a bound.
+    VarArray(Option<Value>),
+    /// `type *name`
+    Optional,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnumBody {
+    pub members: Vec<(String, Value)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StructBody {
+    pub fields: Vec<Declaration>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnionBody {
+    /// The `switch (...)` declaration.
+    pub discriminant: Declaration,
+    pub arms: Vec<UnionArm>,
+    /// The `default:` arm, if any.
+    pub default: Option<Declaration>,
+}
+
+/// One or more `case` labels sharing a declaration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnionArm {
+    pub cases: Vec<Value>,
+    pub decl: Declaration,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Program {
+    pub name: String,
+    pub number: i64,
+    pub versions: Vec<Version>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Version {
+    pub name: String,
+    pub number: i64,
+    pub procedures: Vec<Procedure>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Procedure {
+    pub name: String,
+    pub number: i64,
+    /// The result type; `None` for `void`.
+    pub result: Option<TypeSpec>,
+    /// The argument types; empty for `void`.
+    pub args: Vec<TypeSpec>,
+}
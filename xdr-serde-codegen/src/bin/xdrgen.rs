@@ -0,0 +1,79 @@
+//! `xdrgen`: generate Rust types for xdr-serde from an XDR `.x` file.
+
+use std::process::ExitCode;
+use xdr_serde_codegen::{Options, compile_str};
+
+const USAGE: &str = "\
+usage: xdrgen [-o OUTPUT] [--type XDR=RUST]... [--borrowed-strings] INPUT.x
+
+Generate Rust types for xdr-serde from an XDR language file (RFC 4506 §6).
+Writes to standard output unless -o is given.
+
+options:
+  -o, --output FILE   write the generated code to FILE
+  --type XDR=RUST     name the XDR type XDR as RUST; a path (`a::B`) refers
+                      to an existing type, which is not generated
+  --borrowed-strings  generate `&'a str` instead of `String`
+  -h, --help          print this help";
+
+fn main() -> ExitCode {
+    let mut input = None;
+    let mut output = None;
+    let mut options = Options::default();
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return ExitCode::SUCCESS;
+            }
+            "-o" | "--output" => match args.next() {
+                Some(path) => output = Some(path),
+                None => return usage_error("missing argument to -o"),
+            },
+            "--type" => match args.next().as_deref().and_then(|a| a.split_once('=')) {
+                Some((xdr, rust)) => {
+                    options.type_names.insert(xdr.into(), rust.into());
+                }
+                None => return usage_error("expected XDR=RUST after --type"),
+            },
+            "--borrowed-strings" => options.borrowed_strings = true,
+            _ if arg.starts_with('-') => return usage_error(&format!("unknown option `{}`", arg)),
+            _ if input.is_none() => input = Some(arg),
+            _ => return usage_error("more than one input file"),
+        }
+    }
+    let Some(input) = input else {
+        return usage_error("no input file");
+    };
+
+    let src = match std::fs::read_to_string(&input) {
+        Ok(src) => src,
+        Err(e) => {
+            eprintln!("xdrgen: {}: {}", input, e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let rust = match compile_str(&src, &options) {
+        Ok(rust) => rust,
+        Err(e) => {
+            eprintln!("xdrgen: {}: {}", input, e);
+            return ExitCode::FAILURE;
+        }
+    };
+    match output {
+        Some(path) => {
+            if let Err(e) = std::fs::write(&path, rust) {
+                eprintln!("xdrgen: {}: {}", path, e);
+                return ExitCode::FAILURE;
+            }
+        }
+        None => print!("{}", rust),
+    }
+    ExitCode::SUCCESS
+}
+
+fn usage_error(message: &str) -> ExitCode {
+    eprintln!("xdrgen: {}\n\n{}", message, USAGE);
+    ExitCode::from(2)
+}
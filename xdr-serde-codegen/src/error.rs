@@ -0,0 +1,47 @@
+use std::fmt;
+
+/// A parse or code generation error, with the source line when known.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error {
+    line: Option<usize>,
+    message: String,
+}
+
+impl Error {
+    pub(crate) fn at(line: usize, message: impl Into<String>) -> Self {
+        Error {
+            line: Some(line),
+            message: message.into(),
+        }
+    }
+
+    pub(crate) fn new(message: impl Into<String>) -> Self {
+        Error {
+            line: None,
+            message: message.into(),
+        }
+    }
+
+    /// The 1-based source line the error refers to.
+    pub fn line(&self) -> Option<usize> {
+        self.line
+    }
+
+    /// The error message, without the line.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {}: {}", line, self.message),
+            None => f.write_str(&self.message),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+pub type Result<T> = std::result::Result<T, Error>;
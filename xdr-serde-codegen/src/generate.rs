@@ -0,0 +1,720 @@
+//! Rust code generation from a parsed [`Specification`].
+//!
+//! Generated types use `#[derive(XdrSerialize, XdrDeserialize)]` from
+//! `xdr-serde` (its `derive` feature), with `#[xdr(...)]` attributes for
+//! discriminants, bounds and opaque data.
+
+use crate::ast::*;
+use crate::error::{Error, Result};
+use crate::naming::{camel, snake};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::Write;
+
+/// Code generation options.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Options {
+    /// Derives for structs, unions and typedef newtypes, in addition to
+    /// `XdrSerialize` and `XdrDeserialize`.
+    pub derives: Vec<String>,
+    /// Derives for enums, in addition to `XdrSerialize` and `XdrDeserialize`.
+    pub enum_derives: Vec<String>,
+    /// Rust names for XDR types, overriding the `UpperCamelCase` default.
+    /// Keys are XDR names; an inline type is named `{parent}_{field}`. A
+    /// value containing `::` names an existing type: the definition is not
+    /// generated and references use the path.
+    pub type_names: BTreeMap<String, String>,
+    /// Generate `&'a str` instead of `String` for strings. Types that
+    /// contain a string, directly or through other generated types, take a
+    /// lifetime parameter `'a` and borrow from the input when decoded with
+    /// `from_bytes_partial`.
+    pub borrowed_strings: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            derives: ["Debug", "Clone", "PartialEq"].map(String::from).to_vec(),
+            enum_derives: ["Debug", "Clone", "Copy", "PartialEq", "Eq", "Hash"]
+                .map(String::from)
+                .to_vec(),
+            type_names: BTreeMap::new(),
+            borrowed_strings: false,
+        }
+    }
+}
+
+/// Generate Rust source for `spec`.
+pub fn generate(spec: &Specification, options: &Options) -> Result<String> {
+    let mut items = Vec::new();
+    for def in &spec.definitions {
+        hoist_definition(def, &mut items);
+    }
+
+    let mut generator = Generator {
+        options,
+        consts: HashMap::new(),
+        members: HashMap::new(),
+        lifetimes: HashSet::new(),
+        emitted: HashMap::new(),
+        out: String::new(),
+    };
+    generator.collect(&items)?;
+
+    generator
+        .out
+        .push_str("// Generated by xdr-serde-codegen. Do not edit.\n");
+    let mut previous_const = false;
+    for item in &items {
+        if item.name().is_some_and(|name| generator.is_external(name)) {
+            continue;
+        }
+        // Runs of constants are kept together.
+        let is_const = matches!(item, Item::Const(..));
+        if !(is_const && previous_const) {
+            generator.out.push('\n');
+        }
+        previous_const = is_const;
+        generator.item(item)?;
+    }
+    Ok(generator.out)
+}
+
+/// A top-level item, after inline type definitions have been given names.
+enum Item<'a> {
+    Const(&'a str, i64),
+    Enum(String, EnumBody),
+    Struct(String, StructBody),
+    Union(String, UnionBody),
+    Typedef(Declaration),
+    Program(&'a Program),
+}
+
+impl Item<'_> {
+    /// The XDR name of a type definition.
+    fn name(&self) -> Option<&str> {
+        match self {
+            Item::Enum(name, _) | Item::Struct(name, _) | Item::Union(name, _) => Some(name),
+            Item::Typedef(Declaration::Named { name, .. }) => Some(name),
+            _ => None,
+        }
+    }
+
+    /// The declarations a struct, union or typedef is made of.
+    fn decls(&self) -> Vec<&Declaration> {
+        match self {
+            Item::Struct(_, body) => body.fields.iter().collect(),
+            Item::Union(_, body) => body
+                .arms
+                .iter()
+                .map(|arm| &arm.decl)
+                .chain(&body.default)
+                .collect(),
+            Item::Typedef(decl) => vec![decl],
+            _ => Vec::new(),
+        }
+    }
+}
+
+fn hoist_definition<'a>(def: &'a Definition, items: &mut Vec<Item<'a>>) {
+    match def {
+        Definition::Const { name, value } => items.push(Item::Const(name, *value)),
+        Definition::Enum { name, body } => items.push(Item::Enum(name.clone(), body.clone())),
+        Definition::Struct { name, body } => {
+            let body = hoist_struct(name, body.clone(), items);
+            items.push(Item::Struct(name.clone(), body));
+        }
+        Definition::Union { name, body } => {
+            let body = hoist_union(name, body.clone(), items);
+            items.push(Item::Union(name.clone(), body));
+        }
+        // `typedef struct { ... } name;` defines the type directly.
+        Definition::Typedef(Declaration::Named {
+            name,
+            ty,
+            kind: DeclKind::Scalar,
+        }) if is_inline(ty) => {
+            let item = match ty.clone() {
+                TypeSpec::Enum(body) => Item::Enum(name.clone(), body),
+                TypeSpec::Struct(body) => {
+                    Item::Struct(name.clone(), hoist_struct(name, body, items))
+                }
+                TypeSpec::Union(body) => Item::Union(name.clone(), hoist_union(name, *body, items)),
+                _ => unreachable!(),
+            };
+            items.push(item);
+        }
+        Definition::Typedef(decl) => {
+            let decl = hoist_decl("", decl.clone(), items);
+            items.push(Item::Typedef(decl));
+        }
+        Definition::Program(program) => items.push(Item::Program(program)),
+    }
+}
+
+fn is_inline(ty: &TypeSpec) -> bool {
+    matches!(
+        ty,
+        TypeSpec::Enum(_) | TypeSpec::Struct(_) | TypeSpec::Union(_)
+    )
+}
+
+fn hoist_struct(parent: &str, mut body: StructBody, items: &mut Vec<Item>) -> StructBody {
+    body.fields = body
+        .fields
+        .into_iter()
+        .map(|f| hoist_decl(parent, f, items))
+        .collect();
+    body
+}
+
+fn hoist_union(parent: &str, mut body: UnionBody, items: &mut Vec<Item>) -> UnionBody {
+    body.discriminant = hoist_decl(parent, body.discriminant, items);
+    for arm in &mut body.arms {
+        arm.decl = hoist_decl(
+            parent,
+            std::mem::replace(&mut arm.decl, Declaration::Void),
+            items,
+        );
+    }
+    body.default = body.default.map(|d| hoist_decl(parent, d, items));
+    body
+}
+
+/// Replace an inline enum, struct or union type with a reference to a new
+/// top-level type named `{parent}_{field}`.
+fn hoist_decl(parent: &str, decl: Declaration, items: &mut Vec<Item>) -> Declaration {
+    let Declaration::Named { name, ty, kind } = decl else {
+        return decl;
+    };
+    if !is_inline(&ty) {
+        return Declaration::Named { name, ty, kind };
+    }
+    let hoisted = if parent.is_empty() {
+        format!("{}_body", name)
+    } else {
+        format!("{}_{}", parent, name)
+    };
+    let item = match ty {
+        TypeSpec::Enum(body) => Item::Enum(hoisted.clone(), body),
+        TypeSpec::Struct(body) => {
+            Item::Struct(hoisted.clone(), hoist_struct(&hoisted, body, items))
+        }
+        TypeSpec::Union(body) => Item::Union(hoisted.clone(), hoist_union(&hoisted, *body, items)),
+        _ => unreachable!(),
+    };
+    items.push(item);
+    Declaration::Named {
+        name,
+        ty: TypeSpec::Named(hoisted),
+        kind,
+    }
+}
+
+/// The Rust type a constant or discriminant expression is needed as.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Want {
+    U32,
+    I32,
+    Usize,
+}
+
+impl Want {
+    fn as_str(self) -> &'static str {
+        match self {
+            Want::U32 => "u32",
+            Want::I32 => "i32",
+            Want::Usize => "usize",
+        }
+    }
+}
+
+/// The `#[xdr(...)]` attribute (if any) and Rust type of a declaration.
+type Field = (Option<String>, String);
+
+struct Generator<'a> {
+    options: &'a Options,
+    /// XDR constant name to the Rust type it is emitted with.
+    consts: HashMap<String, &'static str>,
+    /// Enum member name to its Rust path.
+    members: HashMap<String, String>,
+    /// Types that take a lifetime parameter for borrowed strings.
+    lifetimes: HashSet<String>,
+    /// Program, version and procedure constants already emitted.
+    emitted: HashMap<String, i64>,
+    out: String,
+}
+
+impl Generator<'_> {
+    fn collect(&mut self, items: &[Item]) -> Result<()> {
+        for item in items {
+            match item {
+                Item::Const(name, value) => {
+                    self.consts.insert(name.to_string(), const_type(*value));
+                }
+                Item::Enum(name, body) => {
+                    for (member, _) in &body.members {
+                        let path = format!("{}::{}", self.rust_name(name), camel(member));
+                        if self.members.insert(member.clone(), path).is_some() {
+                            return Err(Error::new(format!(
+                                "enum value `{}` is defined more than once",
+                                member
+                            )));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        // A type borrows if any of its declarations is a string or a
+        // borrowing type; iterate until no more types are found.
+        if self.options.borrowed_strings {
+            loop {
+                let found: Vec<&str> = items
+                    .iter()
+                    .filter_map(|item| {
+                        let name = item.name()?;
+                        let new = !self.lifetimes.contains(name) && !self.is_external(name);
+                        (new && item.decls().into_iter().any(|d| self.borrows(d))).then_some(name)
+                    })
+                    .collect();
+                if found.is_empty() {
+                    break;
+                }
+                self.lifetimes.extend(found.into_iter().map(String::from));
+            }
+        }
+        Ok(())
+    }
+
+    fn borrows(&self, decl: &Declaration) -> bool {
+        match decl {
+            Declaration::Named {
+                ty: TypeSpec::String,
+                ..
+            } => self.options.borrowed_strings,
+            Declaration::Named {
+                ty: TypeSpec::Named(name),
+                ..
+            } => self.lifetimes.contains(name),
+            _ => false,
+        }
+    }
+
+    /// Whether `name` is mapped to an existing type rather than generated.
+    fn is_external(&self, name: &str) -> bool {
+        self.options
+            .type_names
+            .get(name)
+            .is_some_and(|rust| rust.contains("::"))
+    }
+
+    /// The Rust name of the XDR type `name`, without generics.
+    fn rust_name(&self, name: &str) -> String {
+        match self.options.type_names.get(name) {
+            Some(rust) => rust.clone(),
+            None => camel(name),
+        }
+    }
+
+    /// The Rust type for a reference to the XDR type `name`.
+    fn type_ref(&self, name: &str) -> String {
+        format!("{}{}", self.rust_name(name), self.generics(name))
+    }
+
+    /// `<'a>` if `name` borrows strings.
+    fn generics(&self, name: &str) -> &'static str {
+        if self.lifetimes.contains(name) {
+            "<'a>"
+        } else {
+            ""
+        }
+    }
+
+    /// A Rust expression for `value` of type `want`.
+    fn value(&self, value: &Value, want: Want) -> Result<String> {
+        match value {
+            Value::Constant(n) => {
+                let n = *n;
+                match want {
+                    Want::I32 if i32::try_from(n).is_ok() => Ok(n.to_string()),
+                    Want::I32 if u32::try_from(n).is_ok() => Ok(format!("{:#X}_u32 as i32", n)),
+                    Want::U32 | Want::Usize if u32::try_from(n).is_ok() => Ok(n.to_string()),
+                    _ => Err(Error::new(format!(
+                        "{} does not fit in {}",
+                        n,
+                        want.as_str()
+                    ))),
+                }
+            }
+            Value::Name(name) => {
+                if let Some(ty) = self.consts.get(name) {
+                    if *ty == want.as_str() {
+                        return Ok(name.clone());
+                    }
+                    return Ok(format!("{} as {}", name, want.as_str()));
+                }
+                if let Some(path) = self.members.get(name) {
+                    return Ok(format!("{} as {}", path, want.as_str()));
+                }
+                match name.as_str() {
+                    "TRUE" => return Ok("1".into()),
+                    "FALSE" => return Ok("0".into()),
+                    _ => {}
+                }
+                Err(Error::new(format!("unknown constant `{}`", name)))
+            }
+        }
+    }
+
+    fn derive(&mut self, derives: &[String]) {
+        let mut all: Vec<&str> = derives.iter().map(String::as_str).collect();
+        all.extend(["::xdr_serde::XdrSerialize", "::xdr_serde::XdrDeserialize"]);
+        writeln!(self.out, "#[derive({})]", all.join(", ")).unwrap();
+    }
+
+    fn item(&mut self, item: &Item) -> Result<()> {
+        match item {
+            Item::Const(name, value) => {
+                self.constant(name, *value, None);
+                Ok(())
+            }
+            Item::Enum(name, body) => self.enumeration(name, body),
+            Item::Struct(name, body) => self.structure(name, body),
+            Item::Union(name, body) => self.union(name, body),
+            Item::Typedef(decl) => self.typedef(decl),
+            Item::Program(program) => self.program(program),
+        }
+    }
+
+    fn constant(&mut self, name: &str, value: i64, doc: Option<String>) {
+        if let Some(doc) = doc {
+            writeln!(self.out, "/// {}", doc).unwrap();
+        }
+        if name.chars().any(|c| c.is_ascii_lowercase()) {
+            self.out.push_str("#[allow(non_upper_case_globals)]\n");
+        }
+        writeln!(
+            self.out,
+            "pub const {}: {} = {};",
+            name,
+            const_type(value),
+            value
+        )
+        .unwrap();
+    }
+
+    fn enumeration(&mut self, name: &str, body: &EnumBody) -> Result<()> {
+        let derives = self.options.enum_derives.clone();
+        self.derive(&derives);
+        self.out.push_str("#[repr(i32)]\n");
+        // Variants keep the XDR names, prefixes and all.
+        self.out.push_str("#[allow(clippy::enum_variant_names)]\n");
+        writeln!(self.out, "pub enum {} {{", self.rust_name(name)).unwrap();
+        for (member, value) in &body.members {
+            let value = self.value(value, Want::I32)?;
+            writeln!(self.out, "    {} = {},", camel(member), value).unwrap();
+        }
+        self.out.push_str("}\n");
+        Ok(())
+    }
+
+    fn structure(&mut self, name: &str, body: &StructBody) -> Result<()> {
+        let derives = self.options.derives.clone();
+        self.derive(&derives);
+        writeln!(self.out, "pub struct {} {{", self.type_ref(name)).unwrap();
+        for field in &body.fields {
+            let Declaration::Named {
+                name: field_name, ..
+            } = field
+            else {
+                unreachable!("the parser rejects void struct fields");
+            };
+            let (attr, ty) = self.field(field)?;
+            if let Some(attr) = attr {
+                writeln!(self.out, "    {}", attr).unwrap();
+            }
+            writeln!(self.out, "    pub {}: {},", snake(field_name), ty).unwrap();
+        }
+        self.out.push_str("}\n");
+        Ok(())
+    }
+
+    fn union(&mut self, name: &str, body: &UnionBody) -> Result<()> {
+        let mut variants: Vec<(String, String, Option<Field>)> = Vec::new();
+        for arm in &body.arms {
+            let field = match &arm.decl {
+                Declaration::Void => None,
+                decl => Some(self.field(decl)?),
+            };
+            for case in &arm.cases {
+                let variant = arm_name(case, &arm.decl, arm.cases.len(), body);
+                if variants.iter().any(|(v, ..)| *v == variant) {
+                    return Err(Error::new(format!(
+                        "union `{}` has two arms named `{}`",
+                        name, variant
+                    )));
+                }
+                let disc = self.value(case, Want::I32)?;
+                variants.push((variant, disc, field.clone()));
+            }
+        }
+
+        let derives = self.options.derives.clone();
+        self.derive(&derives);
+        self.out
+            .push_str("#[allow(clippy::enum_variant_names, clippy::large_enum_variant)]\n");
+        writeln!(self.out, "pub enum {} {{", self.type_ref(name)).unwrap();
+        for (variant, disc, field) in variants {
+            writeln!(self.out, "    #[xdr(discriminant = {})]", disc).unwrap();
+            match field {
+                None => writeln!(self.out, "    {},", variant).unwrap(),
+                Some((attr, ty)) => writeln!(
+                    self.out,
+                    "    {}({}{}),",
+                    variant,
+                    attr.map(|a| a + " ").unwrap_or_default(),
+                    ty
+                )
+                .unwrap(),
+            }
+        }
+        if let Some(default) = &body.default {
+            self.out.push_str("    #[xdr(default_arm)]\n");
+            match default {
+                Declaration::Void => self.out.push_str("    Default(i32),\n"),
+                decl => {
+                    let (attr, ty) = self.field(decl)?;
+                    writeln!(
+                        self.out,
+                        "    Default(i32, {}{}),",
+                        attr.map(|a| a + " ").unwrap_or_default(),
+                        ty
+                    )
+                    .unwrap();
+                }
+            }
+        }
+        self.out.push_str("}\n");
+        Ok(())
+    }
+
+    fn typedef(&mut self, decl: &Declaration) -> Result<()> {
+        let Declaration::Named { name, .. } = decl else {
+            unreachable!("the parser rejects `typedef void`");
+        };
+        let (attr, ty) = self.field(decl)?;
+        match attr {
+            // Attributes only apply to fields, so the typedef becomes a
+            // newtype, which XDR encodes exactly like its field.
+            Some(attr) => {
+                let derives = self.options.derives.clone();
+                self.derive(&derives);
+                writeln!(
+                    self.out,
+                    "pub struct {}({} pub {});",
+                    self.type_ref(name),
+                    attr,
+                    ty
+                )
+                .unwrap();
+            }
+            None => writeln!(self.out, "pub type {} = {};", self.type_ref(name), ty).unwrap(),
+        }
+        Ok(())
+    }
+
+    fn program(&mut self, program: &Program) -> Result<()> {
+        self.program_constant(
+            &program.name,
+            program.number,
+            format!("Program `{}`.", program.name),
+        )?;
+        for version in &program.versions {
+            self.out.push('\n');
+            self.program_constant(
+                &version.name,
+                version.number,
+                format!("Version `{}` of program `{}`.", version.name, program.name),
+            )?;
+            for procedure in &version.procedures {
+                let args = if procedure.args.is_empty() {
+                    "void".to_string()
+                } else {
+                    procedure
+                        .args
+                        .iter()
+                        .map(xdr_type)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                };
+                let result = procedure
+                    .result
+                    .as_ref()
+                    .map_or("void".to_string(), xdr_type);
+                let doc = format!("`{}({}) -> {}`", procedure.name, args, result);
+                self.program_constant(&procedure.name, procedure.number, doc)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Emit a program, version or procedure number. Procedures often keep
+    /// their names across versions; a repeated name with the same number is
+    /// emitted once.
+    fn program_constant(&mut self, name: &str, value: i64, doc: String) -> Result<()> {
+        match self.emitted.get(name) {
+            Some(&previous) if previous == value => return Ok(()),
+            Some(_) => {
+                return Err(Error::new(format!(
+                    "`{}` is defined with two different numbers",
+                    name
+                )));
+            }
+            None => {}
+        }
+        if self.consts.contains_key(name) {
+            return Err(Error::new(format!("`{}` is defined more than once", name)));
+        }
+        self.emitted.insert(name.to_string(), value);
+        self.constant(name, value, Some(doc));
+        Ok(())
+    }
+
+    /// The `#[xdr(...)]` attribute (if any) and Rust type for a declaration.
+    fn field(&self, decl: &Declaration) -> Result<Field> {
+        let Declaration::Named { ty, kind, .. } = decl else {
+            return Err(Error::new("unexpected void declaration"));
+        };
+        Ok(match (ty, kind) {
+            (TypeSpec::Opaque, DeclKind::FixedArray(len)) => (
+                Some("#[xdr(fixed)]".into()),
+                format!("[u8; {}]", self.value(len, Want::Usize)?),
+            ),
+            (TypeSpec::Opaque, DeclKind::VarArray(None)) => {
+                (Some("#[xdr(opaque)]".into()), "Vec<u8>".into())
+            }
+            (TypeSpec::Opaque, DeclKind::VarArray(Some(max))) => (
+                Some(format!(
+                    "#[xdr(opaque, max = {})]",
+                    self.value(max, Want::U32)?
+                )),
+                "Vec<u8>".into(),
+            ),
+            (TypeSpec::String, DeclKind::VarArray(None)) => (None, self.string_type()),
+            (TypeSpec::String, DeclKind::VarArray(Some(max))) => (
+                Some(format!("#[xdr(max = {})]", self.value(max, Want::U32)?)),
+                self.string_type(),
+            ),
+            (TypeSpec::Quadruple, DeclKind::Scalar) => {
+                (Some("#[xdr(fixed)]".into()), "[u8; 16]".into())
+            }
+            (TypeSpec::Quadruple, _) => {
+                return Err(Error::new("arrays of quadruple are not supported"));
+            }
+            (ty, DeclKind::Scalar) => (None, self.base_type(ty)),
+            (ty, DeclKind::FixedArray(len)) => (
+                None,
+                format!(
+                    "[{}; {}]",
+                    self.base_type(ty),
+                    self.value(len, Want::Usize)?
+                ),
+            ),
+            (ty, DeclKind::VarArray(None)) => (None, format!("Vec<{}>", self.base_type(ty))),
+            (ty, DeclKind::VarArray(Some(max))) => (
+                Some(format!("#[xdr(max = {})]", self.value(max, Want::U32)?)),
+                format!("Vec<{}>", self.base_type(ty)),
+            ),
+            // Named types may be recursive (linked lists), so they are boxed.
+            (TypeSpec::Named(name), DeclKind::Optional) => {
+                (None, format!("Option<Box<{}>>", self.type_ref(name)))
+            }
+            (ty, DeclKind::Optional) => (None, format!("Option<{}>", self.base_type(ty))),
+        })
+    }
+
+    fn string_type(&self) -> String {
+        if self.options.borrowed_strings {
+            "&'a str".into()
+        } else {
+            "String".into()
+        }
+    }
+
+    fn base_type(&self, ty: &TypeSpec) -> String {
+        match ty {
+            TypeSpec::Int => "i32".into(),
+            TypeSpec::UnsignedInt => "u32".into(),
+            TypeSpec::Hyper => "i64".into(),
+            TypeSpec::UnsignedHyper => "u64".into(),
+            TypeSpec::Float => "f32".into(),
+            TypeSpec::Double => "f64".into(),
+            TypeSpec::Bool => "bool".into(),
+            TypeSpec::Named(name) => self.type_ref(name),
+            TypeSpec::Quadruple
+            | TypeSpec::Opaque
+            | TypeSpec::String
+            | TypeSpec::Enum(_)
+            | TypeSpec::Struct(_)
+            | TypeSpec::Union(_) => unreachable!("handled before base_type"),
+        }
+    }
+}
+
+/// The narrowest of `u32`, `i32` and `i64` that holds `value`.
+fn const_type(value: i64) -> &'static str {
+    if u32::try_from(value).is_ok() {
+        "u32"
+    } else if i32::try_from(value).is_ok() {
+        "i32"
+    } else {
+        "i64"
+    }
+}
+
+/// A type as written in XDR, for documentation.
+fn xdr_type(ty: &TypeSpec) -> String {
+    match ty {
+        TypeSpec::Int => "int".into(),
+        TypeSpec::UnsignedInt => "unsigned int".into(),
+        TypeSpec::Hyper => "hyper".into(),
+        TypeSpec::UnsignedHyper => "unsigned hyper".into(),
+        TypeSpec::Float => "float".into(),
+        TypeSpec::Double => "double".into(),
+        TypeSpec::Quadruple => "quadruple".into(),
+        TypeSpec::Bool => "bool".into(),
+        TypeSpec::Opaque => "opaque".into(),
+        TypeSpec::String => "string".into(),
+        TypeSpec::Enum(_) => "enum { ... }".into(),
+        TypeSpec::Struct(_) => "struct { ... }".into(),
+        TypeSpec::Union(_) => "union { ... }".into(),
+        TypeSpec::Named(name) => name.clone(),
+    }
+}
+
+/// The variant name for one `case` of a union arm: the case label when it is
+/// a name, otherwise the arm's field name, falling back to `Case{n}`.
+fn arm_name(case: &Value, decl: &Declaration, cases: usize, body: &UnionBody) -> String {
+    match case {
+        Value::Name(name) => camel(name),
+        Value::Constant(n) => {
+            let field = match decl {
+                Declaration::Named { name, .. } if cases == 1 => Some(name),
+                _ => None,
+            };
+            let unique = field.is_some_and(|f| {
+                body.arms
+                    .iter()
+                    .filter(|a| matches!(&a.decl, Declaration::Named { name, .. } if name == f))
+                    .count()
+                    == 1
+            });
+            match field {
+                Some(field) if unique => camel(field),
+                _ if *n < 0 => format!("CaseMinus{}", n.unsigned_abs()),
+                _ => format!("Case{}", n),
+            }
+        }
+    }
+}
@@ -0,0 +1,66 @@
+//! # xdr-serde-codegen
+//!
+//! A parser for the XDR language (RFC 4506 §6, `.x` files as read by
+//! `rpcgen`) and a generator of Rust types for [`xdr-serde`].
+//!
+//! Every definition in the language is supported: `const`, `typedef`,
+//! `enum`, `struct`, `union` (including `default:` arms), optional data
+//! (`type *name`), and the `program` / `version` blocks of ONC RPC
+//! (RFC 5531 §12). Generated types derive `XdrSerialize` / `XdrDeserialize`,
+//! so they need `xdr-serde` with its `derive` feature:
+//!
+//! | XDR                     | Rust                                              |
+//! |-------------------------|---------------------------------------------------|
+//! | `const NAME = 4;`       | `pub const NAME: u32 = 4;`                        |
+//! | `opaque x[N]`           | `#[xdr(fixed)] x: [u8; N]`                        |
+//! | `opaque x<N>`           | `#[xdr(opaque, max = N)] x: Vec<u8>`              |
+//! | `string x<N>`           | `#[xdr(max = N)] x: String`                       |
+//! | `T x<N>`                | `#[xdr(max = N)] x: Vec<T>`                       |
+//! | `T x[N]`                | `x: [T; N]`                                       |
+//! | `T *x`                  | `x: Option<Box<T>>`                               |
+//! | `typedef opaque t<N>;`  | `pub struct T(#[xdr(opaque, max = N)] pub Vec<u8>)` |
+//! | `typedef T u;`          | `pub type U = T;`                                 |
+//! | `program` / `version`   | `pub const` program, version and procedure numbers |
+//!
+//! Type and variant names are converted to `UpperCamelCase` and fields to
+//! `snake_case`. A union arm is named after its case label; a union's
+//! `default:` arm becomes `Default(i32)` or `Default(i32, T)`. Inline type
+//! definitions become top-level types named after their parent and field.
+//!
+//! ```rust
+//! use xdr_serde_codegen::{Options, compile_str};
+//!
+//! let rust = compile_str(
+//!     "const MAXNAMLEN = 255;
+//!      struct entry { unsigned hyper fileid; string name<MAXNAMLEN>; entry *next; };",
+//!     &Options::default(),
+//! )
+//! .unwrap();
+//! assert!(rust.contains("pub struct Entry {"));
+//! assert!(rust.contains("    #[xdr(max = MAXNAMLEN)]\n    pub name: String,"));
+//! assert!(rust.contains("    pub next: Option<Box<Entry>>,"));
+//! ```
+//!
+//! [`Options`] select the derives, rename types (or map them onto existing
+//! ones), and switch strings to borrowed `&'a str`.
+//!
+//! The `xdrgen` binary wraps [`compile_str`]: `xdrgen nfs_prot.x -o nfs_prot.rs`.
+//! To generate code from `build.rs`, use `xdr-serde-build`.
+//!
+//! [`xdr-serde`]: https://docs.rs/xdr-serde
+
+pub mod ast;
+mod error;
+mod generate;
+mod lexer;
+mod naming;
+mod parser;
+
+pub use error::{Error, Result};
+pub use generate::{Options, generate};
+pub use parser::parse;
+
+/// Parse XDR source and generate Rust source for it.
+pub fn compile_str(src: &str, options: &Options) -> Result<String> {
+    generate(&parse(src)?, options)
+}
@@ -0,0 +1,70 @@
+//! Mapping XDR identifiers onto Rust naming conventions.
+
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "dyn", "else", "enum", "extern", "false",
+    "fn", "for", "gen", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "static", "struct", "trait", "true", "type", "unsafe", "use", "where",
+    "while", "abstract", "become", "box", "do", "final", "macro", "override", "priv", "try",
+    "typeof", "unsized", "virtual", "yield",
+];
+
+/// Split an identifier into words at underscores, lower-to-upper and
+/// digit-to-letter transitions, and before the last capital of an acronym:
+/// `READDIR3resok` is `READDIR3`, `resok`; `NFS3ERR_NOENT` is `NFS3`, `ERR`,
+/// `NOENT`.
+fn words(name: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    for segment in name.split('_').filter(|s| !s.is_empty()) {
+        let chars: Vec<char> = segment.chars().collect();
+        let mut word = String::new();
+        for (i, &c) in chars.iter().enumerate() {
+            if i > 0 {
+                let prev = chars[i - 1];
+                let next_lower = chars.get(i + 1).is_some_and(|n| n.is_ascii_lowercase());
+                let boundary = (c.is_ascii_uppercase()
+                    && (prev.is_ascii_lowercase() || prev.is_ascii_digit()))
+                    || (c.is_ascii_uppercase() && prev.is_ascii_uppercase() && next_lower)
+                    || (c.is_ascii_lowercase() && prev.is_ascii_digit());
+                if boundary {
+                    words.push(std::mem::take(&mut word));
+                }
+            }
+            word.push(c);
+        }
+        words.push(word);
+    }
+    words
+}
+
+/// `UpperCamelCase`, for types and variants.
+pub(crate) fn camel(name: &str) -> String {
+    let camel: String = words(name)
+        .iter()
+        .map(|w| {
+            let mut chars = w.chars();
+            let first = chars.next().map(|c| c.to_ascii_uppercase());
+            first
+                .into_iter()
+                .chain(chars.map(|c| c.to_ascii_lowercase()))
+                .collect::<String>()
+        })
+        .collect();
+    if camel == "Self" {
+        return "Self_".to_string();
+    }
+    camel
+}
+
+/// `snake_case`, escaped if it is a Rust keyword, for fields.
+pub(crate) fn snake(name: &str) -> String {
+    let snake = words(name)
+        .iter()
+        .map(|w| w.to_ascii_lowercase())
+        .collect::<Vec<_>>()
+        .join("_");
+    match snake.as_str() {
+        "self" | "super" | "crate" => format!("{}_", snake),
+        s if KEYWORDS.contains(&s) => format!("r#{}", snake),
+        _ => snake,
+    }
+}
@@ -0,0 +1,426 @@
+//! Recursive-descent parser for RFC 4506 §6.3 plus RFC 5531 §12.2 programs.
+
+use crate::ast::*;
+use crate::error::{Error, Result};
+use crate::lexer::{Tok, Token, tokenize};
+
+const KEYWORDS: &[&str] = &[
+    "bool",
+    "case",
+    "const",
+    "default",
+    "double",
+    "quadruple",
+    "enum",
+    "float",
+    "hyper",
+    "int",
+    "opaque",
+    "string",
+    "struct",
+    "switch",
+    "typedef",
+    "union",
+    "unsigned",
+    "void",
+    "program",
+    "version",
+];
+
+/// Parse the contents of a `.x` file.
+pub fn parse(src: &str) -> Result<Specification> {
+    let mut parser = Parser {
+        tokens: tokenize(src)?,
+        pos: 0,
+    };
+    let mut definitions = Vec::new();
+    while parser.peek() != &Tok::Eof {
+        definitions.push(parser.definition()?);
+    }
+    Ok(Specification { definitions })
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> &Tok {
+        &self.tokens[self.pos].tok
+    }
+
+    fn line(&self) -> usize {
+        self.tokens[self.pos].line
+    }
+
+    fn bump(&mut self) -> Tok {
+        let tok = self.tokens[self.pos].tok.clone();
+        if tok != Tok::Eof {
+            self.pos += 1;
+        }
+        tok
+    }
+
+    fn error<T>(&self, expected: &str) -> Result<T> {
+        let found = match self.peek() {
+            Tok::Ident(s) => format!("`{}`", s),
+            Tok::Number(n) => format!("`{}`", n),
+            Tok::Punct(c) => format!("`{}`", c),
+            Tok::Eof => "end of file".to_string(),
+        };
+        Err(Error::at(
+            self.line(),
+            format!("expected {}, found {}", expected, found),
+        ))
+    }
+
+    fn is_punct(&self, c: char) -> bool {
+        self.peek() == &Tok::Punct(c)
+    }
+
+    fn eat_punct(&mut self, c: char) -> bool {
+        let found = self.is_punct(c);
+        if found {
+            self.bump();
+        }
+        found
+    }
+
+    fn expect_punct(&mut self, c: char) -> Result<()> {
+        if self.eat_punct(c) {
+            Ok(())
+        } else {
+            self.error(&format!("`{}`", c))
+        }
+    }
+
+    fn is_keyword(&self, kw: &str) -> bool {
+        matches!(self.peek(), Tok::Ident(s) if s == kw)
+    }
+
+    fn eat_keyword(&mut self, kw: &str) -> bool {
+        let found = self.is_keyword(kw);
+        if found {
+            self.bump();
+        }
+        found
+    }
+
+    fn expect_keyword(&mut self, kw: &str) -> Result<()> {
+        if self.eat_keyword(kw) {
+            Ok(())
+        } else {
+            self.error(&format!("`{}`", kw))
+        }
+    }
+
+    fn ident(&mut self) -> Result<String> {
+        match self.peek() {
+            Tok::Ident(s) if !KEYWORDS.contains(&s.as_str()) => {
+                let s = s.clone();
+                self.bump();
+                Ok(s)
+            }
+            _ => self.error("an identifier"),
+        }
+    }
+
+    fn constant(&mut self) -> Result<i64> {
+        match *self.peek() {
+            Tok::Number(n) => {
+                self.bump();
+                Ok(n)
+            }
+            _ => self.error("a number"),
+        }
+    }
+
+    fn value(&mut self) -> Result<Value> {
+        match self.peek() {
+            Tok::Number(_) => self.constant().map(Value::Constant),
+            _ => self.ident().map(Value::Name),
+        }
+    }
+
+    fn definition(&mut self) -> Result<Definition> {
+        let def = if self.eat_keyword("const") {
+            let name = self.ident()?;
+            self.expect_punct('=')?;
+            let value = self.constant()?;
+            Definition::Const { name, value }
+        } else if self.eat_keyword("typedef") {
+            match self.declaration()? {
+                Declaration::Void => return self.error("a type name after `typedef`"),
+                decl => Definition::Typedef(decl),
+            }
+        } else if self.eat_keyword("enum") {
+            let name = self.ident()?;
+            let body = self.enum_body()?;
+            Definition::Enum { name, body }
+        } else if self.eat_keyword("struct") {
+            let name = self.ident()?;
+            let body = self.struct_body()?;
+            Definition::Struct { name, body }
+        } else if self.eat_keyword("union") {
+            let name = self.ident()?;
+            let body = self.union_body()?;
+            Definition::Union { name, body }
+        } else if self.eat_keyword("program") {
+            Definition::Program(self.program()?)
+        } else {
+            return self.error("a definition");
+        };
+        self.expect_punct(';')?;
+        Ok(def)
+    }
+
+    fn declaration(&mut self) -> Result<Declaration> {
+        if self.eat_keyword("void") {
+            return Ok(Declaration::Void);
+        }
+        if self.eat_keyword("opaque") {
+            let name = self.ident()?;
+            let kind = match self.array_kind()? {
+                DeclKind::Scalar => return self.error("`[` or `<` after an opaque name"),
+                kind => kind,
+            };
+            return Ok(Declaration::Named {
+                name,
+                ty: TypeSpec::Opaque,
+                kind,
+            });
+        }
+        if self.eat_keyword("string") {
+            let name = self.ident()?;
+            let kind = match self.array_kind()? {
+                kind @ DeclKind::VarArray(_) => kind,
+                _ => return self.error("`<` after a string name"),
+            };
+            return Ok(Declaration::Named {
+                name,
+                ty: TypeSpec::String,
+                kind,
+            });
+        }
+
+        let ty = self.type_spec()?;
+        if self.eat_punct('*') {
+            let name = self.ident()?;
+            return Ok(Declaration::Named {
+                name,
+                ty,
+                kind: DeclKind::Optional,
+            });
+        }
+        let name = self.ident()?;
+        let kind = self.array_kind()?;
+        Ok(Declaration::Named { name, ty, kind })
+    }
+
+    /// An optional `[N]`, `<N>` or `<>` suffix.
+    fn array_kind(&mut self) -> Result<DeclKind> {
+        if self.eat_punct('[') {
+            let len = self.value()?;
+            self.expect_punct(']')?;
+            Ok(DeclKind::FixedArray(len))
+        } else if self.eat_punct('<') {
+            if self.eat_punct('>') {
+                return Ok(DeclKind::VarArray(None));
+            }
+            let max = self.value()?;
+            self.expect_punct('>')?;
+            Ok(DeclKind::VarArray(Some(max)))
+        } else {
+            Ok(DeclKind::Scalar)
+        }
+    }
+
+    fn type_spec(&mut self) -> Result<TypeSpec> {
+        if self.eat_keyword("unsigned") {
+            if self.eat_keyword("hyper") {
+                return Ok(TypeSpec::UnsignedHyper);
+            }
+            self.eat_keyword("int");
+            return Ok(TypeSpec::UnsignedInt);
+        }
+        let simple = [
+            ("int", TypeSpec::Int),
+            ("hyper", TypeSpec::Hyper),
+            ("float", TypeSpec::Float),
+            ("double", TypeSpec::Double),
+            ("quadruple", TypeSpec::Quadruple),
+            ("bool", TypeSpec::Bool),
+        ];
+        for (kw, ty) in simple {
+            if self.eat_keyword(kw) {
+                return Ok(ty);
+            }
+        }
+        // `enum name`, `struct name` and `union name` refer to a named type,
+        // as rpcgen allows.
+        if self.eat_keyword("enum") {
+            if self.is_punct('{') {
+                return self.enum_body().map(TypeSpec::Enum);
+            }
+            return self.ident().map(TypeSpec::Named);
+        }
+        if self.eat_keyword("struct") {
+            if self.is_punct('{') {
+                return self.struct_body().map(TypeSpec::Struct);
+            }
+            return self.ident().map(TypeSpec::Named);
+        }
+        if self.eat_keyword("union") {
+            if self.is_keyword("switch") {
+                return self.union_body().map(|b| TypeSpec::Union(Box::new(b)));
+            }
+            return self.ident().map(TypeSpec::Named);
+        }
+        match self.peek() {
+            Tok::Ident(s) if !KEYWORDS.contains(&s.as_str()) => self.ident().map(TypeSpec::Named),
+            _ => self.error("a type"),
+        }
+    }
+
+    fn enum_body(&mut self) -> Result<EnumBody> {
+        self.expect_punct('{')?;
+        let mut members = Vec::new();
+        loop {
+            let name = self.ident()?;
+            self.expect_punct('=')?;
+            members.push((name, self.value()?));
+            if !self.eat_punct(',') || self.is_punct('}') {
+                break;
+            }
+        }
+        self.expect_punct('}')?;
+        Ok(EnumBody { members })
+    }
+
+    fn struct_body(&mut self) -> Result<StructBody> {
+        self.expect_punct('{')?;
+        let mut fields = Vec::new();
+        loop {
+            let line = self.line();
+            match self.declaration()? {
+                Declaration::Void => return Err(Error::at(line, "a struct field cannot be void")),
+                decl => fields.push(decl),
+            }
+            self.expect_punct(';')?;
+            if self.eat_punct('}') {
+                break;
+            }
+        }
+        Ok(StructBody { fields })
+    }
+
+    fn union_body(&mut self) -> Result<UnionBody> {
+        self.expect_keyword("switch")?;
+        self.expect_punct('(')?;
+        let discriminant = match self.declaration()? {
+            Declaration::Void => return self.error("a discriminant declaration"),
+            decl => decl,
+        };
+        self.expect_punct(')')?;
+        self.expect_punct('{')?;
+
+        let mut arms = Vec::new();
+        while self.is_keyword("case") {
+            let mut cases = Vec::new();
+            while self.eat_keyword("case") {
+                cases.push(self.value()?);
+                self.expect_punct(':')?;
+            }
+            let decl = self.declaration()?;
+            self.expect_punct(';')?;
+            arms.push(UnionArm { cases, decl });
+        }
+        if arms.is_empty() {
+            return self.error("`case`");
+        }
+        let default = if self.eat_keyword("default") {
+            self.expect_punct(':')?;
+            let decl = self.declaration()?;
+            self.expect_punct(';')?;
+            Some(decl)
+        } else {
+            None
+        };
+        self.expect_punct('}')?;
+        Ok(UnionBody {
+            discriminant,
+            arms,
+            default,
+        })
+    }
+
+    fn program(&mut self) -> Result<Program> {
+        let name = self.ident()?;
+        self.expect_punct('{')?;
+        let mut versions = Vec::new();
+        while self.eat_keyword("version") {
+            versions.push(self.version()?);
+        }
+        if versions.is_empty() {
+            return self.error("`version`");
+        }
+        self.expect_punct('}')?;
+        self.expect_punct('=')?;
+        let number = self.constant()?;
+        Ok(Program {
+            name,
+            number,
+            versions,
+        })
+    }
+
+    fn version(&mut self) -> Result<Version> {
+        let name = self.ident()?;
+        self.expect_punct('{')?;
+        let mut procedures = Vec::new();
+        while !self.eat_punct('}') {
+            procedures.push(self.procedure()?);
+        }
+        if procedures.is_empty() {
+            return self.error("a procedure");
+        }
+        self.expect_punct('=')?;
+        let number = self.constant()?;
+        self.expect_punct(';')?;
+        Ok(Version {
+            name,
+            number,
+            procedures,
+        })
+    }
+
+    fn procedure(&mut self) -> Result<Procedure> {
+        let result = if self.eat_keyword("void") {
+            None
+        } else {
+            Some(self.type_spec()?)
+        };
+        let name = self.ident()?;
+        self.expect_punct('(')?;
+        let mut args = Vec::new();
+        if !self.eat_keyword("void") {
+            loop {
+                args.push(self.type_spec()?);
+                if !self.eat_punct(',') {
+                    break;
+                }
+            }
+        }
+        self.expect_punct(')')?;
+        self.expect_punct('=')?;
+        let number = self.constant()?;
+        self.expect_punct(';')?;
+        Ok(Procedure {
+            name,
+            number,
+            result,
+            args,
+        })
+    }
+}
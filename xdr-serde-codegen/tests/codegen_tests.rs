@@ -0,0 +1,298 @@
+use xdr_serde::{Error as XdrError, from_bytes, from_bytes_partial, to_bytes};
+use xdr_serde_codegen::ast::{DeclKind, Declaration, Definition, TypeSpec, Value};
+use xdr_serde_codegen::{Options, compile_str, parse};
+
+#[allow(dead_code)]
+mod demo {
+    include!("fixtures/demo.rs");
+}
+
+use demo::*;
+
+/// Stands in for the generated `nfstime` in `borrowed.x`.
+#[derive(Debug, Clone, PartialEq, xdr_serde::XdrSerialize, xdr_serde::XdrDeserialize)]
+pub struct Timestamp {
+    seconds: u32,
+    nseconds: u32,
+}
+
+#[allow(dead_code)]
+mod borrowed {
+    include!("fixtures/borrowed.rs");
+}
+
+fn borrowed_options() -> Options {
+    let mut options = Options { borrowed_strings: true, ..Options::default() };
+    options.type_names.insert("fhandle".into(), "FileHandle".into());
+    options.type_names.insert("nfstime".into(), "super::Timestamp".into());
+    options
+}
+
+// ══════════════════════════════════════════════════════════════════════════
+// Generated fixture
+// ══════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_fixture_up_to_date() {
+    let src = include_str!("fixtures/demo.x");
+    let generated = compile_str(src, &Options::default()).unwrap();
+    assert_eq!(
+        generated,
+        include_str!("fixtures/demo.rs"),
+        "regenerate with `cargo run -p xdr-serde-codegen --bin xdrgen -- \
+         tests/fixtures/demo.x -o tests/fixtures/demo.rs`"
+    );
+
+    let src = include_str!("fixtures/borrowed.x");
+    let generated = compile_str(src, &borrowed_options()).unwrap();
+    assert_eq!(
+        generated,
+        include_str!("fixtures/borrowed.rs"),
+        "regenerate with `cargo run -p xdr-serde-codegen --bin xdrgen -- --borrowed-strings \
+         --type fhandle=FileHandle --type nfstime=super::Timestamp \
+         tests/fixtures/borrowed.x -o tests/fixtures/borrowed.rs`"
+    );
+}
+
+#[test]
+fn test_generated_constants_and_enums() {
+    assert_eq!(NFS3_FHSIZE, 64);
+    assert_eq!(ERR_SENTINEL, -1);
+    assert_eq!(NFS_PROGRAM, 100003);
+    assert_eq!((NFS_V3, NFSPROC3_READDIR), (3, 16));
+
+    assert_eq!(to_bytes(&Nfsstat3::Nfs3ErrServerfault).unwrap(), 10006u32.to_be_bytes());
+    assert_eq!(to_bytes(&Ftype3::Nf3Lnk).unwrap(), [0xFF; 4]);
+    assert_eq!(from_bytes::<Nfsstat3>(&[0, 0, 0, 3]), Err(XdrError::InvalidDiscriminant(3)));
+}
+
+#[test]
+fn test_generated_typedef_newtypes() {
+    let fh = NfsFh3(vec![1, 2, 3]);
+    let bytes = to_bytes(&fh).unwrap();
+    assert_eq!(bytes, [0, 0, 0, 3, 1, 2, 3, 0]);
+    assert_eq!(fh, from_bytes(&bytes).unwrap());
+    assert_eq!(to_bytes(&NfsFh3(vec![0; 65])), Err(XdrError::LengthOverflow { max: 64, got: 65 }));
+
+    let verf = Cookieverf3([9; 8]);
+    assert_eq!(to_bytes(&verf).unwrap(), [9; 8]);
+
+    assert_eq!(
+        from_bytes::<Gids>(&[0, 0, 0, 17]),
+        Err(XdrError::LengthOverflow { max: 16, got: 17 })
+    );
+}
+
+#[test]
+fn test_generated_struct_layout() {
+    let auth = AuthUnix {
+        stamp: 1,
+        machinename: "h".into(),
+        uid: 2,
+        gid: 3,
+        gids: vec![4],
+        verifier: [0xAA; 4],
+        cookie: vec![0xBB],
+        ranks: [-1, 1],
+    };
+    let bytes = to_bytes(&auth).unwrap();
+    #[rustfmt::skip]
+    assert_eq!(bytes, [
+        0, 0, 0, 1,
+        0, 0, 0, 1, b'h', 0, 0, 0,
+        0, 0, 0, 2,
+        0, 0, 0, 3,
+        0, 0, 0, 1, 0, 0, 0, 4,
+        0xAA, 0xAA, 0xAA, 0xAA,
+        0, 0, 0, 1, 0xBB, 0, 0, 0,
+        0xFF, 0xFF, 0xFF, 0xFF, 0, 0, 0, 1,
+    ]);
+    assert_eq!(auth, from_bytes(&bytes).unwrap());
+}
+
+#[test]
+fn test_generated_linked_list_and_unions() {
+    let attrs = Fattr3 {
+        r#type: Ftype3::Nf3Dir,
+        mode: 0o755,
+        size: 4096,
+        mtime: Nfstime3 { seconds: 1, nseconds: 2 },
+        hidden: false,
+        offset: -8,
+        ratio: 0.5,
+    };
+    let list = Dirlist3 {
+        entries: Some(Box::new(Entry3 {
+            fileid: 1,
+            name: ".".into(),
+            cookie: Cookieverf3([1; 8]),
+            nextentry: Some(Box::new(Entry3 {
+                fileid: 2,
+                name: "..".into(),
+                cookie: Cookieverf3([2; 8]),
+                nextentry: None,
+            })),
+        })),
+        eof: true,
+    };
+    for res in [
+        Readdir3Res::Nfs3Ok(list),
+        Readdir3Res::Nfs3ErrNoent(PostOpAttr::True(attrs)),
+        Readdir3Res::Nfs3ErrPerm(PostOpAttr::False),
+        Readdir3Res::Default(5),
+    ] {
+        let bytes = to_bytes(&res).unwrap();
+        assert_eq!(res, from_bytes(&bytes).unwrap());
+    }
+
+    // Both labels of a shared arm decode to their own variant.
+    assert_eq!(
+        from_bytes::<Readdir3Res>(&[0, 0, 0, 1, 0, 0, 0, 0]).unwrap(),
+        Readdir3Res::Nfs3ErrPerm(PostOpAttr::False)
+    );
+    assert_eq!(from_bytes::<Readdir3Res>(&[0, 0, 0, 5]).unwrap(), Readdir3Res::Default(5));
+}
+
+#[test]
+fn test_generated_numeric_cases_and_inline_types() {
+    let small = OpaqueBody::Small(vec![1, 2]);
+    assert_eq!(to_bytes(&small).unwrap(), [0, 0, 0, 0, 0, 0, 0, 2, 1, 2, 0, 0]);
+    assert_eq!(to_bytes(&OpaqueBody::CaseMinus1).unwrap(), [0xFF; 4]);
+    let other = OpaqueBody::Default(7, NfsFh3(vec![3]));
+    assert_eq!(other, from_bytes(&to_bytes(&other).unwrap()).unwrap());
+    assert_eq!(
+        to_bytes(&OpaqueBody::Small(vec![0; 9])),
+        Err(XdrError::LengthOverflow { max: 8, got: 9 })
+    );
+
+    let sattr = Sattr3 {
+        mode: Sattr3Mode::True(0o644),
+        atime: Some(Box::new(Sattr3Atime { seconds: 10 })),
+    };
+    let bytes = to_bytes(&sattr).unwrap();
+    assert_eq!(bytes, [0, 0, 0, 1, 0, 0, 0x01, 0xA4, 0, 0, 0, 1, 0, 0, 0, 10]);
+    assert_eq!(sattr, from_bytes(&bytes).unwrap());
+
+    let kv = KvPair { key: "k".into(), value: "v".into() };
+    assert_eq!(kv, from_bytes(&to_bytes(&kv).unwrap()).unwrap());
+}
+
+#[test]
+fn test_generated_borrowed_strings_and_type_names() {
+    use borrowed::*;
+
+    let entry = Dirent {
+        fileid: 7,
+        name: Filename("a.txt"),
+        mtime: Timestamp { seconds: 1, nseconds: 2 },
+        next: Some(Box::new(Dirent {
+            fileid: 8,
+            name: Filename("b"),
+            mtime: Timestamp { seconds: 3, nseconds: 4 },
+            next: None,
+        })),
+    };
+    let bytes = to_bytes(&entry).unwrap();
+    let (decoded, rest) = from_bytes_partial::<Dirent>(&bytes).unwrap();
+    assert!(rest.is_empty());
+    assert_eq!(decoded, entry);
+    // The name points into the input rather than a fresh allocation.
+    let range = bytes.as_ptr_range();
+    assert!(range.contains(&decoded.name.0.as_ptr()));
+
+    assert_eq!(
+        to_bytes(&Filename(&"x".repeat(256))),
+        Err(XdrError::LengthOverflow { max: 255, got: 256 })
+    );
+
+    let res = ReadlinkRes::Data("/etc/hosts");
+    let bytes = to_bytes(&res).unwrap();
+    assert_eq!(from_bytes_partial::<ReadlinkRes>(&bytes).unwrap().0, res);
+
+    let info = Fsinfo { root: FileHandle([5; 32]), flags: 1 };
+    assert_eq!(info, from_bytes(&to_bytes(&info).unwrap()).unwrap());
+}
+
+// ══════════════════════════════════════════════════════════════════════════
+// Parser
+// ══════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_parse_declarations() {
+    let spec = parse("const N = 0x10; typedef int counts<N>; typedef opaque raw[012];").unwrap();
+    assert_eq!(spec.definitions[0], Definition::Const { name: "N".into(), value: 16 });
+    assert_eq!(
+        spec.definitions[1],
+        Definition::Typedef(Declaration::Named {
+            name: "counts".into(),
+            ty: TypeSpec::Int,
+            kind: DeclKind::VarArray(Some(Value::Name("N".into()))),
+        })
+    );
+    assert_eq!(
+        spec.definitions[2],
+        Definition::Typedef(Declaration::Named {
+            name: "raw".into(),
+            ty: TypeSpec::Opaque,
+            kind: DeclKind::FixedArray(Value::Constant(10)),
+        })
+    );
+}
+
+#[test]
+fn test_parse_program() {
+    let spec = parse(
+        "program P { version V { void NULL(void) = 0; int ADD(int, int) = 1; } = 2; } = 0x20000000;",
+    )
+    .unwrap();
+    let Definition::Program(p) = &spec.definitions[0] else { panic!() };
+    assert_eq!((p.name.as_str(), p.number), ("P", 0x2000_0000));
+    let v = &p.versions[0];
+    assert_eq!((v.name.as_str(), v.number), ("V", 2));
+    assert_eq!(v.procedures[0].result, None);
+    assert!(v.procedures[0].args.is_empty());
+    assert_eq!(v.procedures[1].args, [TypeSpec::Int, TypeSpec::Int]);
+}
+
+#[test]
+fn test_parse_errors_report_line() {
+    let err = parse("const A = 1;\nstruct s {\n  int a\n};").unwrap_err();
+    assert_eq!(err.line(), Some(4));
+    assert_eq!(err.message(), "expected `;`, found `}`");
+
+    let err = parse("typedef string name[4];").unwrap_err();
+    assert_eq!(err.to_string(), "line 1: expected `<` after a string name, found `;`");
+
+    assert!(parse("struct s { void; };").is_err());
+    assert!(parse("/* unterminated").is_err());
+    assert!(parse("const int = 4;").is_err());
+}
+
+#[test]
+fn test_generate_errors() {
+    let err = compile_str("typedef int a<MISSING>;", &Options::default()).unwrap_err();
+    assert_eq!(err.message(), "unknown constant `MISSING`");
+
+    let err = compile_str(
+        "program P { version V { void A(void) = 1; } = 1; version W { void A(void) = 2; } = 2; } = 1;",
+        &Options::default(),
+    )
+    .unwrap_err();
+    assert_eq!(err.message(), "`A` is defined with two different numbers");
+}
+
+#[test]
+fn test_generate_options_derives() {
+    let options = Options {
+        derives: vec!["Debug".into()],
+        enum_derives: vec![],
+        ..Options::default()
+    };
+    let rust = compile_str("struct s { int a; }; enum e { A = 1 };", &options).unwrap();
+    assert!(rust.contains(
+        "#[derive(Debug, ::xdr_serde::XdrSerialize, ::xdr_serde::XdrDeserialize)]\npub struct S {"
+    ));
+    assert!(rust.contains(
+        "#[derive(::xdr_serde::XdrSerialize, ::xdr_serde::XdrDeserialize)]\n#[repr(i32)]\n"
+    ));
+}
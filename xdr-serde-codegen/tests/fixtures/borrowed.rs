@@ -0,0 +1,41 @@
+// Generated by xdr-serde-codegen. Do not edit.
+
+pub const MAXNAMLEN: u32 = 255;
+pub const FHSIZE: u32 = 32;
+
+#[derive(Debug, Clone, PartialEq, ::xdr_serde::XdrSerialize, ::xdr_serde::XdrDeserialize)]
+pub struct Filename<'a>(#[xdr(max = MAXNAMLEN)] pub &'a str);
+
+pub type Path<'a> = &'a str;
+
+#[derive(Debug, Clone, PartialEq, ::xdr_serde::XdrSerialize, ::xdr_serde::XdrDeserialize)]
+pub struct FileHandle(#[xdr(fixed)] pub [u8; FHSIZE as usize]);
+
+#[derive(Debug, Clone, PartialEq, ::xdr_serde::XdrSerialize, ::xdr_serde::XdrDeserialize)]
+pub struct Dirent<'a> {
+    pub fileid: u64,
+    pub name: Filename<'a>,
+    pub mtime: super::Timestamp,
+    pub next: Option<Box<Dirent<'a>>>,
+}
+
+#[derive(Debug, Clone, PartialEq, ::xdr_serde::XdrSerialize, ::xdr_serde::XdrDeserialize)]
+pub struct Diropargs<'a> {
+    pub dir: FileHandle,
+    pub name: Filename<'a>,
+}
+
+#[derive(Debug, Clone, PartialEq, ::xdr_serde::XdrSerialize, ::xdr_serde::XdrDeserialize)]
+#[allow(clippy::enum_variant_names, clippy::large_enum_variant)]
+pub enum ReadlinkRes<'a> {
+    #[xdr(discriminant = 0)]
+    Data(Path<'a>),
+    #[xdr(default_arm)]
+    Default(i32),
+}
+
+#[derive(Debug, Clone, PartialEq, ::xdr_serde::XdrSerialize, ::xdr_serde::XdrDeserialize)]
+pub struct Fsinfo {
+    pub root: FileHandle,
+    pub flags: u32,
+}
@@ -0,0 +1,41 @@
+/*
+ * Borrowed strings and type-name mapping:
+ *   xdrgen --borrowed-strings --type fhandle=FileHandle \
+ *          --type nfstime=super::Timestamp borrowed.x
+ */
+
+const MAXNAMLEN = 255;
+const FHSIZE = 32;
+
+typedef string filename<MAXNAMLEN>;
+typedef string path<>;
+typedef opaque fhandle[FHSIZE];
+
+struct nfstime {
+    unsigned int seconds;
+    unsigned int nseconds;
+};
+
+struct dirent {
+    unsigned hyper fileid;
+    filename name;
+    nfstime mtime;
+    dirent *next;
+};
+
+struct diropargs {
+    fhandle dir;
+    filename name;
+};
+
+union readlink_res switch (int status) {
+case 0:
+    path data;
+default:
+    void;
+};
+
+struct fsinfo {
+    fhandle root;
+    unsigned int flags;
+};
@@ -0,0 +1,168 @@
+// Generated by xdr-serde-codegen. Do not edit.
+
+pub const NFS3_FHSIZE: u32 = 64;
+pub const NFS3_COOKIEVERFSIZE: u32 = 8;
+pub const MNTPATHLEN: u32 = 1024;
+pub const MAXGIDS: u32 = 16;
+pub const ERR_SENTINEL: i32 = -1;
+
+pub type Uint64 = u64;
+
+pub type Uint32 = u32;
+
+pub type Filename3 = String;
+
+#[derive(Debug, Clone, PartialEq, ::xdr_serde::XdrSerialize, ::xdr_serde::XdrDeserialize)]
+pub struct Dirpath(#[xdr(max = MNTPATHLEN)] pub String);
+
+#[derive(Debug, Clone, PartialEq, ::xdr_serde::XdrSerialize, ::xdr_serde::XdrDeserialize)]
+pub struct NfsFh3(#[xdr(opaque, max = NFS3_FHSIZE)] pub Vec<u8>);
+
+#[derive(Debug, Clone, PartialEq, ::xdr_serde::XdrSerialize, ::xdr_serde::XdrDeserialize)]
+pub struct Cookieverf3(#[xdr(fixed)] pub [u8; NFS3_COOKIEVERFSIZE as usize]);
+
+#[derive(Debug, Clone, PartialEq, ::xdr_serde::XdrSerialize, ::xdr_serde::XdrDeserialize)]
+pub struct Gids(#[xdr(max = MAXGIDS)] pub Vec<Uint32>);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ::xdr_serde::XdrSerialize, ::xdr_serde::XdrDeserialize)]
+#[repr(i32)]
+#[allow(clippy::enum_variant_names)]
+pub enum Nfsstat3 {
+    Nfs3Ok = 0,
+    Nfs3ErrPerm = 1,
+    Nfs3ErrNoent = 2,
+    Nfs3ErrIo = 5,
+    Nfs3ErrServerfault = 10006,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ::xdr_serde::XdrSerialize, ::xdr_serde::XdrDeserialize)]
+#[repr(i32)]
+#[allow(clippy::enum_variant_names)]
+pub enum Ftype3 {
+    Nf3Reg = 1,
+    Nf3Dir = 2,
+    Nf3Lnk = ERR_SENTINEL,
+}
+
+#[derive(Debug, Clone, PartialEq, ::xdr_serde::XdrSerialize, ::xdr_serde::XdrDeserialize)]
+pub struct Nfstime3 {
+    pub seconds: Uint32,
+    pub nseconds: Uint32,
+}
+
+#[derive(Debug, Clone, PartialEq, ::xdr_serde::XdrSerialize, ::xdr_serde::XdrDeserialize)]
+pub struct Fattr3 {
+    pub r#type: Ftype3,
+    pub mode: u32,
+    pub size: Uint64,
+    pub mtime: Nfstime3,
+    pub hidden: bool,
+    pub offset: i64,
+    pub ratio: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, ::xdr_serde::XdrSerialize, ::xdr_serde::XdrDeserialize)]
+pub struct Entry3 {
+    pub fileid: Uint64,
+    pub name: Filename3,
+    pub cookie: Cookieverf3,
+    pub nextentry: Option<Box<Entry3>>,
+}
+
+#[derive(Debug, Clone, PartialEq, ::xdr_serde::XdrSerialize, ::xdr_serde::XdrDeserialize)]
+pub struct Dirlist3 {
+    pub entries: Option<Box<Entry3>>,
+    pub eof: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, ::xdr_serde::XdrSerialize, ::xdr_serde::XdrDeserialize)]
+pub struct AuthUnix {
+    pub stamp: u32,
+    #[xdr(max = 255)]
+    pub machinename: String,
+    pub uid: u32,
+    pub gid: u32,
+    #[xdr(max = MAXGIDS)]
+    pub gids: Vec<u32>,
+    #[xdr(fixed)]
+    pub verifier: [u8; 4],
+    #[xdr(opaque)]
+    pub cookie: Vec<u8>,
+    pub ranks: [i32; 2],
+}
+
+#[derive(Debug, Clone, PartialEq, ::xdr_serde::XdrSerialize, ::xdr_serde::XdrDeserialize)]
+#[allow(clippy::enum_variant_names, clippy::large_enum_variant)]
+pub enum PostOpAttr {
+    #[xdr(discriminant = 1)]
+    True(Fattr3),
+    #[xdr(discriminant = 0)]
+    False,
+}
+
+#[derive(Debug, Clone, PartialEq, ::xdr_serde::XdrSerialize, ::xdr_serde::XdrDeserialize)]
+#[allow(clippy::enum_variant_names, clippy::large_enum_variant)]
+pub enum Readdir3Res {
+    #[xdr(discriminant = Nfsstat3::Nfs3Ok as i32)]
+    Nfs3Ok(Dirlist3),
+    #[xdr(discriminant = Nfsstat3::Nfs3ErrPerm as i32)]
+    Nfs3ErrPerm(PostOpAttr),
+    #[xdr(discriminant = Nfsstat3::Nfs3ErrNoent as i32)]
+    Nfs3ErrNoent(PostOpAttr),
+    #[xdr(default_arm)]
+    Default(i32),
+}
+
+#[derive(Debug, Clone, PartialEq, ::xdr_serde::XdrSerialize, ::xdr_serde::XdrDeserialize)]
+#[allow(clippy::enum_variant_names, clippy::large_enum_variant)]
+pub enum OpaqueBody {
+    #[xdr(discriminant = 0)]
+    Small(#[xdr(opaque, max = 8)] Vec<u8>),
+    #[xdr(discriminant = 1)]
+    Text(String),
+    #[xdr(discriminant = -1)]
+    CaseMinus1,
+    #[xdr(default_arm)]
+    Default(i32, NfsFh3),
+}
+
+#[derive(Debug, Clone, PartialEq, ::xdr_serde::XdrSerialize, ::xdr_serde::XdrDeserialize)]
+#[allow(clippy::enum_variant_names, clippy::large_enum_variant)]
+pub enum Sattr3Mode {
+    #[xdr(discriminant = 1)]
+    True(Uint32),
+    #[xdr(default_arm)]
+    Default(i32),
+}
+
+#[derive(Debug, Clone, PartialEq, ::xdr_serde::XdrSerialize, ::xdr_serde::XdrDeserialize)]
+pub struct Sattr3Atime {
+    pub seconds: Uint32,
+}
+
+#[derive(Debug, Clone, PartialEq, ::xdr_serde::XdrSerialize, ::xdr_serde::XdrDeserialize)]
+pub struct Sattr3 {
+    pub mode: Sattr3Mode,
+    pub atime: Option<Box<Sattr3Atime>>,
+}
+
+#[derive(Debug, Clone, PartialEq, ::xdr_serde::XdrSerialize, ::xdr_serde::XdrDeserialize)]
+pub struct KvPair {
+    pub key: String,
+    pub value: String,
+}
+
+/// Program `NFS_PROGRAM`.
+pub const NFS_PROGRAM: u32 = 100003;
+
+/// Version `NFS_V3` of program `NFS_PROGRAM`.
+pub const NFS_V3: u32 = 3;
+/// `NFSPROC3_NULL(void) -> void`
+pub const NFSPROC3_NULL: u32 = 0;
+/// `NFSPROC3_GETATTR(nfs_fh3) -> post_op_attr`
+pub const NFSPROC3_GETATTR: u32 = 1;
+/// `NFSPROC3_READDIR(nfs_fh3, uint64) -> readdir3res`
+pub const NFSPROC3_READDIR: u32 = 16;
+
+/// Version `NFS_V3_DUP` of program `NFS_PROGRAM`.
+pub const NFS_V3_DUP: u32 = 4;
@@ -0,0 +1,128 @@
+/*
+ * Exercises every construct xdrgen supports. Loosely modelled on
+ * nfs_prot.x, mount.x and rpcb_prot.x.
+ */
+
+%#include <rpc/types.h>
+
+const NFS3_FHSIZE = 64;
+const NFS3_COOKIEVERFSIZE = 8;
+const MNTPATHLEN = 1024;
+const MAXGIDS = 16;
+const ERR_SENTINEL = -1;
+
+typedef unsigned hyper uint64;
+typedef unsigned int uint32;
+typedef string filename3<>;
+typedef string dirpath<MNTPATHLEN>;
+typedef opaque nfs_fh3<NFS3_FHSIZE>;
+typedef opaque cookieverf3[NFS3_COOKIEVERFSIZE];
+typedef uint32 gids<MAXGIDS>;
+
+enum nfsstat3 {
+    NFS3_OK = 0,
+    NFS3ERR_PERM = 1,
+    NFS3ERR_NOENT = 2,
+    NFS3ERR_IO = 5,
+    NFS3ERR_SERVERFAULT = 10006
+};
+
+enum ftype3 {
+    NF3REG = 1,
+    NF3DIR = 2,
+    NF3LNK = ERR_SENTINEL
+};
+
+struct nfstime3 {
+    uint32 seconds;
+    uint32 nseconds;
+};
+
+struct fattr3 {
+    ftype3 type;
+    unsigned int mode;
+    uint64 size;
+    nfstime3 mtime;
+    bool hidden;
+    hyper offset;
+    double ratio;
+};
+
+struct entry3 {
+    uint64 fileid;
+    filename3 name;
+    cookieverf3 cookie;
+    entry3 *nextentry;
+};
+
+struct dirlist3 {
+    entry3 *entries;
+    bool eof;
+};
+
+struct auth_unix {
+    unsigned int stamp;
+    string machinename<255>;
+    unsigned int uid;
+    unsigned int gid;
+    unsigned int gids<MAXGIDS>;
+    opaque verifier[4];
+    opaque cookie<>;
+    int ranks[2];
+};
+
+union post_op_attr switch (bool attributes_follow) {
+case TRUE:
+    fattr3 attributes;
+case FALSE:
+    void;
+};
+
+union readdir3res switch (nfsstat3 status) {
+case NFS3_OK:
+    dirlist3 reply;
+case NFS3ERR_PERM:
+case NFS3ERR_NOENT:
+    post_op_attr dir_attributes;
+default:
+    void;
+};
+
+union opaque_body switch (int kind) {
+case 0:
+    opaque small<8>;
+case 1:
+    string text<>;
+case -1:
+    void;
+default:
+    nfs_fh3 handle;
+};
+
+struct sattr3 {
+    union switch (bool set_it) {
+    case TRUE:
+        uint32 mode;
+    default:
+        void;
+    } mode;
+    struct {
+        uint32 seconds;
+    } *atime;
+};
+
+typedef struct {
+    string key<>;
+    string value<>;
+} kv_pair;
+
+program NFS_PROGRAM {
+    version NFS_V3 {
+        void NFSPROC3_NULL(void) = 0;
+        post_op_attr NFSPROC3_GETATTR(nfs_fh3) = 1;
+        readdir3res NFSPROC3_READDIR(nfs_fh3, uint64) = 16;
+    } = 3;
+    version NFS_V3_DUP {
+        void NFSPROC3_NULL(void) = 0;
+    } = 4;
+} = 100003;
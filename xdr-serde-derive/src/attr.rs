@@ -0,0 +1,253 @@
+//! Parsing of `#[xdr(...)]` attributes into the model the expanders work on.
+
+use proc_macro2::TokenStream;
+use quote::{ToTokens, quote};
+use syn::spanned::Spanned;
+use syn::{Attribute, Data, DeriveInput, Error, Expr, Fields, Ident, Member, Result, Type};
+
+/// How a single field or arm value is put on the wire.
+pub(crate) enum Encoding {
+    /// The field's own `Serialize` / `Deserialize` impls.
+    Plain,
+    /// `#[xdr(fixed)]`: fixed-length opaque, no length prefix.
+    Fixed,
+    /// `#[xdr(opaque)]`: variable-length opaque.
+    Opaque,
+    /// `#[xdr(max = N)]`: a string or array of at most `N` items.
+    Bounded(Expr),
+    /// `#[xdr(opaque, max = N)]`: `opaque<N>`.
+    BoundedOpaque(Expr),
+}
+
+pub(crate) struct Field<'a> {
+    pub member: Member,
+    pub ty: &'a Type,
+    pub encoding: Encoding,
+}
+
+/// Shape of a struct or enum variant, which selects the serde data model call.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Style {
+    Unit,
+    Newtype,
+    Tuple,
+    Struct,
+}
+
+pub(crate) struct Variant<'a> {
+    pub ident: &'a Ident,
+    /// The discriminant expression; for a default arm, the binding that holds it.
+    pub disc: TokenStream,
+    pub style: Style,
+    /// Fields that go on the wire after the discriminant.
+    pub fields: Vec<Field<'a>>,
+    pub default_arm: bool,
+}
+
+pub(crate) enum Body<'a> {
+    Struct(Style, Vec<Field<'a>>),
+    Enum(Vec<Variant<'a>>),
+}
+
+pub(crate) struct Container<'a> {
+    pub input: &'a DeriveInput,
+    pub body: Body<'a>,
+}
+
+impl<'a> Container<'a> {
+    pub fn from_ast(input: &'a DeriveInput) -> Result<Self> {
+        if let Some(attr) = input.attrs.iter().find(|a| a.path().is_ident("xdr")) {
+            return Err(Error::new_spanned(
+                attr,
+                "#[xdr(...)] is not supported on the type itself",
+            ));
+        }
+        let body = match &input.data {
+            Data::Struct(data) => {
+                let (style, fields) = parse_fields(&data.fields)?;
+                Body::Struct(style, fields)
+            }
+            Data::Enum(data) => Body::Enum(parse_variants(input, data)?),
+            Data::Union(data) => {
+                return Err(Error::new_spanned(
+                    data.union_token,
+                    "Rust unions are not supported; use an enum for an XDR union",
+                ));
+            }
+        };
+        Ok(Container { input, body })
+    }
+}
+
+fn style_of(fields: &Fields) -> Style {
+    match fields {
+        Fields::Unit => Style::Unit,
+        Fields::Named(_) => Style::Struct,
+        Fields::Unnamed(f) if f.unnamed.len() == 1 => Style::Newtype,
+        Fields::Unnamed(_) => Style::Tuple,
+    }
+}
+
+fn parse_fields(fields: &Fields) -> Result<(Style, Vec<Field<'_>>)> {
+    let parsed = fields
+        .iter()
+        .enumerate()
+        .map(|(i, f)| {
+            Ok(Field {
+                member: match &f.ident {
+                    Some(ident) => Member::Named(ident.clone()),
+                    None => Member::Unnamed(i.into()),
+                },
+                ty: &f.ty,
+                encoding: parse_encoding(&f.attrs)?,
+            })
+        })
+        .collect::<Result<_>>()?;
+    Ok((style_of(fields), parsed))
+}
+
+fn parse_encoding(attrs: &[Attribute]) -> Result<Encoding> {
+    let mut fixed = false;
+    let mut opaque = false;
+    let mut max = None;
+    for attr in attrs.iter().filter(|a| a.path().is_ident("xdr")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("fixed") {
+                fixed = true;
+            } else if meta.path.is_ident("opaque") {
+                opaque = true;
+            } else if meta.path.is_ident("max") {
+                max = Some(meta.value()?.parse::<Expr>()?);
+            } else {
+                return Err(meta.error("expected `fixed`, `opaque` or `max = N`"));
+            }
+            Ok(())
+        })?;
+        if fixed && (opaque || max.is_some()) {
+            return Err(Error::new_spanned(
+                attr,
+                "`fixed` cannot be combined with `opaque` or `max`",
+            ));
+        }
+    }
+    Ok(match (fixed, opaque, max) {
+        (true, ..) => Encoding::Fixed,
+        (false, true, Some(n)) => Encoding::BoundedOpaque(n),
+        (false, true, None) => Encoding::Opaque,
+        (false, false, Some(n)) => Encoding::Bounded(n),
+        (false, false, None) => Encoding::Plain,
+    })
+}
+
+fn parse_variants<'a>(input: &'a DeriveInput, data: &'a syn::DataEnum) -> Result<Vec<Variant<'a>>> {
+    if data.variants.is_empty() {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "an XDR enum needs at least one variant",
+        ));
+    }
+
+    let mut variants = Vec::with_capacity(data.variants.len());
+    // Discriminants without an explicit value continue from the previous one,
+    // as in C and in Rust's own enums.
+    let mut next = quote!(0i32);
+    for (i, v) in data.variants.iter().enumerate() {
+        let mut explicit = None;
+        let mut default_arm = false;
+        for attr in v.attrs.iter().filter(|a| a.path().is_ident("xdr")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("discriminant") {
+                    explicit = Some(meta.value()?.parse::<Expr>()?);
+                } else if meta.path.is_ident("default_arm") {
+                    default_arm = true;
+                } else {
+                    return Err(meta.error("expected `discriminant = N` or `default_arm`"));
+                }
+                Ok(())
+            })?;
+        }
+        if let Some((_, expr)) = &v.discriminant {
+            if explicit.is_some() {
+                return Err(Error::new_spanned(
+                    expr,
+                    "discriminant given both as `= value` and #[xdr(discriminant)]",
+                ));
+            }
+            explicit = Some(expr.clone());
+        }
+
+        if default_arm {
+            variants.push(parse_default_arm(
+                v,
+                explicit,
+                i + 1 == data.variants.len(),
+            )?);
+            continue;
+        }
+
+        let disc = match explicit {
+            Some(expr) => expr.into_token_stream(),
+            None => next,
+        };
+        next = quote!((#disc) + 1i32);
+        let (style, fields) = parse_fields(&v.fields)?;
+        variants.push(Variant {
+            ident: &v.ident,
+            disc,
+            style,
+            fields,
+            default_arm,
+        });
+    }
+    Ok(variants)
+}
+
+/// A default arm is `Name(i32)` or `Name(i32, Type)`; the `i32` keeps the
+/// discriminant actually seen on the wire.
+fn parse_default_arm(v: &syn::Variant, explicit: Option<Expr>, last: bool) -> Result<Variant<'_>> {
+    if let Some(expr) = explicit {
+        return Err(Error::new_spanned(
+            expr,
+            "a default arm takes every unlisted discriminant and cannot declare one",
+        ));
+    }
+    if !last {
+        return Err(Error::new_spanned(
+            &v.ident,
+            "the default arm must be the last variant",
+        ));
+    }
+    let shape_error = || {
+        Error::new(
+            v.fields.span(),
+            "a default arm must have the form `Name(i32)` or `Name(i32, Type)`",
+        )
+    };
+    let Fields::Unnamed(unnamed) = &v.fields else {
+        return Err(shape_error());
+    };
+    let first = unnamed.unnamed.first().ok_or_else(shape_error)?;
+    if unnamed.unnamed.len() > 2 || first.ty.to_token_stream().to_string() != "i32" {
+        return Err(shape_error());
+    }
+    if let Some(attr) = first.attrs.iter().find(|a| a.path().is_ident("xdr")) {
+        return Err(Error::new_spanned(
+            attr,
+            "the discriminant field of a default arm takes no #[xdr(...)] attributes",
+        ));
+    }
+
+    let (_, mut fields) = parse_fields(&v.fields)?;
+    fields.remove(0);
+    Ok(Variant {
+        ident: &v.ident,
+        disc: quote!(__xdr_d),
+        style: if fields.is_empty() {
+            Style::Unit
+        } else {
+            Style::Newtype
+        },
+        fields,
+        default_arm: true,
+    })
+}
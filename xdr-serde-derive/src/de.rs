@@ -0,0 +1,376 @@
+//! `#[derive(XdrDeserialize)]` expansion.
+
+use crate::attr::{Body, Container, Encoding, Field, Style, Variant};
+use crate::ser::field_name;
+use crate::{binding, bound_generics, krate, private};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{GenericParam, Generics, Ident, LifetimeParam, LitStr, Result};
+
+pub(crate) fn expand(cont: &Container) -> Result<TokenStream> {
+    let input = cont.input;
+
+    let p = private();
+    let ident = &input.ident;
+    let name = LitStr::new(&ident.to_string(), ident.span());
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    let generics = de_generics(&input.generics);
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+    let cx = Cx {
+        ident,
+        generics: &generics,
+    };
+    let visitor = format_ident!("__Visitor");
+    let (items, methods, call) = match &cont.body {
+        Body::Struct(style, fields) => {
+            let methods = struct_methods(&cx, *style, fields);
+            let new = cx.new_visitor(&visitor);
+            let call = match style {
+                Style::Unit => quote!(__deserializer.deserialize_unit_struct(#name, #new)),
+                Style::Newtype => quote!(__deserializer.deserialize_newtype_struct(#name, #new)),
+                Style::Tuple => {
+                    let len = fields.len();
+                    quote!(__deserializer.deserialize_tuple_struct(#name, #len, #new))
+                }
+                Style::Struct => {
+                    let keys = fields.iter().map(field_name);
+                    quote!(__deserializer.deserialize_struct(#name, &[#(#keys),*], #new))
+                }
+            };
+            (TokenStream::new(), methods, call)
+        }
+        Body::Enum(variants) => {
+            let (items, method) = enum_method(&cx, variants);
+            let new = cx.new_visitor(&visitor);
+            let names = variants
+                .iter()
+                .map(|v| LitStr::new(&v.ident.to_string(), v.ident.span()));
+            let call = quote!(__deserializer.deserialize_enum(#name, &[#(#names),*], #new));
+            (items, method, call)
+        }
+    };
+    let expecting = match &cont.body {
+        Body::Struct(..) => format!("struct {ident}"),
+        Body::Enum(..) => format!("XDR union {ident}"),
+    };
+    let visitor_impl = cx.visitor(&visitor, &expecting, methods);
+
+    Ok(quote! {
+        impl #impl_generics #p::serde::Deserialize<'de> for #ident #ty_generics #where_clause {
+            fn deserialize<__D>(
+                __deserializer: __D,
+            ) -> ::core::result::Result<Self, __D::Error>
+            where
+                __D: #p::serde::Deserializer<'de>,
+            {
+                #items
+                #visitor_impl
+                #call
+            }
+        }
+    })
+}
+
+/// The container's generics plus `'de`, with `T: Deserialize<'de>` bounds.
+/// `'de` outlives every lifetime of the container, so fields may borrow from
+/// the input.
+fn de_generics(generics: &Generics) -> Generics {
+    let p = private();
+    let mut generics = bound_generics(generics, quote!(#p::serde::Deserialize<'de>));
+    let mut de: LifetimeParam = syn::parse_quote!('de);
+    de.bounds = generics.lifetimes().map(|l| l.lifetime.clone()).collect();
+    generics.params.insert(0, GenericParam::Lifetime(de));
+    generics
+}
+
+struct Cx<'a> {
+    ident: &'a Ident,
+    /// Generics for the visitors: the container's plus `'de`.
+    generics: &'a Generics,
+}
+
+impl Cx<'_> {
+    /// Declare a visitor struct named `visitor` and implement `Visitor` for it.
+    fn visitor(&self, visitor: &Ident, expecting: &str, methods: TokenStream) -> TokenStream {
+        let p = private();
+        let ident = self.ident;
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+        let container_ty_generics = container_ty_generics(self.generics);
+        quote! {
+            struct #visitor #impl_generics #where_clause {
+                marker: ::core::marker::PhantomData<#ident #container_ty_generics>,
+                lifetime: ::core::marker::PhantomData<&'de ()>,
+            }
+
+            impl #impl_generics #p::serde::de::Visitor<'de> for #visitor #ty_generics
+            #where_clause
+            {
+                type Value = #ident #container_ty_generics;
+
+                fn expecting(
+                    &self,
+                    __f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    __f.write_str(#expecting)
+                }
+
+                #methods
+            }
+        }
+    }
+
+    fn new_visitor(&self, visitor: &Ident) -> TokenStream {
+        quote! {
+            #visitor {
+                marker: ::core::marker::PhantomData,
+                lifetime: ::core::marker::PhantomData,
+            }
+        }
+    }
+}
+
+/// The container's own type generics, recovered by dropping `'de`.
+fn container_ty_generics(generics: &Generics) -> TokenStream {
+    let mut container = generics.clone();
+    container.params = container.params.into_iter().skip(1).collect();
+    let (_, ty_generics, _) = container.split_for_impl();
+    quote!(#ty_generics)
+}
+
+/// The type to deserialize for `field`, and the suffix that turns a value of
+/// that type back into the field's type.
+fn field_type(field: &Field) -> (TokenStream, TokenStream) {
+    let p = private();
+    let ty = field.ty;
+    match &field.encoding {
+        Encoding::Plain => (quote!(#ty), TokenStream::new()),
+        Encoding::Fixed => (quote!(#p::DeFixed<#ty>), quote!(.0)),
+        Encoding::Opaque => (quote!(#p::DeOpaque), quote!(.0)),
+        Encoding::Bounded(n) => (quote!(#p::DeBounded<{ #n }, #ty>), quote!(.0)),
+        Encoding::BoundedOpaque(n) => (quote!(#p::DeBounded<{ #n }, #p::DeOpaque>), quote!(.0.0)),
+    }
+}
+
+/// `visit_seq`, reading `fields` in order and building `path { .. }`.
+fn visit_seq(path: TokenStream, fields: &[Field]) -> TokenStream {
+    let p = private();
+    let reads = fields.iter().enumerate().map(|(i, f)| {
+        let b = binding(f);
+        let (ty, unwrap) = field_type(f);
+        quote! {
+            let #b = match #p::serde::de::SeqAccess::next_element::<#ty>(&mut __seq)? {
+                ::core::option::Option::Some(__v) => __v #unwrap,
+                ::core::option::Option::None => {
+                    return ::core::result::Result::Err(
+                        #p::serde::de::Error::invalid_length(#i, &self),
+                    );
+                }
+            };
+        }
+    });
+    let members = fields.iter().map(|f| &f.member);
+    let binds = fields.iter().map(binding);
+    quote! {
+        fn visit_seq<__A>(
+            self,
+            mut __seq: __A,
+        ) -> ::core::result::Result<Self::Value, __A::Error>
+        where
+            __A: #p::serde::de::SeqAccess<'de>,
+        {
+            #(#reads)*
+            ::core::result::Result::Ok(#path { #(#members: #binds,)* })
+        }
+    }
+}
+
+/// `visit_map`, for self-describing formats that encode named fields as a
+/// map. Fields may come in any order; unknown ones are skipped.
+fn visit_map(path: TokenStream, fields: &[Field]) -> TokenStream {
+    let p = private();
+    let keys: Vec<LitStr> = fields.iter().map(field_name).collect();
+    let binds: Vec<Ident> = fields.iter().map(binding).collect();
+    let reads = fields.iter().enumerate().map(|(i, f)| {
+        let (b, key) = (&binds[i], &keys[i]);
+        let (ty, unwrap) = field_type(f);
+        quote! {
+            ::core::option::Option::Some(#i) => {
+                if #b.is_some() {
+                    return ::core::result::Result::Err(
+                        #p::serde::de::Error::duplicate_field(#key),
+                    );
+                }
+                #b = ::core::option::Option::Some(
+                    #p::serde::de::MapAccess::next_value::<#ty>(&mut __map)? #unwrap,
+                );
+            }
+        }
+    });
+    let members = fields.iter().map(|f| &f.member);
+    quote! {
+        fn visit_map<__M>(
+            self,
+            mut __map: __M,
+        ) -> ::core::result::Result<Self::Value, __M::Error>
+        where
+            __M: #p::serde::de::MapAccess<'de>,
+        {
+            #(let mut #binds = ::core::option::Option::None;)*
+            while let ::core::option::Option::Some(__key) =
+                #p::serde::de::MapAccess::next_key_seed(&mut __map, #p::FieldSeed(&[#(#keys),*]))?
+            {
+                match __key {
+                    #(#reads)*
+                    _ => {
+                        #p::serde::de::MapAccess::next_value::<#p::serde::de::IgnoredAny>(
+                            &mut __map,
+                        )?;
+                    }
+                }
+            }
+            #(
+                let #binds = match #binds {
+                    ::core::option::Option::Some(__v) => __v,
+                    ::core::option::Option::None => {
+                        return ::core::result::Result::Err(
+                            #p::serde::de::Error::missing_field(#keys),
+                        );
+                    }
+                };
+            )*
+            ::core::result::Result::Ok(#path { #(#members: #binds,)* })
+        }
+    }
+}
+
+fn struct_methods(cx: &Cx, style: Style, fields: &[Field]) -> TokenStream {
+    let p = private();
+    let ident = cx.ident;
+    match style {
+        Style::Unit => quote! {
+            fn visit_unit<__E>(self) -> ::core::result::Result<Self::Value, __E>
+            where
+                __E: #p::serde::de::Error,
+            {
+                ::core::result::Result::Ok(#ident {})
+            }
+        },
+        Style::Newtype => {
+            let (ty, unwrap) = field_type(&fields[0]);
+            let seq = visit_seq(quote!(#ident), fields);
+            quote! {
+                fn visit_newtype_struct<__E>(
+                    self,
+                    __e: __E,
+                ) -> ::core::result::Result<Self::Value, __E::Error>
+                where
+                    __E: #p::serde::Deserializer<'de>,
+                {
+                    let __v = <#ty as #p::serde::Deserialize>::deserialize(__e)?;
+                    ::core::result::Result::Ok(#ident(__v #unwrap))
+                }
+
+                #seq
+            }
+        }
+        Style::Tuple => visit_seq(quote!(#ident), fields),
+        Style::Struct => {
+            let seq = visit_seq(quote!(#ident), fields);
+            let map = visit_map(quote!(#ident), fields);
+            quote!(#seq #map)
+        }
+    }
+}
+
+fn enum_method(cx: &Cx, variants: &[Variant]) -> (TokenStream, TokenStream) {
+    let p = private();
+    let krate = krate();
+    let ident = cx.ident;
+
+    let mut items = TokenStream::new();
+    let mut arms = Vec::new();
+    let mut default = None;
+    for v in variants {
+        let var = v.ident;
+        let d = v.default_arm.then(|| quote!(0: __xdr_d,));
+        let arm = match v.style {
+            Style::Unit => quote! {
+                #p::serde::de::VariantAccess::unit_variant(__variant)
+                    .map(|()| #ident::#var { #d })
+            },
+            Style::Newtype => {
+                let member = &v.fields[0].member;
+                let (ty, unwrap) = field_type(&v.fields[0]);
+                quote! {
+                    #p::serde::de::VariantAccess::newtype_variant::<#ty>(__variant)
+                        .map(|__v| #ident::#var { #d #member: __v #unwrap })
+                }
+            }
+            Style::Tuple | Style::Struct => {
+                let visitor = format_ident!("__Visitor{}", var);
+                let expecting = format!("variant {ident}::{var}");
+                let mut methods = visit_seq(quote!(#ident::#var), &v.fields);
+                if v.style == Style::Struct {
+                    methods.extend(visit_map(quote!(#ident::#var), &v.fields));
+                }
+                items.extend(cx.visitor(&visitor, &expecting, methods));
+                let new = cx.new_visitor(&visitor);
+                if v.style == Style::Tuple {
+                    let len = v.fields.len();
+                    quote!(#p::serde::de::VariantAccess::tuple_variant(__variant, #len, #new))
+                } else {
+                    let keys = v.fields.iter().map(field_name);
+                    quote! {
+                        #p::serde::de::VariantAccess::struct_variant(
+                            __variant, &[#(#keys),*], #new,
+                        )
+                    }
+                }
+            }
+        };
+        if v.default_arm {
+            default = Some(arm);
+        } else {
+            arms.push((v, arm));
+        }
+    }
+
+    let names = arms.iter().map(|(v, _)| {
+        let name = LitStr::new(&v.ident.to_string(), v.ident.span());
+        let disc = &v.disc;
+        quote!((#name, #disc))
+    });
+    let checks = arms.iter().map(|(v, arm)| {
+        let disc = &v.disc;
+        quote! {
+            if __xdr_d == (#disc) {
+                return #arm;
+            }
+        }
+    });
+    let has_default = default.is_some();
+    let fallback = match default {
+        Some(arm) => quote!(#arm),
+        None => quote! {
+            ::core::unreachable!("discriminant {} was checked by DiscriminantSeed", __xdr_d)
+        },
+    };
+    let method = quote! {
+        fn visit_enum<__A>(self, __data: __A) -> ::core::result::Result<Self::Value, __A::Error>
+        where
+            __A: #p::serde::de::EnumAccess<'de>,
+        {
+            let (__xdr_d, __variant) = #p::serde::de::EnumAccess::variant_seed(
+                __data,
+                #krate::discriminant::DiscriminantSeed {
+                    arms: &[#(#names),*],
+                    default: #has_default,
+                },
+            )?;
+            #(#checks)*
+            #fallback
+        }
+    };
+    (items, method)
+}
@@ -0,0 +1,178 @@
+//! # xdr-serde-derive
+//!
+//! `#[derive(XdrSerialize, XdrDeserialize)]` for [`xdr-serde`]. The derives
+//! generate ordinary `serde::Serialize` / `serde::Deserialize` impls, so the
+//! types work anywhere serde does (self-describing formats see named fields
+//! as a map and variants by name), but they understand XDR-specific
+//! attributes that plain serde derives cannot express:
+//!
+//! | Attribute                       | Applies to      | XDR meaning                          |
+//! |---------------------------------|-----------------|--------------------------------------|
+//! | `#[xdr(discriminant = 5)]`      | enum variant    | explicit enum / union discriminant   |
+//! | `#[xdr(default_arm)]`           | last variant    | the union's `default:` arm           |
+//! | `#[xdr(fixed)]`                 | `[u8; N]` field | `opaque name[N]` (no length prefix)  |
+//! | `#[xdr(opaque)]`                | `Vec<u8>` field | `opaque name<>`                      |
+//! | `#[xdr(max = 255)]`             | field           | `string name<255>` / `T name<255>`   |
+//! | `#[xdr(opaque, max = 255)]`     | `Vec<u8>` field | `opaque name<255>`                   |
+//!
+//! ```rust
+//! use xdr_serde::{from_bytes, to_bytes};
+//! use xdr_serde_derive::{XdrDeserialize, XdrSerialize};
+//!
+//! #[derive(Debug, PartialEq, XdrSerialize, XdrDeserialize)]
+//! struct StateId {
+//!     seqid: u32,
+//!     #[xdr(fixed)]
+//!     other: [u8; 12],
+//! }
+//!
+//! #[derive(Debug, PartialEq, XdrSerialize, XdrDeserialize)]
+//! enum OpenRes {
+//!     #[xdr(discriminant = 0)]
+//!     Ok(StateId),
+//!     #[xdr(discriminant = 2)]
+//!     NoEnt,
+//!     #[xdr(default_arm)]
+//!     Error(i32),
+//! }
+//!
+//! let res = OpenRes::Ok(StateId { seqid: 1, other: [7; 12] });
+//! let bytes = to_bytes(&res).unwrap();
+//! assert_eq!(bytes.len(), 4 + 4 + 12);
+//! assert_eq!(res, from_bytes(&bytes).unwrap());
+//!
+//! assert_eq!(OpenRes::NoEnt.discriminant(), 2);
+//! assert_eq!(from_bytes::<OpenRes>(&[0, 0, 0, 13]).unwrap(), OpenRes::Error(13));
+//! ```
+//!
+//! # Enums
+//!
+//! A variant's discriminant is its `#[xdr(discriminant = ...)]`, or its Rust
+//! discriminant (`Variant = 5`) for a unit variant; any constant `i32`
+//! expression is accepted. A variant without one takes the previous value
+//! plus one, starting at 0. A variant may carry no value (a `void` arm), one
+//! value, or several values encoded one after another, like an inline struct.
+//! Field attributes work inside variants too.
+//!
+//! A `#[xdr(default_arm)]` variant must come last and have the form
+//! `Name(i32)` or `Name(i32, Type)`. The `i32` holds the discriminant that was
+//! actually decoded, so the value re-encodes byte-for-byte.
+//!
+//! `XdrSerialize` also emits a `discriminant(&self) -> i32` accessor for enums.
+//! Unknown discriminants decode as
+//! [`Error::InvalidDiscriminant`](https://docs.rs/xdr-serde/latest/xdr_serde/enum.Error.html).
+//!
+//! # Bounds
+//!
+//! `max` must be a constant `u32` expression. It is checked when serializing
+//! and as soon as the length prefix is decoded.
+//!
+//! # Generics and borrowing
+//!
+//! Type parameters get `Serialize` / `Deserialize` bounds. Lifetime
+//! parameters are allowed, so `&'a str` and `&'a [u8]` fields borrow from the
+//! input when decoding with `from_bytes_partial` or a `Deserializer`.
+//!
+//! [`xdr-serde`]: https://docs.rs/xdr-serde
+
+mod attr;
+mod de;
+mod ser;
+
+use attr::{Body, Container, Field, Variant};
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+use syn::{DeriveInput, Generics, Ident, Member, parse_macro_input};
+
+/// Derive `serde::Serialize` using XDR attributes. See the [crate docs](crate).
+#[proc_macro_derive(XdrSerialize, attributes(xdr))]
+pub fn derive_serialize(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    Container::from_ast(&input)
+        .map(|cont| {
+            let mut tokens = ser::expand(&cont);
+            if let Body::Enum(variants) = &cont.body {
+                tokens.extend(discriminant_fn(&cont, variants));
+            }
+            tokens
+        })
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derive `serde::Deserialize` using XDR attributes. See the [crate docs](crate).
+#[proc_macro_derive(XdrDeserialize, attributes(xdr))]
+pub fn derive_deserialize(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    Container::from_ast(&input)
+        .and_then(|cont| de::expand(&cont))
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+pub(crate) fn krate() -> TokenStream2 {
+    quote!(::xdr_serde)
+}
+
+pub(crate) fn private() -> TokenStream2 {
+    let krate = krate();
+    quote!(#krate::__private)
+}
+
+/// The local variable a field is bound to in patterns and `visit_seq`.
+pub(crate) fn binding(field: &Field) -> Ident {
+    match &field.member {
+        Member::Named(ident) => format_ident!("__xdr_{}", ident.unraw()),
+        Member::Unnamed(index) => format_ident!("__xdr_{}", index.index),
+    }
+}
+
+/// `generics` with `bound` added for every type parameter.
+pub(crate) fn bound_generics(generics: &Generics, bound: TokenStream2) -> Generics {
+    let mut generics = generics.clone();
+    let params: Vec<Ident> = generics.type_params().map(|p| p.ident.clone()).collect();
+    let where_clause = generics.make_where_clause();
+    for param in params {
+        where_clause
+            .predicates
+            .push(syn::parse_quote!(#param: #bound));
+    }
+    generics
+}
+
+/// A pattern matching `v` that binds its wire fields (and, for a default arm,
+/// the discriminant as `__xdr_d`).
+pub(crate) fn variant_pattern(ident: &Ident, v: &Variant) -> TokenStream2 {
+    let var = v.ident;
+    let d = v.default_arm.then(|| quote!(0: __xdr_d,));
+    let members = v.fields.iter().map(|f| &f.member);
+    let binds = v.fields.iter().map(binding);
+    quote!(#ident::#var { #d #(#members: #binds,)* })
+}
+
+fn discriminant_fn(cont: &Container, variants: &[Variant]) -> TokenStream2 {
+    let ident = &cont.input.ident;
+    let (impl_generics, ty_generics, where_clause) = cont.input.generics.split_for_impl();
+    let arms = variants.iter().map(|v| {
+        let var = v.ident;
+        if v.default_arm {
+            quote!(#ident::#var(__xdr_d, ..) => *__xdr_d,)
+        } else {
+            let disc = &v.disc;
+            quote!(#ident::#var { .. } => #disc,)
+        }
+    });
+    quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            /// The XDR discriminant of this value.
+            #[allow(dead_code)]
+            pub fn discriminant(&self) -> i32 {
+                match self {
+                    #(#arms)*
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,156 @@
+//! `#[derive(XdrSerialize)]` expansion.
+
+use crate::attr::{Body, Container, Encoding, Field, Style, Variant};
+use crate::{binding, bound_generics, private};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Ident, LitStr};
+
+pub(crate) fn expand(cont: &Container) -> TokenStream {
+    let p = private();
+    let ident = &cont.input.ident;
+    let name = LitStr::new(&ident.to_string(), ident.span());
+
+    let body = match &cont.body {
+        Body::Struct(style, fields) => serialize_struct(&name, *style, fields),
+        Body::Enum(variants) => serialize_enum(ident, &name, variants),
+    };
+
+    let generics = bound_generics(&cont.input.generics, quote!(#p::serde::Serialize));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    quote! {
+        impl #impl_generics #p::serde::Serialize for #ident #ty_generics #where_clause {
+            fn serialize<__S>(
+                &self,
+                __serializer: __S,
+            ) -> ::core::result::Result<__S::Ok, __S::Error>
+            where
+                __S: #p::serde::Serializer,
+            {
+                #body
+            }
+        }
+    }
+}
+
+/// An expression of type `&impl Serialize` for `field`, given `access` of
+/// type `&FieldType`.
+fn value(field: &Field, access: TokenStream) -> TokenStream {
+    let p = private();
+    match &field.encoding {
+        Encoding::Plain => access,
+        Encoding::Fixed => quote!(&#p::SerFixed(#access)),
+        Encoding::Opaque => quote!(&#p::SerOpaque(#access)),
+        Encoding::Bounded(n) => quote!(&#p::SerBounded::<{ #n }, _>(#access)),
+        Encoding::BoundedOpaque(n) => {
+            quote!(&#p::SerBounded::<{ #n }, _>(&#p::SerOpaque(#access)))
+        }
+    }
+}
+
+fn serialize_struct(name: &LitStr, style: Style, fields: &[Field]) -> TokenStream {
+    let p = private();
+    let accesses = fields.iter().map(|f| {
+        let member = &f.member;
+        quote!(&self.#member)
+    });
+    match style {
+        Style::Unit => quote!(__serializer.serialize_unit_struct(#name)),
+        Style::Newtype => {
+            let v = value(&fields[0], quote!(&self.0));
+            quote!(__serializer.serialize_newtype_struct(#name, #v))
+        }
+        Style::Tuple => {
+            let len = fields.len();
+            let values = fields.iter().zip(accesses).map(|(f, a)| value(f, a));
+            quote! {
+                let mut __state = __serializer.serialize_tuple_struct(#name, #len)?;
+                #(
+                    #p::serde::ser::SerializeTupleStruct::serialize_field(&mut __state, #values)?;
+                )*
+                #p::serde::ser::SerializeTupleStruct::end(__state)
+            }
+        }
+        Style::Struct => {
+            let len = fields.len();
+            let keys = fields.iter().map(field_name);
+            let values = fields.iter().zip(accesses).map(|(f, a)| value(f, a));
+            quote! {
+                let mut __state = __serializer.serialize_struct(#name, #len)?;
+                #(
+                    #p::serde::ser::SerializeStruct::serialize_field(
+                        &mut __state, #keys, #values,
+                    )?;
+                )*
+                #p::serde::ser::SerializeStruct::end(__state)
+            }
+        }
+    }
+}
+
+fn serialize_enum(ident: &Ident, name: &LitStr, variants: &[Variant]) -> TokenStream {
+    let p = private();
+    let arms = variants.iter().map(|v| {
+        let var = v.ident;
+        let var_name = LitStr::new(&var.to_string(), var.span());
+        let pattern = crate::variant_pattern(ident, v);
+        let disc = if v.default_arm {
+            quote!(*__xdr_d as u32)
+        } else {
+            let d = &v.disc;
+            quote!((#d) as i32 as u32)
+        };
+        let values = v.fields.iter().map(|f| {
+            let b = binding(f);
+            value(f, quote!(#b))
+        });
+        let len = v.fields.len();
+        let body = match v.style {
+            Style::Unit => quote!(__serializer.serialize_unit_variant(#name, #disc, #var_name)),
+            Style::Newtype => {
+                let v = value(&v.fields[0], {
+                    let b = binding(&v.fields[0]);
+                    quote!(#b)
+                });
+                quote!(__serializer.serialize_newtype_variant(#name, #disc, #var_name, #v))
+            }
+            Style::Tuple => quote! {
+                let mut __state =
+                    __serializer.serialize_tuple_variant(#name, #disc, #var_name, #len)?;
+                #(
+                    #p::serde::ser::SerializeTupleVariant::serialize_field(&mut __state, #values)?;
+                )*
+                #p::serde::ser::SerializeTupleVariant::end(__state)
+            },
+            Style::Struct => {
+                let keys = v.fields.iter().map(field_name);
+                quote! {
+                    let mut __state =
+                        __serializer.serialize_struct_variant(#name, #disc, #var_name, #len)?;
+                    #(
+                        #p::serde::ser::SerializeStructVariant::serialize_field(
+                            &mut __state, #keys, #values,
+                        )?;
+                    )*
+                    #p::serde::ser::SerializeStructVariant::end(__state)
+                }
+            }
+        };
+        quote!(#pattern => { #body })
+    });
+    quote! {
+        match self {
+            #(#arms)*
+        }
+    }
+}
+
+pub(crate) fn field_name(field: &Field) -> LitStr {
+    match &field.member {
+        syn::Member::Named(ident) => {
+            let s = syn::ext::IdentExt::unraw(ident).to_string();
+            LitStr::new(&s, ident.span())
+        }
+        syn::Member::Unnamed(index) => LitStr::new(&index.index.to_string(), index.span),
+    }
+}
@@ -0,0 +1,299 @@
+use serde::{Deserialize, Serialize};
+use xdr_serde::{Error, from_bytes, from_bytes_partial, from_reader, to_bytes};
+use xdr_serde_derive::{XdrDeserialize, XdrSerialize};
+
+// ══════════════════════════════════════════════════════════════════════════
+// Structs and field attributes
+// ══════════════════════════════════════════════════════════════════════════
+
+#[derive(Debug, PartialEq, XdrSerialize, XdrDeserialize)]
+struct StateId {
+    seqid: u32,
+    #[xdr(fixed)]
+    other: [u8; 12],
+}
+
+const MAXNAMLEN: u32 = 255;
+
+#[derive(Debug, PartialEq, XdrSerialize, XdrDeserialize)]
+struct AuthSys {
+    stamp: u32,
+    #[xdr(max = MAXNAMLEN)]
+    machine_name: String,
+    uid: u32,
+    gid: u32,
+    #[xdr(max = 16)]
+    gids: Vec<u32>,
+    #[xdr(opaque, max = 8)]
+    cookie: Vec<u8>,
+    #[xdr(opaque)]
+    data: Vec<u8>,
+}
+
+fn auth_sys() -> AuthSys {
+    AuthSys {
+        stamp: 1,
+        machine_name: "host".into(),
+        uid: 1000,
+        gid: 100,
+        gids: vec![100, 101],
+        cookie: vec![0xAA; 3],
+        data: vec![1, 2, 3, 4, 5],
+    }
+}
+
+#[test]
+fn test_derive_fixed_opaque() {
+    let id = StateId { seqid: 7, other: [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12] };
+    let bytes = to_bytes(&id).unwrap();
+    assert_eq!(bytes.len(), 16);
+    assert_eq!(&bytes[..4], [0, 0, 0, 7]);
+    assert_eq!(&bytes[4..], id.other);
+    assert_eq!(id, from_bytes(&bytes).unwrap());
+}
+
+#[test]
+fn test_derive_bounded_and_opaque_fields() {
+    let v = auth_sys();
+    let bytes = to_bytes(&v).unwrap();
+    #[rustfmt::skip]
+    assert_eq!(bytes, [
+        0, 0, 0, 1,
+        0, 0, 0, 4, b'h', b'o', b's', b't',
+        0, 0, 0x03, 0xE8,
+        0, 0, 0, 100,
+        0, 0, 0, 2, 0, 0, 0, 100, 0, 0, 0, 101,
+        0, 0, 0, 3, 0xAA, 0xAA, 0xAA, 0,
+        0, 0, 0, 5, 1, 2, 3, 4, 5, 0, 0, 0,
+    ]);
+    assert_eq!(v, from_bytes(&bytes).unwrap());
+    assert_eq!(v, from_reader(std::io::Cursor::new(&bytes)).unwrap());
+}
+
+#[test]
+fn test_derive_bound_rejected_on_encode_and_decode() {
+    let mut v = auth_sys();
+    v.gids = vec![0; 17];
+    assert_eq!(to_bytes(&v), Err(Error::LengthOverflow { max: 16, got: 17 }));
+
+    let mut v = auth_sys();
+    v.cookie = vec![0; 9];
+    assert_eq!(to_bytes(&v), Err(Error::LengthOverflow { max: 8, got: 9 }));
+
+    // Oversized machine_name prefix, rejected before the data is read.
+    let bytes = [0, 0, 0, 1, 0, 0, 1, 0];
+    assert_eq!(from_bytes::<AuthSys>(&bytes), Err(Error::LengthOverflow { max: 255, got: 256 }));
+}
+
+#[derive(Debug, PartialEq, XdrSerialize, XdrDeserialize)]
+struct Handle(#[xdr(opaque, max = 64)] Vec<u8>);
+
+#[derive(Debug, PartialEq, XdrSerialize, XdrDeserialize)]
+struct Pair(u32, #[xdr(fixed)] [u8; 2]);
+
+#[derive(Debug, PartialEq, XdrSerialize, XdrDeserialize)]
+struct Void;
+
+#[test]
+fn test_derive_newtype_tuple_unit_structs() {
+    let h = Handle(vec![9; 5]);
+    let bytes = to_bytes(&h).unwrap();
+    assert_eq!(bytes, [0, 0, 0, 5, 9, 9, 9, 9, 9, 0, 0, 0]);
+    assert_eq!(h, from_bytes(&bytes).unwrap());
+    assert_eq!(from_bytes::<Handle>(&[0, 0, 0, 65]), Err(Error::LengthOverflow { max: 64, got: 65 }));
+
+    let p = Pair(3, [0xAB, 0xCD]);
+    let bytes = to_bytes(&p).unwrap();
+    assert_eq!(bytes, [0, 0, 0, 3, 0xAB, 0xCD, 0, 0]);
+    assert_eq!(p, from_bytes(&bytes).unwrap());
+
+    assert_eq!(to_bytes(&Void).unwrap(), []);
+    assert_eq!(from_bytes::<Void>(&[]).unwrap(), Void);
+}
+
+#[derive(Debug, PartialEq, XdrSerialize, XdrDeserialize)]
+struct Page<T> {
+    #[xdr(max = 4)]
+    items: Vec<T>,
+    eof: bool,
+}
+
+#[test]
+fn test_derive_generic_struct() {
+    let page = Page { items: vec![StateId { seqid: 1, other: [0; 12] }], eof: true };
+    let bytes = to_bytes(&page).unwrap();
+    assert_eq!(bytes.len(), 4 + 16 + 4);
+    assert_eq!(page, from_bytes(&bytes).unwrap());
+
+    let too_many = Page { items: vec![0u32; 5], eof: false };
+    assert_eq!(to_bytes(&too_many), Err(Error::LengthOverflow { max: 4, got: 5 }));
+}
+
+// ══════════════════════════════════════════════════════════════════════════
+// Enums and unions
+// ══════════════════════════════════════════════════════════════════════════
+
+#[derive(Debug, Clone, Copy, PartialEq, XdrSerialize, XdrDeserialize)]
+enum Nfsstat3 {
+    Ok = 0,
+    Perm = 1,
+    NoEnt = 2,
+    Io = 5,
+    Nxio, // 6
+    #[xdr(discriminant = -1)]
+    Negative,
+}
+
+#[test]
+fn test_derive_enum_discriminants() {
+    assert_eq!(to_bytes(&Nfsstat3::Io).unwrap(), [0, 0, 0, 5]);
+    assert_eq!(to_bytes(&Nfsstat3::Nxio).unwrap(), [0, 0, 0, 6]);
+    assert_eq!(to_bytes(&Nfsstat3::Negative).unwrap(), [0xFF; 4]);
+    assert_eq!(Nfsstat3::NoEnt.discriminant(), 2);
+    for v in [Nfsstat3::Ok, Nfsstat3::Perm, Nfsstat3::NoEnt, Nfsstat3::Io, Nfsstat3::Nxio, Nfsstat3::Negative] {
+        assert_eq!(v, from_bytes(&to_bytes(&v).unwrap()).unwrap());
+    }
+    assert_eq!(from_bytes::<Nfsstat3>(&[0, 0, 0, 3]), Err(Error::InvalidDiscriminant(3)));
+}
+
+#[test]
+fn test_derive_enum_matches_xdr_enum_macro() {
+    xdr_serde::xdr_enum! {
+        enum Stat { Ok = 0, Perm = 1, NoEnt = 2, Io = 5, Nxio = 6, Negative = -1 }
+    }
+    assert_eq!(to_bytes(&Stat::Nxio).unwrap(), to_bytes(&Nfsstat3::Nxio).unwrap());
+    assert_eq!(to_bytes(&Stat::Negative).unwrap(), to_bytes(&Nfsstat3::Negative).unwrap());
+}
+
+#[derive(Debug, PartialEq, XdrSerialize, XdrDeserialize)]
+enum OpenRes {
+    #[xdr(discriminant = 0)]
+    Ok(StateId),
+    #[xdr(discriminant = 10)]
+    Named(#[xdr(max = 8)] String),
+    #[xdr(discriminant = 11)]
+    Range { offset: u64, length: u32 },
+    #[xdr(discriminant = 12)]
+    Handles(#[xdr(fixed)] [u8; 4], #[xdr(opaque)] Vec<u8>),
+    #[xdr(discriminant = 2)]
+    NoEnt,
+    #[xdr(default_arm)]
+    Error(i32),
+}
+
+#[test]
+fn test_derive_union_arms() {
+    let ok = OpenRes::Ok(StateId { seqid: 1, other: [7; 12] });
+    let bytes = to_bytes(&ok).unwrap();
+    assert_eq!(&bytes[..4], [0, 0, 0, 0]);
+    assert_eq!(bytes.len(), 20);
+    assert_eq!(ok, from_bytes(&bytes).unwrap());
+
+    let named = OpenRes::Named("abc".into());
+    let bytes = to_bytes(&named).unwrap();
+    assert_eq!(bytes, [0, 0, 0, 10, 0, 0, 0, 3, b'a', b'b', b'c', 0]);
+    assert_eq!(named, from_bytes(&bytes).unwrap());
+    assert_eq!(
+        to_bytes(&OpenRes::Named("too long!".into())),
+        Err(Error::LengthOverflow { max: 8, got: 9 })
+    );
+
+    let range = OpenRes::Range { offset: 1 << 32, length: 9 };
+    let bytes = to_bytes(&range).unwrap();
+    assert_eq!(bytes, [0, 0, 0, 11, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 9]);
+    assert_eq!(range, from_bytes(&bytes).unwrap());
+
+    let handles = OpenRes::Handles([1, 2, 3, 4], vec![5]);
+    let bytes = to_bytes(&handles).unwrap();
+    assert_eq!(bytes, [0, 0, 0, 12, 1, 2, 3, 4, 0, 0, 0, 1, 5, 0, 0, 0]);
+    assert_eq!(handles, from_reader(std::io::Cursor::new(&bytes)).unwrap());
+
+    assert_eq!(to_bytes(&OpenRes::NoEnt).unwrap(), [0, 0, 0, 2]);
+    assert_eq!(OpenRes::Range { offset: 0, length: 0 }.discriminant(), 11);
+}
+
+#[test]
+fn test_derive_default_arm_void() {
+    let bytes = [0, 0, 0, 13];
+    let v: OpenRes = from_bytes(&bytes).unwrap();
+    assert_eq!(v, OpenRes::Error(13));
+    assert_eq!(v.discriminant(), 13);
+    assert_eq!(to_bytes(&v).unwrap(), bytes);
+    // Listed discriminants still take their own arm.
+    assert_eq!(from_bytes::<OpenRes>(&[0, 0, 0, 2]).unwrap(), OpenRes::NoEnt);
+}
+
+#[derive(Debug, PartialEq, XdrSerialize, XdrDeserialize)]
+enum ResOp {
+    #[xdr(discriminant = 3)]
+    Access(u32),
+    #[xdr(default_arm)]
+    Unknown(i32, #[xdr(opaque)] Vec<u8>),
+}
+
+#[test]
+fn test_derive_default_arm_with_body() {
+    let bytes = [0, 0, 0, 99, 0, 0, 0, 2, 0xDE, 0xAD, 0, 0];
+    let v: ResOp = from_bytes(&bytes).unwrap();
+    assert_eq!(v, ResOp::Unknown(99, vec![0xDE, 0xAD]));
+    assert_eq!(to_bytes(&v).unwrap(), bytes);
+    assert_eq!(from_bytes::<ResOp>(&[0, 0, 0, 99, 0, 0]), Err(Error::UnexpectedEof));
+}
+
+// Derived types nest inside plain serde types and vice versa.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Compound {
+    tag: String,
+    results: Vec<ResOp>,
+    status: Nfsstat3,
+}
+
+#[test]
+fn test_derive_nested_in_serde_derive() {
+    let c = Compound {
+        tag: "t".into(),
+        results: vec![ResOp::Access(1), ResOp::Unknown(-5, vec![])],
+        status: Nfsstat3::Perm,
+    };
+    let bytes = to_bytes(&c).unwrap();
+    assert_eq!(c, from_bytes(&bytes).unwrap());
+}
+
+// ══════════════════════════════════════════════════════════════════════════
+// Borrowed fields
+// ══════════════════════════════════════════════════════════════════════════
+
+#[derive(Debug, PartialEq, XdrSerialize, XdrDeserialize)]
+struct DirEntry<'a> {
+    fileid: u64,
+    #[xdr(max = 255)]
+    name: &'a str,
+    next: Option<Box<DirEntry<'a>>>,
+}
+
+#[derive(Debug, PartialEq, XdrSerialize, XdrDeserialize)]
+enum Lookup<'a> {
+    #[xdr(discriminant = 0)]
+    Found(DirEntry<'a>),
+    #[xdr(default_arm)]
+    Failed(i32, &'a str),
+}
+
+#[test]
+fn test_derive_borrowed_fields() {
+    let entry = DirEntry {
+        fileid: 1,
+        name: "a",
+        next: Some(Box::new(DirEntry { fileid: 2, name: "bc", next: None })),
+    };
+    let bytes = to_bytes(&Lookup::Found(entry)).unwrap();
+    let (decoded, rest): (Lookup, _) = from_bytes_partial(&bytes).unwrap();
+    assert!(rest.is_empty());
+    let Lookup::Found(ref e) = decoded else { panic!() };
+    assert_eq!(e.name, "a");
+    // The name points into the input buffer.
+    assert!(bytes.as_ptr_range().contains(&e.name.as_ptr()));
+
+    let bytes = to_bytes(&Lookup::Failed(70, "stale")).unwrap();
+    assert_eq!(from_bytes_partial::<Lookup>(&bytes).unwrap().0, Lookup::Failed(70, "stale"));
+}
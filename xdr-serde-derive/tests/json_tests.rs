@@ -0,0 +1,65 @@
+//! The derived impls through a self-describing format, which sees structs as
+//! maps and enum variants by name.
+
+use xdr_serde_derive::{XdrDeserialize, XdrSerialize};
+
+#[derive(Debug, PartialEq, XdrSerialize, XdrDeserialize)]
+struct StateId {
+    seqid: u32,
+    #[xdr(fixed)]
+    other: [u8; 12],
+}
+
+#[derive(Debug, PartialEq, XdrSerialize, XdrDeserialize)]
+struct AuthSys {
+    stamp: u32,
+    #[xdr(max = 255)]
+    machine_name: String,
+    #[xdr(max = 16)]
+    gids: Vec<u32>,
+    #[xdr(opaque, max = 8)]
+    cookie: Vec<u8>,
+}
+
+#[derive(Debug, PartialEq, XdrSerialize, XdrDeserialize)]
+enum OpenRes {
+    #[xdr(discriminant = 0)]
+    Ok(StateId),
+    #[xdr(discriminant = 10)]
+    Named(#[xdr(max = 8)] String),
+    #[xdr(discriminant = 11)]
+    Range { offset: u64, length: u32 },
+    #[xdr(discriminant = 2)]
+    NoEnt,
+}
+
+
+#[test]
+fn test_derive_roundtrip_through_json() {
+    let auth = AuthSys { stamp: 1, machine_name: "host".into(), gids: vec![100, 101], cookie: vec![0xaa; 3] };
+    let json = serde_json::to_string(&auth).unwrap();
+    assert!(json.starts_with(r#"{"stamp":1,"machine_name":"#), "{json}");
+    assert_eq!(serde_json::from_str::<AuthSys>(&json).unwrap(), auth);
+
+    let range = OpenRes::Range { offset: 1 << 32, length: 9 };
+    let json = serde_json::to_string(&range).unwrap();
+    assert_eq!(json, r#"{"Range":{"offset":4294967296,"length":9}}"#);
+    assert_eq!(serde_json::from_str::<OpenRes>(&json).unwrap(), range);
+
+    let ok = OpenRes::Ok(StateId { seqid: 1, other: [7; 12] });
+    assert_eq!(serde_json::from_str::<OpenRes>(&serde_json::to_string(&ok).unwrap()).unwrap(), ok);
+    let named = OpenRes::Named("abc".into());
+    assert_eq!(serde_json::from_str::<OpenRes>(&serde_json::to_string(&named).unwrap()).unwrap(), named);
+    assert_eq!(serde_json::from_str::<OpenRes>(r#""NoEnt""#).unwrap(), OpenRes::NoEnt);
+}
+
+#[test]
+fn test_derive_map_fields_in_any_order() {
+    let json = r#"{"other":[0,0,0,0,0,0,0,0,0,0,0,2],"unknown":true,"seqid":5}"#;
+    assert_eq!(serde_json::from_str::<StateId>(json).unwrap(), StateId { seqid: 5, other: [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2] });
+
+    let err = serde_json::from_str::<StateId>(r#"{"seqid":5}"#).unwrap_err();
+    assert!(err.to_string().contains("missing field `other`"), "{err}");
+    let err = serde_json::from_str::<StateId>(r#"{"seqid":5,"seqid":6}"#).unwrap_err();
+    assert!(err.to_string().contains("duplicate field `seqid`"), "{err}");
+}